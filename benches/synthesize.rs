@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn long_text() -> String {
+    "This is a sentence which will be repeated, to benchmark long syntheses. ".repeat(50)
+}
+
+fn synthesize(c: &mut Criterion) {
+    let text = long_text();
+    let mut speaker = espeakng::initialise(espeakng::InitOptions::default())
+        .unwrap()
+        .lock();
+
+    c.bench_function("synthesize", |b| {
+        b.iter(|| speaker.synthesize(black_box(&text)).unwrap());
+    });
+
+    c.bench_function("synthesize_to_sink", |b| {
+        b.iter(|| {
+            let mut samples = 0;
+            speaker
+                .synthesize_to_sink(
+                    black_box(&text),
+                    &espeakng::SynthesisOptions::default(),
+                    |audio| samples += audio.len(),
+                )
+                .unwrap();
+
+            samples
+        });
+    });
+}
+
+criterion_group!(benches, synthesize);
+criterion_main!(benches);
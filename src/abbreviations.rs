@@ -0,0 +1,153 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::cache::StableHasher;
+
+/// The punctuation which may surround an abbreviation, such as `(Dr.` or `km/h,`.
+const OPENING_PUNCTUATION: &[char] = &['(', '[', '{', '"', '\'', '“', '‘', '«'];
+const CLOSING_PUNCTUATION: &[char] = &[
+    ',', ';', ':', '!', '?', ')', ']', '}', '"', '\'', '”', '’', '»',
+];
+
+#[rustfmt::skip]
+const ENGLISH: &[(&str, &str)] = &[
+    ("Dr.", "Doctor"), ("Mr.", "Mister"), ("Mrs.", "Missus"), ("Ms.", "Miz"),
+    ("Prof.", "Professor"), ("Jr.", "Junior"), ("Sr.", "Senior"),
+    ("etc.", "et cetera"), ("e.g.", "for example"), ("i.e.", "that is"),
+    ("vs.", "versus"), ("approx.", "approximately"),
+    ("km/h", "kilometres per hour"), ("mph", "miles per hour"),
+];
+
+#[rustfmt::skip]
+const GERMAN: &[(&str, &str)] = &[
+    ("Dr.", "Doktor"), ("Prof.", "Professor"), ("Hr.", "Herr"), ("Fr.", "Frau"),
+    ("z.B.", "zum Beispiel"), ("usw.", "und so weiter"), ("bzw.", "beziehungsweise"),
+    ("ca.", "circa"), ("km/h", "Kilometer pro Stunde"),
+];
+
+#[rustfmt::skip]
+const FRENCH: &[(&str, &str)] = &[
+    ("M.", "Monsieur"), ("Mme", "Madame"), ("Mlle", "Mademoiselle"), ("Dr", "Docteur"),
+    ("etc.", "et cetera"), ("km/h", "kilomètres par heure"),
+];
+
+#[rustfmt::skip]
+const SPANISH: &[(&str, &str)] = &[
+    ("Sr.", "Señor"), ("Sra.", "Señora"), ("Dr.", "Doctor"), ("Dra.", "Doctora"),
+    ("etc.", "etcétera"), ("km/h", "kilómetros por hora"),
+];
+
+/// A table of abbreviations and acronyms to expand before synthesis, such as `Dr.` to `Doctor`,
+/// used by [`crate::Speaker::set_abbreviations`].
+///
+/// Abbreviations are matched case sensitively against whole words, ignoring surrounding brackets,
+/// quotes and punctuation, other than a `.` which is part of the abbreviation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Abbreviations {
+    expansions: HashMap<String, String>,
+}
+
+impl Abbreviations {
+    /// An empty table, for only custom abbreviations.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The default table for `language`, such as `en` or `de-de`, which is empty for languages
+    /// without defaults.
+    ///
+    /// Defaults are included for English, German, French and Spanish.
+    #[must_use]
+    pub fn for_language(language: &str) -> Self {
+        let language = language.split(['-', '_']).next().unwrap_or_default();
+        let defaults = match language.to_ascii_lowercase().as_str() {
+            "en" => ENGLISH,
+            "de" => GERMAN,
+            "fr" => FRENCH,
+            "es" => SPANISH,
+            _ => &[],
+        };
+
+        Self {
+            expansions: defaults
+                .iter()
+                .map(|(abbreviation, expansion)| {
+                    ((*abbreviation).to_owned(), (*expansion).to_owned())
+                })
+                .collect(),
+        }
+    }
+
+    /// Expand `abbreviation` to `expansion`, replacing any previous expansion.
+    pub fn insert(&mut self, abbreviation: impl Into<String>, expansion: impl Into<String>) {
+        self.expansions
+            .insert(abbreviation.into(), expansion.into());
+    }
+
+    /// Stop expanding `abbreviation`, returning its previous expansion.
+    pub fn remove(&mut self, abbreviation: &str) -> Option<String> {
+        self.expansions.remove(abbreviation)
+    }
+
+    /// The expansion of `abbreviation`, if any.
+    #[must_use]
+    pub fn get(&self, abbreviation: &str) -> Option<&str> {
+        self.expansions.get(abbreviation).map(String::as_str)
+    }
+
+    /// Replace each abbreviation in `text` with its expansion.
+    #[must_use]
+    pub fn expand<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.expansions.is_empty() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut expanded = String::new();
+        let mut copied = 0;
+        for (start, word) in words(text) {
+            let unopened = word.trim_start_matches(OPENING_PUNCTUATION);
+            let word_start = start + (word.len() - unopened.len());
+            let abbreviation = unopened.trim_end_matches(CLOSING_PUNCTUATION);
+            if abbreviation.is_empty() {
+                continue;
+            }
+
+            // A full stop may end the sentence, rather than being part of the abbreviation.
+            let abbreviation = match abbreviation.strip_suffix('.') {
+                Some(stripped) if !self.expansions.contains_key(abbreviation) => stripped,
+                _ => abbreviation,
+            };
+
+            if let Some(expansion) = self.get(abbreviation) {
+                expanded.push_str(&text[copied..word_start]);
+                expanded.push_str(expansion);
+                copied = word_start + abbreviation.len();
+            }
+        }
+
+        if copied == 0 {
+            return Cow::Borrowed(text);
+        }
+
+        expanded.push_str(&text[copied..]);
+        Cow::Owned(expanded)
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut StableHasher) {
+        hasher.write_map(&self.expansions);
+    }
+}
+
+/// The byte offset and text of each whitespace separated word.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        let start = position + text[position..].find(|c: char| !c.is_whitespace())?;
+        let end = text[start..]
+            .find(char::is_whitespace)
+            .map_or(text.len(), |len| start + len);
+
+        position = end;
+        Some((start, &text[start..end]))
+    })
+}
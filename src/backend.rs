@@ -0,0 +1,381 @@
+//! The interface between [`Speaker`] and the espeak-ng library, allowing the high-level logic to
+//! run over alternative backends, such as in tests.
+//!
+//! The [Speaker] returned by [`crate::initialise`] always uses [`FfiBackend`], while other backends
+//! are wrapped with [`Speaker::with_backend`].
+
+use std::{ffi::CStr, ops::Range, sync::atomic::Ordering};
+
+use crate::{
+    bindings,
+    error::handle_error,
+    utils::{self, CFile},
+    CallbackTarget, ESpeakNgError, Error, Parameter, PhonemeTraceMode, Result, Speaker,
+    SynthCallbackHandler, SynthesisOptions, TextMode, Voice, CANCEL_SYNTHESIS,
+};
+
+/// The operations of espeak-ng which the backend-independent [Speaker] methods are built on.
+///
+/// Text passed to a backend has already been checked and preprocessed by the [Speaker], such as
+/// with [`Speaker::set_text_filter`], and any overrides of [`SynthesisOptions`] applied.
+///
+/// This trait is public, rather than sealed, so code built on [Speaker] can be tested against a fake
+/// backend without espeak-ng installed, such as the `mock` module. Methods added to it later have
+/// default implementations, so existing backends keep compiling.
+pub trait EspeakBackend: Send {
+    /// Get the value of either the currently set or default value of a settings parameter.
+    fn get_parameter(&mut self, param: Parameter, default: bool) -> i32;
+
+    /// Set a settings parameter, either to `value` or adjusted by `value` if `relative`.
+    ///
+    /// # Errors
+    /// If the parameter could not be set.
+    fn set_parameter(&mut self, param: Parameter, value: i32, relative: bool) -> Result<()>;
+
+    /// List the installed voices.
+    fn list_voices(&mut self) -> Vec<Voice>;
+
+    /// Get the currently set voice, or [`None`] if no voice is set.
+    fn current_voice(&self) -> Option<Voice>;
+
+    /// Set the voice by its filename, such as `gmw/en`, optionally followed by `+` and a variant.
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::VoiceNotFound`] if no voice has the filename.
+    fn set_voice_by_name(&mut self, filename: &str) -> Result<()>;
+
+    /// Get the sample rate of the audio output, in Hz.
+    fn sample_rate(&self) -> u32;
+
+    /// Synthesize `text`, encoded as [`SynthesisOptions::text_mode`], passing the audio and events
+    /// to `handler` as they are generated, and returning once synthesis has completed.
+    ///
+    /// # Errors
+    /// If synthesis fails, or [`Error::Cancelled`] if it was cancelled.
+    fn synthesize(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<()>;
+
+    /// Synthesize `text` as [`EspeakBackend::synthesize`] does, returning the phoneme trace
+    /// written in `mode` during synthesis.
+    ///
+    /// # Errors
+    /// If synthesis fails, or the trace could not be captured.
+    fn synthesize_traced(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        mode: PhonemeTraceMode,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<Vec<u8>>;
+
+    /// Translate `text` into phonemes, with the text and phoneme modes of `espeak_TextToPhonemes`.
+    ///
+    /// # Errors
+    /// If the text could not be passed to the backend, such as if it contains a NUL.
+    fn text_to_phonemes(
+        &mut self,
+        text: &str,
+        text_mode: TextMode,
+        phoneme_mode: u32,
+    ) -> Result<String>;
+
+    /// Translate `text` into phonemes one clause at a time, as espeak does when
+    /// `espeak_TextToPhonemes` is called repeatedly, calling `func` with the byte range within
+    /// `text` and the phonemes of each non-empty clause.
+    ///
+    /// By default, the whole text is translated as one clause with
+    /// [`EspeakBackend::text_to_phonemes`].
+    ///
+    /// # Errors
+    /// If the text could not be passed to the backend, such as if it contains a NUL.
+    fn text_to_phoneme_clauses(
+        &mut self,
+        text: &str,
+        text_mode: TextMode,
+        phoneme_mode: u32,
+        func: &mut dyn FnMut(Range<usize>, &str),
+    ) -> Result<()> {
+        let phonemes = self.text_to_phonemes(text, text_mode, phoneme_mode)?;
+        if !phonemes.is_empty() {
+            func(0..text.len(), &phonemes);
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable the phoneme events passed to [`SynthCallbackHandler::on_event`], with
+    /// the phoneme names either in espeak's notation or IPA.
+    ///
+    /// # Errors
+    /// If the phoneme events could not be changed.
+    fn set_phoneme_events(&mut self, enabled: bool, ipa: bool) -> Result<()>;
+}
+
+/// The backend calling into the espeak-ng C library, only constructed by [`crate::initialise`].
+pub struct FfiBackend {
+    /// The NUL terminated text passed to espeak, reused between calls.
+    text_buffer: Vec<u8>,
+}
+
+impl FfiBackend {
+    pub(crate) fn new() -> Self {
+        Self {
+            text_buffer: Vec::new(),
+        }
+    }
+
+    /// Queue the text for synthesis, which may complete after returning in asynchronous output modes.
+    ///
+    /// `target` must therefore outlive the synthesis.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(text_len = text.len()))
+    )]
+    pub(crate) fn start_synthesis(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        target: *const CallbackTarget<'_>,
+    ) -> Result<()> {
+        // Wide characters need an aligned buffer and a full width NUL terminator.
+        let text_nul_term;
+        let text_wide_nul_term;
+        let (text_ptr, text_size) = if options.text_mode == TextMode::WideChar {
+            text_wide_nul_term = utils::null_term_wide_bytes(text)?;
+            (
+                text_wide_nul_term.as_ptr().cast::<std::ffi::c_void>(),
+                std::mem::size_of_val(text_wide_nul_term.as_slice()),
+            )
+        } else {
+            text_nul_term = utils::null_term_bytes(text)?;
+            (
+                text_nul_term.as_ptr().cast::<std::ffi::c_void>(),
+                text_nul_term.as_bytes_with_nul().len(),
+            )
+        };
+
+        handle_error("espeak_ng_Synthesize", unsafe {
+            bindings::espeak_ng_Synthesize(
+                text_ptr,
+                text_size,
+                options.position,
+                options.position_type as u32,
+                options.end_position,
+                options.text_mode as u32 | options.flags.bits(),
+                std::ptr::null_mut(),
+                target.cast_mut().cast::<std::ffi::c_void>(),
+            )
+        })
+    }
+
+    /// Processes NUL terminated wide characters into phonemes.
+    fn text_to_phonemes_wide(text_nul_term: &[libc::wchar_t], phoneme_mode: u32) -> String {
+        let output = unsafe {
+            CStr::from_ptr(bindings::espeak_TextToPhonemes(
+                &mut text_nul_term.as_ptr().cast() as *mut *const std::ffi::c_void,
+                TextMode::WideChar as i32,
+                phoneme_mode as i32,
+            ))
+        };
+
+        output.to_string_lossy().to_string()
+    }
+}
+
+impl EspeakBackend for FfiBackend {
+    fn get_parameter(&mut self, param: Parameter, default: bool) -> i32 {
+        unsafe { bindings::espeak_GetParameter(param as u32, i32::from(!default)) }
+    }
+
+    fn set_parameter(&mut self, param: Parameter, value: i32, relative: bool) -> Result<()> {
+        handle_error("espeak_ng_SetParameter", unsafe {
+            bindings::espeak_ng_SetParameter(param as u32, value, i32::from(relative))
+        })
+    }
+
+    fn list_voices(&mut self) -> Vec<Voice> {
+        Speaker::get_voices()
+    }
+
+    fn current_voice(&self) -> Option<Voice> {
+        let voice_ptr = unsafe { bindings::espeak_GetCurrentVoice() };
+        if voice_ptr.is_null() {
+            None
+        } else {
+            Some(Voice::from(unsafe { *voice_ptr }))
+        }
+    }
+
+    fn set_voice_by_name(&mut self, filename: &str) -> Result<()> {
+        let mbrola_voice = filename.starts_with("mb/");
+
+        // We have to do our own VoiceNotFound check as espeakNG seems to internally fail at that.
+        if mbrola_voice {
+            let mut voice_path = Speaker::info().data_path;
+            voice_path.push(format!("voices/{filename}"));
+            if !voice_path.exists() {
+                return Err(Error::ESpeakNg {
+                    func: "espeak_ng_SetVoiceByName",
+                    code: ESpeakNgError::VoiceNotFound,
+                    context: None,
+                });
+            }
+        }
+
+        let name_null_term = utils::null_term(filename)?;
+        if mbrola_voice {
+            // Now we are sure the voice is set, we can loop until espeakNG shuts up.
+            while let Err(err) = handle_error("espeak_ng_SetVoiceByName", unsafe {
+                bindings::espeak_ng_SetVoiceByName(name_null_term.as_ptr())
+            }) {
+                if let Error::ESpeakNg {
+                    code: ESpeakNgError::VoiceNotFound,
+                    ..
+                } = err
+                {
+                    continue;
+                }
+
+                return Err(err);
+            }
+        } else {
+            handle_error("espeak_ng_SetVoiceByName", unsafe {
+                bindings::espeak_ng_SetVoiceByName(name_null_term.as_ptr())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn sample_rate(&self) -> u32 {
+        unsafe { bindings::espeak_ng_GetSampleRate() as u32 }
+    }
+
+    fn synthesize(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<()> {
+        let target = CallbackTarget::Handler(std::cell::UnsafeCell::new(handler));
+        self.start_synthesis(text, options, std::ptr::from_ref(&target))?;
+
+        // Wait until TTS has finished being generated, could be made concurrent but global state....
+        handle_error("espeak_ng_Synchronize", unsafe {
+            bindings::espeak_ng_Synchronize()
+        })?;
+
+        if CANCEL_SYNTHESIS.swap(false, Ordering::SeqCst) {
+            return Err(Error::Cancelled);
+        }
+
+        Ok(())
+    }
+
+    fn synthesize_traced(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        mode: PhonemeTraceMode,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<Vec<u8>> {
+        // Capture the trace in a C temporary file, as fd/handle tricks are not portable
+        let mut trace_file = CFile::temporary()?;
+        unsafe { bindings::espeak_SetPhonemeTrace(mode.bits(), trace_file.as_ptr()) };
+
+        let result = self.synthesize(text, options, handler);
+
+        // Reset the phoneme trace back to stdout, to avoid side effects
+        unsafe { bindings::espeak_SetPhonemeTrace(0, std::ptr::null_mut()) };
+
+        result?;
+        trace_file.read_to_end()
+    }
+
+    fn text_to_phonemes(
+        &mut self,
+        text: &str,
+        text_mode: TextMode,
+        phoneme_mode: u32,
+    ) -> Result<String> {
+        if text_mode == TextMode::WideChar {
+            let chars: Vec<char> = text.chars().collect();
+            return Ok(Self::text_to_phonemes_wide(
+                &utils::null_term_wide(&chars)?,
+                phoneme_mode,
+            ));
+        }
+
+        let text_nul_term = utils::null_term(text)?;
+
+        let output = unsafe {
+            CStr::from_ptr(bindings::espeak_TextToPhonemes(
+                &mut text_nul_term.as_ptr().cast() as *mut *const std::ffi::c_void,
+                text_mode as i32,
+                phoneme_mode as i32,
+            ))
+        };
+
+        Ok(output.to_string_lossy().to_string())
+    }
+
+    fn text_to_phoneme_clauses(
+        &mut self,
+        text: &str,
+        text_mode: TextMode,
+        phoneme_mode: u32,
+        func: &mut dyn FnMut(Range<usize>, &str),
+    ) -> Result<()> {
+        let text_nul_term = utils::null_term_into(&mut self.text_buffer, text)?;
+        let text_start = text_nul_term.as_ptr();
+
+        // espeak advances the pointer past each clause, setting it to null at the end of the text.
+        let mut text_ptr: *const std::ffi::c_void = text_start.cast();
+        while !text_ptr.is_null() {
+            let clause_start = unsafe { text_ptr.cast::<libc::c_char>().offset_from(text_start) };
+            let output = unsafe {
+                CStr::from_ptr(bindings::espeak_TextToPhonemes(
+                    std::ptr::from_mut(&mut text_ptr),
+                    text_mode as i32,
+                    phoneme_mode as i32,
+                ))
+            };
+
+            let clause_end = if text_ptr.is_null() {
+                text.len()
+            } else {
+                unsafe { text_ptr.cast::<libc::c_char>().offset_from(text_start) as usize }
+            };
+
+            if !output.is_empty() {
+                func(
+                    clause_start as usize..clause_end.min(text.len()),
+                    &output.to_string_lossy(),
+                );
+            }
+
+            // Guard against espeak failing to advance, which would loop forever.
+            if clause_end as isize <= clause_start {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_phoneme_events(&mut self, enabled: bool, ipa: bool) -> Result<()> {
+        handle_error("espeak_ng_SetPhonemeEvents", unsafe {
+            bindings::espeak_ng_SetPhonemeEvents(i32::from(enabled), i32::from(ipa))
+        })
+    }
+}
+
+impl Drop for FfiBackend {
+    fn drop(&mut self) {
+        unsafe { bindings::espeak_ng_Terminate() };
+    }
+}
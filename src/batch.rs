@@ -0,0 +1,382 @@
+//! Synthesizing many texts in parallel, such as rendering a set of prompts ahead of time.
+//!
+//! espeak-ng keeps its state in global variables, so only one synthesis can run in a process at a
+//! time. A [`WorkerPool`] instead runs several worker processes, each with its own espeak-ng, and
+//! distributes the texts between them. The workers are the `espeakng-cli` binary, built with the
+//! `cli` feature, running its `worker` command, or any other program which calls [`serve_worker`].
+//!
+//! ```no_run
+//! let prompts = ["Welcome", "Please hold", "Goodbye"];
+//! let results = espeakng::batch::synthesize(&prompts, &espeakng::SynthesisOptions::default())?;
+//!
+//! for (prompt, audio) in prompts.iter().zip(results) {
+//!     println!("{prompt}: {} samples", audio?.len());
+//! }
+//! # Ok::<(), espeakng::Error>(())
+//! ```
+
+use std::{
+    ffi::{OsStr, OsString},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    num::NonZeroUsize,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    backend::EspeakBackend, utils, ESpeakNgError, Error, Pitch, PositionType, Result, Speaker,
+    SynthFlags, SynthesisOptions, TextMode, Volume, WordsPerMinute,
+};
+
+/// The environment variable naming the worker program used by [`synthesize`].
+pub const WORKER_PROGRAM_VAR: &str = "ESPEAKNG_CLI";
+
+/// Synthesize each of `texts` with `options` on a new [`WorkerPool`] of
+/// [`WorkerPool::default_program`], with a worker per CPU, returning the results in the same order
+/// as the texts.
+///
+/// A text which fails to synthesize does not stop the rest of the batch.
+///
+/// # Errors
+/// If the workers could not be started, see [`WorkerPool::spawn`].
+pub fn synthesize<T: AsRef<str> + Sync>(
+    texts: &[T],
+    options: &SynthesisOptions,
+) -> Result<Vec<Result<Vec<i16>>>> {
+    let count = std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(texts.len());
+
+    let mut pool = WorkerPool::spawn(WorkerPool::default_program(), count)?;
+    Ok(pool.synthesize(texts, options))
+}
+
+/// Worker processes which each synthesize one text at a time, see the [module docs](self).
+///
+/// The workers are stopped when the pool is dropped.
+pub struct WorkerPool {
+    workers: Vec<Worker>,
+}
+
+impl WorkerPool {
+    /// The worker program used by [`synthesize`], `$ESPEAKNG_CLI` if set, otherwise `espeakng-cli`
+    /// found on the `PATH`.
+    #[must_use]
+    pub fn default_program() -> OsString {
+        std::env::var_os(WORKER_PROGRAM_VAR).unwrap_or_else(|| OsString::from("espeakng-cli"))
+    }
+
+    /// Start `count` workers, at least one, each running `program worker`, such as
+    /// `espeakng-cli worker`.
+    ///
+    /// # Errors
+    /// If a worker process could not be started.
+    pub fn spawn(program: impl AsRef<OsStr>, count: usize) -> Result<Self> {
+        let workers = (0..count.max(1))
+            .map(|_| Worker::spawn(program.as_ref()))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { workers })
+    }
+
+    /// Synthesize each of `texts` with `options`, distributing them between the workers, returning
+    /// the results in the same order as the texts.
+    ///
+    /// A text which fails to synthesize does not stop the rest of the batch. If a worker stops,
+    /// the text it was synthesizing fails with [`Error::WorkerDisconnected`], and the remaining
+    /// texts are synthesized by the other workers.
+    pub fn synthesize<T: AsRef<str> + Sync>(
+        &mut self,
+        texts: &[T],
+        options: &SynthesisOptions,
+    ) -> Vec<Result<Vec<i16>>> {
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<Option<Result<Vec<i16>>>> =
+            std::iter::repeat_with(|| None).take(texts.len()).collect();
+
+        std::thread::scope(|scope| {
+            let threads: Vec<_> = self
+                .workers
+                .iter_mut()
+                .map(|worker| {
+                    let next = &next;
+                    scope.spawn(move || {
+                        let mut completed = Vec::new();
+                        while worker.running {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(text) = texts.get(index) else {
+                                break;
+                            };
+
+                            completed.push((index, worker.synthesize(text.as_ref(), options)));
+                        }
+
+                        completed
+                    })
+                })
+                .collect();
+
+            for thread in threads {
+                for (index, result) in thread.join().unwrap_or_default() {
+                    results[index] = Some(result);
+                }
+            }
+        });
+
+        // Any texts left over were not started, as every worker had stopped.
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or(Err(Error::WorkerDisconnected)))
+            .collect()
+    }
+}
+
+struct Worker {
+    child: Child,
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    /// Cleared once the process has stopped responding, so no more texts are sent to it.
+    running: bool,
+}
+
+impl Worker {
+    fn spawn(program: &OsStr) -> Result<Self> {
+        let mut child = Command::new(program)
+            .arg("worker")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or(Error::WorkerDisconnected)?;
+        let stdout = child.stdout.take().ok_or(Error::WorkerDisconnected)?;
+        Ok(Self {
+            child,
+            stdin: BufWriter::new(stdin),
+            stdout: BufReader::new(stdout),
+            running: true,
+        })
+    }
+
+    fn synthesize(&mut self, text: &str, options: &SynthesisOptions) -> Result<Vec<i16>> {
+        let response = write_request(&mut self.stdin, text, options)
+            .and_then(|()| self.stdin.flush())
+            .and_then(|()| read_response(&mut self.stdout));
+
+        response.unwrap_or_else(|_| {
+            self.running = false;
+            let _ = self.child.kill();
+            Err(Error::WorkerDisconnected)
+        })
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        // The worker is idle between batches, so nothing is lost by killing it.
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Synthesize the texts sent by a [`WorkerPool`] on `input`, writing the results to `output`, until
+/// `input` is closed. This is run by the `worker` command of `espeakng-cli`.
+///
+/// # Errors
+/// If reading a request or writing a response fails. Failing to synthesize a text is reported to
+/// the pool instead.
+pub fn serve_worker<B: EspeakBackend>(
+    speaker: &mut Speaker<B>,
+    input: impl Read,
+    output: impl Write,
+) -> Result<()> {
+    let mut input = BufReader::new(input);
+    let mut output = BufWriter::new(output);
+
+    while let Some(request) = read_request(&mut input)? {
+        let result = request
+            .options(speaker)
+            .and_then(|options| speaker.synthesize_with_options(&request.text, &options));
+
+        write_response(&mut output, &result)?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+/// A text and the [`SynthesisOptions`] to synthesize it with, as sent to a worker.
+struct Request {
+    position: u32,
+    position_type: u32,
+    end_position: u32,
+    text_mode: u32,
+    flags: u32,
+    /// The rate, pitch and volume, or -1 if not overridden.
+    parameters: [i32; 3],
+    /// The filename of the voice, or empty if not overridden.
+    voice: String,
+    text: String,
+}
+
+impl Request {
+    /// Rebuild the options, looking the voice up by its filename.
+    fn options<B: EspeakBackend>(&self, speaker: &mut Speaker<B>) -> Result<SynthesisOptions> {
+        let invalid = || Error::Io(std::io::Error::from(ErrorKind::InvalidData));
+        let position_type = match self.position_type {
+            1 => PositionType::Character,
+            2 => PositionType::Word,
+            3 => PositionType::Sentence,
+            _ => return Err(invalid()),
+        };
+
+        let text_mode = match self.text_mode {
+            0 => TextMode::Auto,
+            1 => TextMode::Utf8,
+            2 => TextMode::EightBit,
+            3 => TextMode::WideChar,
+            _ => return Err(invalid()),
+        };
+
+        let [rate, pitch, volume] = self.parameters;
+        let voice = if self.voice.is_empty() {
+            None
+        } else {
+            let voice = speaker
+                .voices()
+                .iter()
+                .find(|voice| voice.filename == self.voice)
+                .cloned();
+
+            Some(voice.ok_or(Error::ESpeakNg {
+                func: "espeak_ng_SetVoiceByName",
+                code: ESpeakNgError::VoiceNotFound,
+                context: None,
+            })?)
+        };
+
+        Ok(SynthesisOptions {
+            position: self.position,
+            position_type,
+            end_position: self.end_position,
+            text_mode,
+            flags: SynthFlags::from_bits_truncate(self.flags),
+            capacity_hint: None,
+            rate: u16::try_from(rate)
+                .ok()
+                .map(WordsPerMinute::new)
+                .transpose()?,
+            pitch: u8::try_from(pitch).ok().map(Pitch::new).transpose()?,
+            volume: u8::try_from(volume).ok().map(Volume::new).transpose()?,
+            voice,
+        })
+    }
+}
+
+fn write_request(
+    writer: &mut impl Write,
+    text: &str,
+    options: &SynthesisOptions,
+) -> std::io::Result<()> {
+    for value in [
+        options.position,
+        options.position_type as u32,
+        options.end_position,
+        options.text_mode as u32,
+        options.flags.bits(),
+    ] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+
+    for value in [
+        options.rate.map(|rate| i32::from(rate.get())),
+        options.pitch.map(|pitch| i32::from(pitch.get())),
+        options.volume.map(|volume| i32::from(volume.get())),
+    ] {
+        writer.write_all(&value.unwrap_or(-1).to_le_bytes())?;
+    }
+
+    let voice = options.voice.as_ref().map_or("", |voice| &voice.filename);
+    write_bytes(writer, voice.as_bytes())?;
+    write_bytes(writer, text.as_bytes())
+}
+
+/// Read the next request, or [`None`] if the pool has closed `reader`.
+fn read_request(reader: &mut impl Read) -> Result<Option<Request>> {
+    let mut position = [0; 4];
+    match reader.read_exact(&mut position) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(Some(Request {
+        position: u32::from_le_bytes(position),
+        position_type: read_u32(reader)?,
+        end_position: read_u32(reader)?,
+        text_mode: read_u32(reader)?,
+        flags: read_u32(reader)?,
+        parameters: [
+            read_u32(reader)? as i32,
+            read_u32(reader)? as i32,
+            read_u32(reader)? as i32,
+        ],
+        voice: String::from_utf8(read_bytes(reader)?)?,
+        text: String::from_utf8(read_bytes(reader)?)?,
+    }))
+}
+
+fn write_response(writer: &mut impl Write, result: &Result<Vec<i16>>) -> std::io::Result<()> {
+    match result {
+        Ok(audio) => {
+            writer.write_all(&[0])?;
+            writer.write_all(&(audio.len() as u64).to_le_bytes())?;
+            utils::write_samples_le(writer, audio)
+        }
+        Err(err) => {
+            writer.write_all(&[1])?;
+            write_bytes(writer, err.to_string().as_bytes())
+        }
+    }
+}
+
+/// Read the result of a request, failing if the worker has stopped.
+fn read_response(reader: &mut impl Read) -> std::io::Result<Result<Vec<i16>>> {
+    let mut status = [0];
+    reader.read_exact(&mut status)?;
+
+    if status[0] != 0 {
+        let message = String::from_utf8_lossy(&read_bytes(reader)?).into_owned();
+        return Ok(Err(Error::WorkerFailed(message)));
+    }
+
+    let mut samples = vec![0; read_len(reader)? * 2];
+    reader.read_exact(&mut samples)?;
+
+    Ok(Ok(samples
+        .chunks_exact(2)
+        .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+        .collect()))
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> std::io::Result<()> {
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut bytes = vec![0; read_len(reader)?];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_len(reader: &mut impl Read) -> std::io::Result<usize> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    usize::try_from(u64::from_le_bytes(bytes)).map_err(|_| ErrorKind::InvalidData.into())
+}
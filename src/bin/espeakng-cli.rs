@@ -0,0 +1,99 @@
+//! A command line interface to the safe espeakNG wrapper, enabled with the `cli` feature.
+
+use std::io::Write;
+
+const USAGE: &str = "\
+Usage: espeakng-cli [--voice <filename>] <command>
+
+Commands:
+    speak <text>             Speak the text through the audio device
+    phonemes [--ipa] <text>  Print the phonemes of the text
+    voices                   List the installed voices
+    wav <text> <path>        Write the spoken text to a WAV file
+    worker                   Synthesize texts for an espeakng::batch::WorkerPool over stdin";
+
+fn main() {
+    if let Err(err) = run(std::env::args().skip(1).collect()) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+fn run(mut args: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut options = espeakng::InitOptions::new();
+    if args.first().is_some_and(|arg| arg == "--voice") {
+        let voice = args.get(1).ok_or(USAGE)?.clone();
+        options = options.default_voice(voice);
+        args.drain(..2);
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["speak", text] => {
+            options = options.output_mode(espeakng::OutputMode::SynchronousPlayback);
+            let mut speaker = espeakng::initialise_and_lock(options)?;
+            speaker.synthesize(text)?;
+        }
+        ["phonemes", rest @ ..] => {
+            let (ipa, text) = match rest {
+                ["--ipa", text] => (true, text),
+                [text] => (false, text),
+                _ => return Err(USAGE.into()),
+            };
+
+            let text_mode = espeakng::TextMode::default();
+            let phoneme_mode = espeakng::PhonemeMode::default();
+            let gen_options = if ipa {
+                espeakng::PhonemeGenOptions::Ipa {
+                    text_mode,
+                    phoneme_mode,
+                }
+            } else {
+                espeakng::PhonemeGenOptions::Standard {
+                    text_mode,
+                    phoneme_mode,
+                }
+            };
+
+            let mut speaker = espeakng::initialise_and_lock(options)?;
+            let phonemes = speaker.text_to_phonemes(text, gen_options)?;
+            println!("{}", phonemes.unwrap_or_default());
+        }
+        ["voices"] => {
+            espeakng::initialise(options)?;
+
+            let mut stdout = std::io::stdout().lock();
+            for voice in espeakng::Speaker::get_voices() {
+                let languages: Vec<&str> =
+                    voice.languages.iter().map(|l| l.name.as_str()).collect();
+                writeln!(
+                    stdout,
+                    "{}\t{}\t{}",
+                    voice.filename,
+                    voice.name,
+                    languages.join(",")
+                )?;
+            }
+        }
+        ["wav", text, path] => {
+            let mut speaker = espeakng::initialise_and_lock(options)?;
+            let audio = speaker.synthesize(text)?;
+            let sample_rate = speaker.sample_rate();
+
+            let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+            espeakng::write_wav(&mut file, sample_rate, &audio)?;
+            file.flush()?;
+        }
+        ["worker"] => {
+            let mut speaker = espeakng::initialise_and_lock(options)?;
+            espeakng::batch::serve_worker(
+                &mut speaker,
+                std::io::stdin().lock(),
+                std::io::stdout().lock(),
+            )?;
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
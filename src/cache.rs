@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{ParameterProfile, Voice};
+
+/// What was generated for a [`CacheKey`], as phoneme generation is cached alongside audio.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum CacheOutput {
+    Audio,
+    /// Phonemes generated with the given `espeak_TextToPhonemes` text and phoneme modes.
+    Phonemes {
+        text_mode: u32,
+        phoneme_mode: u32,
+    },
+    /// The phonemes of every clause, joined by spaces, with the given text and phoneme modes.
+    ClausePhonemes {
+        text_mode: u32,
+        phoneme_mode: u32,
+    },
+}
+
+/// Everything which changes the output of synthesis or phoneme generation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    pub(crate) output: CacheOutput,
+    pub(crate) voice: Option<Voice>,
+    pub(crate) parameters: ParameterProfile,
+    /// A [`StableHasher`] fingerprint of the state outside the key which changes the output, such
+    /// as text preprocessing, so stored entries generated with another configuration are missed.
+    pub(crate) preprocessing: u64,
+    pub(crate) text: String,
+}
+
+impl CacheKey {
+    /// Encode the key as bytes which are stable between runs, for [`crate::DiskCache`].
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        fn push_str(bytes: &mut Vec<u8>, text: &str) {
+            bytes.extend_from_slice(&(text.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(text.as_bytes());
+        }
+
+        let mut bytes = Vec::with_capacity(self.text.len() + 128);
+        match self.output {
+            CacheOutput::Audio => bytes.push(0),
+            CacheOutput::Phonemes {
+                text_mode,
+                phoneme_mode,
+            } => {
+                bytes.push(1);
+                bytes.extend_from_slice(&text_mode.to_le_bytes());
+                bytes.extend_from_slice(&phoneme_mode.to_le_bytes());
+            }
+            CacheOutput::ClausePhonemes {
+                text_mode,
+                phoneme_mode,
+            } => {
+                bytes.push(2);
+                bytes.extend_from_slice(&text_mode.to_le_bytes());
+                bytes.extend_from_slice(&phoneme_mode.to_le_bytes());
+            }
+        }
+
+        if let Some(voice) = &self.voice {
+            bytes.push(1);
+            push_str(&mut bytes, &voice.name);
+            push_str(&mut bytes, &voice.filename);
+            bytes.extend_from_slice(&(voice.languages.len() as u64).to_le_bytes());
+            for language in &voice.languages {
+                push_str(&mut bytes, &language.name);
+                bytes.extend_from_slice(&language.priority.to_le_bytes());
+            }
+
+            bytes.push(voice.gender.map_or(0, |gender| gender as u8));
+            bytes.push(voice.age);
+        } else {
+            bytes.push(0);
+        }
+
+        for (_, value) in self.parameters.iter() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&self.preprocessing.to_le_bytes());
+        push_str(&mut bytes, &self.text);
+        bytes
+    }
+}
+
+/// The 64 bit FNV-1a hash, which unlike [`std::hash::DefaultHasher`] is stable between runs and
+/// Rust versions, as the keys and file names of [`crate::DiskCache`] entries must be.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Write `text` prefixed by its length, so adjacent strings cannot run together.
+    pub(crate) fn write_str(&mut self, text: &str) {
+        self.write_u64(text.len() as u64);
+        self.write(text.as_bytes());
+    }
+
+    /// Write whether `value` is set, then `value` itself with `write`.
+    pub(crate) fn write_option<T>(&mut self, value: Option<&T>, write: impl FnOnce(&T, &mut Self)) {
+        match value {
+            Some(value) => {
+                self.write(&[1]);
+                write(value, self);
+            }
+            None => self.write(&[0]),
+        }
+    }
+
+    /// Write the entries of `map` in sorted order, as the iteration order of a [`HashMap`] is random.
+    pub(crate) fn write_map(&mut self, map: &HashMap<String, String>) {
+        let mut entries: Vec<_> = map.iter().collect();
+        entries.sort_unstable();
+
+        self.write_u64(entries.len() as u64);
+        for (key, value) in entries {
+            self.write_str(key);
+            self.write_str(value);
+        }
+    }
+
+    /// Write the entries of `set` in sorted order, see [`StableHasher::write_map`].
+    pub(crate) fn write_set(&mut self, set: &HashSet<String>) {
+        let mut entries: Vec<_> = set.iter().collect();
+        entries.sort_unstable();
+
+        self.write_u64(entries.len() as u64);
+        for entry in entries {
+            self.write_str(entry);
+        }
+    }
+
+    pub(crate) fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum CachedValue {
+    Audio(Vec<i16>),
+    Phonemes(String),
+}
+
+impl CachedValue {
+    fn len(&self) -> usize {
+        match self {
+            Self::Audio(audio) => std::mem::size_of_val(audio.as_slice()),
+            Self::Phonemes(phonemes) => phonemes.len(),
+        }
+    }
+}
+
+/// An in-memory cache of synthesized audio and phonemes, evicting the least recently used
+/// entries, used by [`crate::Speaker::set_synthesis_cache`].
+///
+/// Entries are keyed on the current voice, the values of [`ParameterProfile::PARAMETERS`] and the
+/// text, so changing either misses the cache rather than returning stale output. Changing text
+/// preprocessing or dictionaries clears the cache.
+#[derive(Debug)]
+pub struct SynthesisCache {
+    capacity: usize,
+    max_bytes: Option<usize>,
+    bytes: usize,
+    entries: HashMap<CacheKey, (u64, CachedValue)>,
+    /// The keys of `entries`, by when they were last used.
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl SynthesisCache {
+    /// Create a cache holding up to `capacity` results.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            max_bytes: None,
+            bytes: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Also limit the total size of the cached audio and phonemes, in bytes.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// The number of cached results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of lookups which returned a cached result.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of lookups which had to synthesize.
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Remove every cached result.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.bytes = 0;
+    }
+
+    pub(crate) fn get(&mut self, key: &CacheKey) -> Option<CachedValue> {
+        self.tick += 1;
+        let Some((last_used, value)) = self.entries.get_mut(key) else {
+            self.misses += 1;
+            return None;
+        };
+
+        let key = self
+            .recency
+            .remove(last_used)
+            .expect("every entry should have a recency");
+
+        *last_used = self.tick;
+        self.recency.insert(self.tick, key);
+
+        self.hits += 1;
+        Some(value.clone())
+    }
+
+    pub(crate) fn insert(&mut self, key: CacheKey, value: CachedValue) {
+        let len = value.len();
+        if self.capacity == 0 || self.max_bytes.is_some_and(|max_bytes| len > max_bytes) {
+            return;
+        }
+
+        self.tick += 1;
+        self.bytes += len;
+        if let Some((last_used, replaced)) = self.entries.insert(key.clone(), (self.tick, value)) {
+            self.recency.remove(&last_used);
+            self.bytes -= replaced.len();
+        }
+
+        self.recency.insert(self.tick, key);
+        while self.entries.len() > self.capacity
+            || self
+                .max_bytes
+                .is_some_and(|max_bytes| self.bytes > max_bytes)
+        {
+            self.evict_oldest();
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        let Some((_, key)) = self.recency.pop_first() else {
+            return;
+        };
+
+        if let Some((_, value)) = self.entries.remove(&key) {
+            self.bytes -= value.len();
+        }
+    }
+}
+
+/// A result which can be stored in a [`SynthesisCache`].
+pub(crate) trait Cacheable: Clone + Sized {
+    fn into_value(self) -> CachedValue;
+    fn from_value(value: CachedValue) -> Option<Self>;
+}
+
+impl Cacheable for Vec<i16> {
+    fn into_value(self) -> CachedValue {
+        CachedValue::Audio(self)
+    }
+
+    fn from_value(value: CachedValue) -> Option<Self> {
+        match value {
+            CachedValue::Audio(audio) => Some(audio),
+            CachedValue::Phonemes(_) => None,
+        }
+    }
+}
+
+impl Cacheable for String {
+    fn into_value(self) -> CachedValue {
+        CachedValue::Phonemes(self)
+    }
+
+    fn from_value(value: CachedValue) -> Option<Self> {
+        match value {
+            CachedValue::Phonemes(phonemes) => Some(phonemes),
+            CachedValue::Audio(_) => None,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{bindings, utils::StringFromCPtr};
+
+/// Receives the output of [`crate::Speaker::synthesize_with_handler`] as espeak generates it.
+///
+/// The handler is called from within the C synth callback, with panics caught and pointers
+/// validated by this crate. Implemented for any `FnMut(&[i16])`, which only receives audio.
+pub trait SynthCallbackHandler: Send {
+    /// Called with each chunk of generated audio, which may be empty.
+    fn on_audio(&mut self, audio: &[i16]);
+
+    /// Called with each event espeak generates, after the audio of the same chunk.
+    ///
+    /// Phoneme events are only generated if enabled with [`crate::InitOptions::phoneme_events`].
+    fn on_event(&mut self, event: RawEvent) {
+        let _ = event;
+    }
+}
+
+impl<F: FnMut(&[i16]) + Send> SynthCallbackHandler for F {
+    fn on_audio(&mut self, audio: &[i16]) {
+        self(audio);
+    }
+}
+
+/// The type of a [`RawEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventType {
+    /// The start of a word.
+    Word,
+    /// The start of a sentence.
+    Sentence,
+    /// An SSML `<mark>` element.
+    Mark,
+    /// An SSML `<audio>` element.
+    Play,
+    /// The end of a sentence or clause.
+    End,
+    /// The end of the synthesis call.
+    MsgTerminated,
+    /// A phoneme, if phoneme events are enabled.
+    Phoneme,
+    /// The sample rate of the following audio.
+    SampleRate,
+    /// An event type not known to this library.
+    Unknown(u32),
+}
+
+/// The identifier carried by a [`RawEvent`], depending on its [`EventType`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventId {
+    /// The word or sentence number, or the sample rate.
+    Number(i32),
+    /// The name of an SSML mark or audio element.
+    Name(String),
+    /// The phoneme mnemonic.
+    Phoneme(String),
+    /// The event carries no identifier.
+    None,
+}
+
+/// An event generated by espeak during synthesis, copied out of the C event list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawEvent {
+    pub event_type: EventType,
+    /// The 1-based character position of the event in the input text.
+    pub text_position: i32,
+    /// The length of the word or phoneme in characters, for word and phoneme events.
+    pub length: i32,
+    /// The time within the generated audio, in milliseconds.
+    pub audio_position: i32,
+    pub id: EventId,
+}
+
+impl RawEvent {
+    pub(crate) fn from_raw(event: &bindings::espeak_EVENT) -> Self {
+        let event_type = match event.type_ {
+            bindings::espeak_EVENT_TYPE_espeakEVENT_WORD => EventType::Word,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_SENTENCE => EventType::Sentence,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_MARK => EventType::Mark,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_PLAY => EventType::Play,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_END => EventType::End,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_MSG_TERMINATED => EventType::MsgTerminated,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_PHONEME => EventType::Phoneme,
+            bindings::espeak_EVENT_TYPE_espeakEVENT_SAMPLERATE => EventType::SampleRate,
+            other => EventType::Unknown(other),
+        };
+
+        let id = match event_type {
+            EventType::Word | EventType::Sentence | EventType::SampleRate => {
+                EventId::Number(unsafe { event.id.number })
+            }
+            EventType::Mark | EventType::Play => {
+                let name = unsafe { event.id.name };
+                if name.is_null() {
+                    EventId::None
+                } else {
+                    EventId::Name(unsafe { String::from_cptr(name) })
+                }
+            }
+            EventType::Phoneme => EventId::Phoneme(crate::phoneme::event_phoneme_name(event)),
+            EventType::End | EventType::MsgTerminated | EventType::Unknown(_) => EventId::None,
+        };
+
+        Self {
+            event_type,
+            text_position: event.text_position,
+            length: event.length,
+            audio_position: event.audio_position,
+            id,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Locating the `espeak-ng-data` directory, mirroring the search espeak performs on initialisation.
+
+use std::path::{Path, PathBuf};
+
+/// The environment variable espeak reads the data path from.
+pub const ENV_VAR: &str = "ESPEAK_DATA_PATH";
+
+/// The directory name espeak looks for within each location.
+const DATA_DIR_NAME: &str = "espeak-ng-data";
+
+/// The locations espeak-ng is commonly installed to.
+#[cfg(windows)]
+const SYSTEM_LOCATIONS: &[&str] = &[
+    "C:\\Program Files\\eSpeak NG",
+    "C:\\Program Files (x86)\\eSpeak NG",
+];
+#[cfg(not(windows))]
+const SYSTEM_LOCATIONS: &[&str] = &[
+    "/usr/share",
+    "/usr/local/share",
+    "/usr/lib/x86_64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+    "/opt/homebrew/share",
+];
+
+/// Where a data directory was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataPathSource {
+    /// The path passed to [`discover`], such as [`crate::InitOptions::data_path`].
+    Explicit,
+    /// The [`ENV_VAR`] environment variable.
+    Environment,
+    /// The home directory of the current user.
+    Home,
+    /// A common system install location.
+    System,
+}
+
+/// The outcome of [`discover`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataPathReport {
+    /// The data directory which was found, and where it came from.
+    pub found: Option<(PathBuf, DataPathSource)>,
+    /// Every directory checked, in order, including the one found.
+    pub searched: Vec<PathBuf>,
+}
+
+/// Whether `path` is a data directory, checked by the presence of the `phontab` file.
+#[must_use]
+pub fn is_data_dir(path: &Path) -> bool {
+    path.join("phontab").is_file()
+}
+
+/// Search for the `espeak-ng-data` directory, checking `explicit`, the [`ENV_VAR`] environment
+/// variable, the home directory, then common system install locations.
+///
+/// Each location may either be the data directory itself, or contain an `espeak-ng-data` directory.
+///
+/// Only `explicit` and [`ENV_VAR`] are passed to espeak on initialisation, see [`discover_configured`].
+/// The other locations are guesses, used only if espeak fails to initialise from the prefix it was
+/// compiled with, see [`discover_installed`].
+#[must_use]
+pub fn discover(explicit: Option<&Path>) -> DataPathReport {
+    search(configured_locations(explicit).chain(installed_locations()))
+}
+
+/// Search for the `espeak-ng-data` directory in the locations the user configured, `explicit` then
+/// the [`ENV_VAR`] environment variable.
+#[must_use]
+pub fn discover_configured(explicit: Option<&Path>) -> DataPathReport {
+    search(configured_locations(explicit))
+}
+
+/// Search for the `espeak-ng-data` directory in the home directory, then common system install
+/// locations.
+#[must_use]
+pub fn discover_installed() -> DataPathReport {
+    search(installed_locations())
+}
+
+fn configured_locations(
+    explicit: Option<&Path>,
+) -> impl Iterator<Item = (PathBuf, DataPathSource)> {
+    let env_path = std::env::var_os(ENV_VAR).map(PathBuf::from);
+
+    [
+        (explicit.map(Path::to_path_buf), DataPathSource::Explicit),
+        (env_path, DataPathSource::Environment),
+    ]
+    .into_iter()
+    .filter_map(|(path, source)| Some((path?, source)))
+}
+
+fn installed_locations() -> impl Iterator<Item = (PathBuf, DataPathSource)> {
+    let home_path =
+        std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).map(PathBuf::from);
+
+    home_path
+        .map(|path| (path, DataPathSource::Home))
+        .into_iter()
+        .chain(
+            SYSTEM_LOCATIONS
+                .iter()
+                .map(|path| (PathBuf::from(path), DataPathSource::System)),
+        )
+}
+
+fn search(locations: impl Iterator<Item = (PathBuf, DataPathSource)>) -> DataPathReport {
+    let mut searched = Vec::new();
+    for (location, source) in locations {
+        // The home directory and system locations only ever contain the data directory.
+        let direct = matches!(
+            source,
+            DataPathSource::Explicit | DataPathSource::Environment
+        )
+        .then(|| location.clone());
+
+        for candidate in direct.into_iter().chain([location.join(DATA_DIR_NAME)]) {
+            let found = is_data_dir(&candidate);
+            searched.push(candidate.clone());
+
+            if found {
+                return DataPathReport {
+                    found: Some((candidate, source)),
+                    searched,
+                };
+            }
+        }
+    }
+
+    DataPathReport {
+        found: None,
+        searched,
+    }
+}
@@ -0,0 +1,48 @@
+use crate::Voice;
+
+/// Convert an ISO 639-3 code from whatlang into the code used by espeak voices.
+///
+/// espeak uses ISO 639-1 codes where they exist, falling back to ISO 639-3 (such as `cmn`).
+fn espeak_language_code(lang: whatlang::Lang) -> &'static str {
+    #[rustfmt::skip]
+    const CODES: &[(&str, &str)] = &[
+        ("afr", "af"), ("amh", "am"), ("ara", "ar"), ("aze", "az"), ("bel", "be"), ("ben", "bn"),
+        ("bul", "bg"), ("cat", "ca"), ("ces", "cs"), ("dan", "da"), ("deu", "de"), ("ell", "el"),
+        ("eng", "en"), ("epo", "eo"), ("est", "et"), ("fin", "fi"), ("fra", "fr"), ("guj", "gu"),
+        ("heb", "he"), ("hin", "hi"), ("hrv", "hr"), ("hun", "hu"), ("hye", "hy"), ("ind", "id"),
+        ("ita", "it"), ("jpn", "ja"), ("kan", "kn"), ("kat", "ka"), ("kor", "ko"), ("lat", "la"),
+        ("lav", "lv"), ("lit", "lt"), ("mal", "ml"), ("mar", "mr"), ("mkd", "mk"), ("mya", "my"),
+        ("nep", "ne"), ("nld", "nl"), ("nob", "nb"), ("ori", "or"), ("pan", "pa"), ("pes", "fa"),
+        ("pol", "pl"), ("por", "pt"), ("ron", "ro"), ("rus", "ru"), ("sin", "si"), ("slk", "sk"),
+        ("slv", "sl"), ("spa", "es"), ("srp", "sr"), ("swe", "sv"), ("tam", "ta"), ("tel", "te"),
+        ("tuk", "tk"), ("tur", "tr"), ("ukr", "uk"), ("urd", "ur"), ("uzb", "uz"), ("vie", "vi"),
+    ];
+
+    let code = lang.code();
+    CODES
+        .iter()
+        .find(|(iso_639_3, _)| *iso_639_3 == code)
+        .map_or(code, |(_, espeak_code)| espeak_code)
+}
+
+/// Find the installed voice best suited to `text`, if the language could be reliably detected.
+pub(crate) fn detect_voice<'v>(voices: &'v [Voice], text: &str) -> Option<&'v Voice> {
+    let info = whatlang::detect(text).filter(whatlang::Info::is_reliable)?;
+    let code = espeak_language_code(info.lang());
+
+    // Voices list languages as either the code alone or with a region, such as `en` or `en-gb`.
+    let matches_code = |name: &str| {
+        name.strip_prefix(code)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+    };
+
+    voices
+        .iter()
+        .filter(|voice| !voice.filename.starts_with("mb/"))
+        .filter_map(|voice| {
+            let language = voice.languages.iter().find(|l| matches_code(&l.name))?;
+            Some((language.priority, voice))
+        })
+        .min_by_key(|(priority, _)| *priority)
+        .map(|(_, voice)| voice)
+}
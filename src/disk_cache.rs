@@ -0,0 +1,274 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    cache::{CacheKey, CacheOutput, CachedValue, StableHasher},
+    Result, Speaker,
+};
+
+/// The extension of cached audio files, so only they are removed when clearing the directory.
+const ENTRY_EXTENSION: &str = "pcm";
+/// The file recording the espeak installation the entries were generated with.
+const IDENTITY_FILE: &str = "identity";
+/// The start of every entry, changed if the format of entries changes.
+const MAGIC: &[u8; 8] = b"ESNGRS2\0";
+
+/// A persistent cache of synthesized audio in a directory, used by [`Speaker::set_disk_cache`]
+/// alongside any [`crate::SynthesisCache`], so repeated text is not synthesized again after a restart.
+///
+/// Each entry is a file named by a hash of the voice, parameters, text preprocessing and text,
+/// which are also stored in the file to detect collisions. The whole cache is cleared if the espeak
+/// version, data directory, data version, or the size or modification time of `phondata` or a
+/// compiled dictionary changes, such as after [`Speaker::compile_dictionary`].
+///
+/// Changing text preprocessing or voice attributes misses entries stored with another
+/// configuration, but does not remove them, so a [Speaker] configured the same way on each startup
+/// reuses them. Text filters set with [`Speaker::set_text_filter`] cannot be compared between runs,
+/// so audio is not stored or looked up while one is set.
+#[derive(Debug)]
+pub struct DiskCache {
+    directory: PathBuf,
+    max_bytes: Option<u64>,
+    bytes: u64,
+}
+
+impl DiskCache {
+    /// Open or create a cache in `directory`, removing the entries if they were generated by a
+    /// different espeak installation.
+    ///
+    /// This should be called after initialising espeak, so the data directory is known.
+    ///
+    /// # Errors
+    /// If the directory could not be created or read.
+    pub fn open(directory: impl Into<PathBuf>) -> Result<Self> {
+        let mut cache = Self {
+            directory: directory.into(),
+            max_bytes: None,
+            bytes: 0,
+        };
+
+        fs::create_dir_all(&cache.directory)?;
+        cache.check_identity()?;
+        Ok(cache)
+    }
+
+    /// Limit the total size of the cached audio, removing the least recently used entries.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self.evict();
+        self
+    }
+
+    /// The directory the entries are stored in.
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The total size of the cached audio, in bytes.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Remove every cached entry.
+    ///
+    /// # Errors
+    /// If the directory could not be read, or an entry could not be removed.
+    pub fn clear(&mut self) -> Result<()> {
+        for (path, _, _) in self.entries()? {
+            fs::remove_file(path)?;
+        }
+
+        self.bytes = 0;
+        Ok(())
+    }
+
+    /// Clear the cache if the espeak installation differs from the one recorded in the directory.
+    pub(crate) fn check_identity(&mut self) -> Result<()> {
+        let info = Speaker::info();
+        let mut identity = format!(
+            "{}\n{}\n{}\n",
+            info.version_string,
+            info.data_path.display(),
+            info.data_version
+                .map(|version| version.to_string())
+                .unwrap_or_default(),
+        );
+
+        for (name, len, modified) in data_files(&info.data_path) {
+            let _ = writeln!(identity, "{name} {len} {modified}");
+        }
+
+        let identity_path = self.directory.join(IDENTITY_FILE);
+        if fs::read_to_string(&identity_path).ok().as_deref() != Some(identity.as_str()) {
+            self.clear()?;
+            fs::write(identity_path, identity)?;
+        }
+
+        self.bytes = self.entries()?.iter().map(|(_, len, _)| len).sum();
+        Ok(())
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<CachedValue> {
+        if key.output != CacheOutput::Audio {
+            return None;
+        }
+
+        let key_bytes = key.to_bytes();
+        let path = self.entry_path(&key_bytes);
+        let contents = fs::read(&path).ok()?;
+
+        let header_len = MAGIC.len() + 8;
+        let stored_key_len = contents.get(MAGIC.len()..header_len)?;
+        let stored_key_len =
+            usize::try_from(u64::from_le_bytes(stored_key_len.try_into().ok()?)).ok()?;
+        let audio_start = header_len.checked_add(stored_key_len)?;
+
+        if !contents.starts_with(MAGIC) || contents.get(header_len..audio_start)? != key_bytes {
+            return None;
+        }
+
+        // Mark the entry as recently used, so it is evicted last.
+        if let Ok(file) = fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+
+        let audio = contents[audio_start..]
+            .chunks_exact(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+            .collect();
+
+        Some(CachedValue::Audio(audio))
+    }
+
+    /// Store `value`, ignoring errors as the cache is only an optimisation.
+    pub(crate) fn insert(&mut self, key: &CacheKey, value: &CachedValue) {
+        let CachedValue::Audio(audio) = value else {
+            return;
+        };
+
+        let key_bytes = key.to_bytes();
+        let mut contents = Vec::with_capacity(MAGIC.len() + 8 + key_bytes.len() + audio.len() * 2);
+        contents.extend_from_slice(MAGIC);
+        contents.extend_from_slice(&(key_bytes.len() as u64).to_le_bytes());
+        contents.extend_from_slice(&key_bytes);
+        for sample in audio {
+            contents.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        let len = contents.len() as u64;
+        if self.max_bytes.is_some_and(|max_bytes| len > max_bytes) {
+            return;
+        }
+
+        // Write to a temporary file then rename it, so readers never see a partial entry.
+        let path = self.entry_path(&key_bytes);
+        let temporary_path = path.with_extension("tmp");
+        let written = fs::File::create(&temporary_path)
+            .and_then(|mut file| file.write_all(&contents))
+            .and_then(|()| {
+                let replaced = fs::metadata(&path).map_or(0, |metadata| metadata.len());
+                fs::rename(&temporary_path, &path)?;
+                Ok(replaced)
+            });
+
+        match written {
+            Ok(replaced) => {
+                self.bytes = self.bytes.saturating_sub(replaced) + len;
+                self.evict();
+            }
+            Err(_) => {
+                let _ = fs::remove_file(temporary_path);
+            }
+        }
+    }
+
+    fn entry_path(&self, key_bytes: &[u8]) -> PathBuf {
+        let mut hasher = StableHasher::new();
+        hasher.write(key_bytes);
+
+        self.directory
+            .join(format!("{:016x}.{ENTRY_EXTENSION}", hasher.finish()))
+    }
+
+    /// The path, size and last use of every entry.
+    fn entries(&self) -> Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some(ENTRY_EXTENSION) {
+                continue;
+            }
+
+            let metadata = fs::metadata(&path)?;
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((path, metadata.len(), modified));
+        }
+
+        Ok(entries)
+    }
+
+    /// Remove the least recently used entries until under the size limit.
+    fn evict(&mut self) {
+        let Some(max_bytes) = self.max_bytes else {
+            return;
+        };
+
+        if self.bytes <= max_bytes {
+            return;
+        }
+
+        let Ok(mut entries) = self.entries() else {
+            return;
+        };
+
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, len, _) in entries {
+            if self.bytes <= max_bytes {
+                break;
+            }
+
+            if fs::remove_file(path).is_ok() {
+                self.bytes = self.bytes.saturating_sub(len);
+            }
+        }
+    }
+}
+
+/// The name, size and modification time in nanoseconds of the compiled data files which change
+/// synthesis, `phondata` and every `*_dict`, sorted by name.
+fn data_files(data_path: &Path) -> Vec<(String, u64, u128)> {
+    let Ok(entries) = fs::read_dir(data_path) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            if name != "phondata" && !name.ends_with("_dict") {
+                return None;
+            }
+
+            // Follow symlinks, such as those of a data overlay.
+            let metadata = fs::metadata(entry.path()).ok()?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .map_or(0, |modified| modified.as_nanos());
+
+            Some((name, metadata.len(), modified))
+        })
+        .collect();
+
+    files.sort_unstable();
+    files
+}
@@ -0,0 +1,172 @@
+use std::{io::BufRead, ops::Range};
+
+use crate::{
+    backend::{EspeakBackend, FfiBackend},
+    Result, Speaker, SynthesisOptions,
+};
+
+/// The maximum length of text, in bytes, synthesized in a single call.
+const MAX_CHUNK_LEN: usize = 1000;
+
+/// The audio generated for a chunk of a document, yielded by [`DocumentSynthesis`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentChunk {
+    /// The audio data generated for the chunk.
+    pub audio: Vec<i16>,
+    /// The byte range of the chunk within the document.
+    pub text_range: Range<usize>,
+    /// The fraction of the document synthesized so far, from 0 to 1.
+    pub progress: f32,
+}
+
+/// An iterator synthesizing a document a chunk at a time, created by [`Speaker::synthesize_document`].
+pub struct DocumentSynthesis<'s, 't, B: EspeakBackend = FfiBackend> {
+    speaker: &'s mut Speaker<B>,
+    text: &'t str,
+    options: &'t SynthesisOptions,
+    position: usize,
+}
+
+impl<'s, 't, B: EspeakBackend> DocumentSynthesis<'s, 't, B> {
+    pub(crate) fn new(
+        speaker: &'s mut Speaker<B>,
+        text: &'t str,
+        options: &'t SynthesisOptions,
+    ) -> Self {
+        Self {
+            speaker,
+            text,
+            options,
+            position: 0,
+        }
+    }
+}
+
+impl<B: EspeakBackend> Iterator for DocumentSynthesis<'_, '_, B> {
+    type Item = Result<DocumentChunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text_range = next_chunk(self.text, self.position, MAX_CHUNK_LEN)?;
+            self.position = text_range.end;
+
+            let chunk = &self.text[text_range.clone()];
+            if chunk.trim().is_empty() {
+                continue;
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            let progress = text_range.end as f32 / self.text.len() as f32;
+            return Some(
+                self.speaker
+                    .synthesize_with_options(chunk, self.options)
+                    .map(|audio| DocumentChunk {
+                        audio,
+                        text_range,
+                        progress,
+                    }),
+            );
+        }
+    }
+}
+
+/// An iterator synthesizing text read from a [`BufRead`] a line at a time, created by
+/// [`Speaker::synthesize_from_reader`].
+pub struct ReaderSynthesis<'s, 'o, R, B: EspeakBackend = FfiBackend> {
+    speaker: &'s mut Speaker<B>,
+    reader: R,
+    options: &'o SynthesisOptions,
+    line: String,
+    position: usize,
+}
+
+impl<'s, 'o, R: BufRead, B: EspeakBackend> ReaderSynthesis<'s, 'o, R, B> {
+    pub(crate) fn new(
+        speaker: &'s mut Speaker<B>,
+        reader: R,
+        options: &'o SynthesisOptions,
+    ) -> Self {
+        Self {
+            speaker,
+            reader,
+            options,
+            line: String::new(),
+            position: 0,
+        }
+    }
+}
+
+impl<R: BufRead, B: EspeakBackend> Iterator for ReaderSynthesis<'_, '_, R, B> {
+    type Item = Result<Vec<i16>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Long lines are split up in the same way as documents.
+            if let Some(text_range) = next_chunk(&self.line, self.position, MAX_CHUNK_LEN) {
+                self.position = text_range.end;
+
+                let chunk = &self.line[text_range];
+                if chunk.trim().is_empty() {
+                    continue;
+                }
+
+                return Some(self.speaker.synthesize_with_options(chunk, self.options));
+            }
+
+            self.line.clear();
+            self.position = 0;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+    }
+}
+
+/// Finds the byte offsets just after each sentence or line ending in `text`.
+pub(crate) fn sentence_ends(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        while let Some((offset, char)) = chars.next() {
+            let is_end = match char {
+                '\n' => true,
+                '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+                _ => false,
+            };
+
+            if is_end {
+                return Some(offset + char.len_utf8());
+            }
+        }
+
+        None
+    })
+}
+
+/// Finds the next chunk of `text` after `start`, ending at a sentence boundary if possible.
+pub(crate) fn next_chunk(text: &str, start: usize, max_len: usize) -> Option<Range<usize>> {
+    let rest = text.get(start..).filter(|rest| !rest.is_empty())?;
+    if rest.len() <= max_len {
+        return Some(start..text.len());
+    }
+
+    let sentence_end = sentence_ends(rest).take_while(|end| *end <= max_len).last();
+    let len = sentence_end
+        // Fall back to splitting at a word boundary, then anywhere.
+        .or_else(|| {
+            rest[..max_len]
+                .rfind(char::is_whitespace)
+                .filter(|i| *i != 0)
+        })
+        .unwrap_or_else(|| {
+            let mut boundary = max_len;
+            while !rest.is_char_boundary(boundary) {
+                boundary -= 1;
+            }
+
+            boundary.max(rest.chars().next().map_or(0, char::len_utf8))
+        });
+
+    Some(start..start + len)
+}
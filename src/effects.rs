@@ -0,0 +1,169 @@
+//! Simple post-processing of synthesized audio, without a separate DSP dependency.
+//!
+//! ```no_run
+//! use espeakng::effects::{Chain, FadeOut, Gain, Processor};
+//!
+//! fn main() -> Result<(), espeakng::Error> {
+//!     let mut speaker = espeakng::initialise_and_lock(espeakng::InitOptions::default())?;
+//!
+//!     let mut effects = Chain::new()
+//!         .then(Gain(0.5))
+//!         .then(FadeOut::from_duration(std::time::Duration::from_millis(50), speaker.sample_rate()));
+//!
+//!     let audio = effects.process(&speaker.synthesize("Hello world")?);
+//!     Ok(())
+//! }
+//! ```
+
+// Samples are processed as floats, then rounded and saturated back into i16s.
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+
+use std::time::Duration;
+
+/// An audio effect, processing a full buffer of samples at a time.
+pub trait Processor {
+    /// Process `input`, returning the new audio.
+    fn process(&mut self, input: &[i16]) -> Vec<i16>;
+}
+
+impl<P: Processor + ?Sized> Processor for Box<P> {
+    fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        (**self).process(input)
+    }
+}
+
+/// Multiplies the amplitude of each sample, saturating at the limits of [`i16`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gain(pub f32);
+
+impl Processor for Gain {
+    fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        input
+            .iter()
+            .map(|&sample| (f32::from(sample) * self.0) as i16)
+            .collect()
+    }
+}
+
+/// Changes the speed of audio by linear resampling, which also changes the pitch.
+///
+/// A factor of `2.0` is twice as fast, and `0.5` is half as fast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Speed(pub f32);
+
+impl Processor for Speed {
+    fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if input.is_empty() || self.0 <= 0.0 {
+            return Vec::new();
+        }
+
+        let output_len = (input.len() as f64 / f64::from(self.0)).round() as usize;
+        (0..output_len)
+            .map(|i| {
+                let position = i as f64 * f64::from(self.0);
+                let index = position as usize;
+                let fraction = position - index as f64;
+
+                let current = f64::from(input[index.min(input.len() - 1)]);
+                let next = f64::from(input[(index + 1).min(input.len() - 1)]);
+                (current + (next - current) * fraction) as i16
+            })
+            .collect()
+    }
+}
+
+/// Linearly fades in from silence over the first `samples` samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FadeIn {
+    pub samples: usize,
+}
+
+impl FadeIn {
+    /// Fade in over `duration`, for audio at `sample_rate`, such as from [`crate::Speaker::sample_rate`].
+    #[must_use]
+    pub fn from_duration(duration: Duration, sample_rate: u32) -> Self {
+        Self {
+            samples: duration_to_samples(duration, sample_rate),
+        }
+    }
+}
+
+impl Processor for FadeIn {
+    fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        let mut output = input.to_vec();
+        let fade_len = self.samples.min(output.len());
+        for (i, sample) in output[..fade_len].iter_mut().enumerate() {
+            *sample = (f32::from(*sample) * (i as f32 / self.samples as f32)) as i16;
+        }
+
+        output
+    }
+}
+
+/// Linearly fades out to silence over the last `samples` samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FadeOut {
+    pub samples: usize,
+}
+
+impl FadeOut {
+    /// Fade out over `duration`, for audio at `sample_rate`, such as from [`crate::Speaker::sample_rate`].
+    #[must_use]
+    pub fn from_duration(duration: Duration, sample_rate: u32) -> Self {
+        Self {
+            samples: duration_to_samples(duration, sample_rate),
+        }
+    }
+}
+
+impl Processor for FadeOut {
+    fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        let mut output = input.to_vec();
+        let fade_len = self.samples.min(output.len());
+        let fade_start = output.len() - fade_len;
+        for (i, sample) in output[fade_start..].iter_mut().rev().enumerate() {
+            *sample = (f32::from(*sample) * (i as f32 / self.samples as f32)) as i16;
+        }
+
+        output
+    }
+}
+
+/// Runs a series of processors in order, each processing the output of the last.
+#[derive(Default)]
+pub struct Chain(Vec<Box<dyn Processor + Send>>);
+
+impl Chain {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a processor to the end of the chain.
+    #[must_use]
+    pub fn then(mut self, processor: impl Processor + Send + 'static) -> Self {
+        self.0.push(Box::new(processor));
+        self
+    }
+}
+
+impl Processor for Chain {
+    fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        let mut processors = self.0.iter_mut();
+        let Some(first) = processors.next() else {
+            return input.to_vec();
+        };
+
+        processors.fold(first.process(input), |audio, processor| {
+            processor.process(&audio)
+        })
+    }
+}
+
+fn duration_to_samples(duration: Duration, sample_rate: u32) -> usize {
+    (duration.as_secs_f64() * f64::from(sample_rate)).round() as usize
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::Result;
+
+static DATA: include_dir::Dir<'static> = include_dir::include_dir!("$ESPEAKNG_DATA_DIR");
+static EXTRACTED_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+const DIRECTORY_NAME: &str = concat!("data-", env!("CARGO_PKG_VERSION"));
+
+/// Get the path of the embedded `espeak-ng-data` directory, extracting it on first use.
+///
+/// The data is extracted into an `espeakng-rs` directory within the user's cache directory, such as
+/// `~/.cache` or `%LOCALAPPDATA%`, unique to the crate version, and is reused by later processes.
+/// Extraction happens in a temporary directory which is renamed into place once complete, so other
+/// processes never see partially extracted data.
+///
+/// If no cache directory can be found, the data is extracted into a new directory for this process.
+///
+/// # Errors
+/// If the data could not be written to the directory.
+pub fn embedded_data_path() -> Result<&'static Path> {
+    EXTRACTED_PATH
+        .get_or_try_init(|| {
+            let Some(cache_dir) = user_cache_dir() else {
+                return extract_unique(&std::env::temp_dir());
+            };
+
+            let parent = cache_dir.join("espeakng-rs");
+            let path = parent.join(DIRECTORY_NAME);
+            if path.is_dir() {
+                return Ok(path);
+            }
+
+            create_private_dir(&parent)?;
+            let extracted = extract_unique(&parent)?;
+            if let Err(err) = fs::rename(&extracted, &path) {
+                let _ = fs::remove_dir_all(&extracted);
+
+                // Another process finished extracting first.
+                if !path.is_dir() {
+                    return Err(err.into());
+                }
+            }
+
+            Ok(path)
+        })
+        .map(PathBuf::as_path)
+}
+
+/// Extract the data into a new directory within `parent`, returning its path.
+fn extract_unique(parent: &Path) -> Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.subsec_nanos());
+
+    let mut attempt = 0_u32;
+    let path = loop {
+        let path = parent.join(format!(
+            ".{DIRECTORY_NAME}.{}.{nanos}.{attempt}",
+            std::process::id()
+        ));
+
+        match fs::create_dir(&path) {
+            Ok(()) => break path,
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists && attempt < 16 => {
+                attempt += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    if let Err(err) = DATA.extract(&path) {
+        let _ = fs::remove_dir_all(&path);
+        return Err(err.into());
+    }
+
+    Ok(path)
+}
+
+/// Create `path` if needed, readable only by the current user.
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(path)
+    }
+
+    #[cfg(not(unix))]
+    fs::create_dir_all(path)
+}
+
+/// The per-user cache directory of the platform, if it can be found.
+fn user_cache_dir() -> Option<PathBuf> {
+    let absolute = |path: PathBuf| path.is_absolute().then_some(path);
+    let from_env = |name| std::env::var_os(name).map(PathBuf::from).and_then(absolute);
+
+    if cfg!(windows) {
+        from_env("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        from_env("HOME").map(|home| home.join("Library").join("Caches"))
+    } else {
+        from_env("XDG_CACHE_HOME").or_else(|| from_env("HOME").map(|home| home.join(".cache")))
+    }
+}
@@ -0,0 +1,134 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::cache::StableHasher;
+
+/// The longest emoji sequence looked up, in characters, such as a family joined with ZWJs.
+const MAX_SEQUENCE_CHARS: usize = 12;
+
+/// The spoken descriptions of emoji and symbols, used by [`crate::Speaker::set_emoji_names`].
+///
+/// The built in names are the English Unicode CLDR short names, such as `grinning face` for 😀.
+/// Names for other languages, or replacements for the built in ones, are added with [`EmojiNames::insert`].
+#[derive(Clone, Debug)]
+pub struct EmojiNames {
+    names: HashMap<String, String>,
+    builtin: bool,
+}
+
+impl Default for EmojiNames {
+    fn default() -> Self {
+        Self {
+            names: HashMap::new(),
+            builtin: true,
+        }
+    }
+}
+
+impl EmojiNames {
+    /// The built in English names, without any custom names.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only the custom names added with [`EmojiNames::insert`], such as for a voice in another language.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            names: HashMap::new(),
+            builtin: false,
+        }
+    }
+
+    /// Speak `emoji` as `name`, replacing any built in name.
+    ///
+    /// This can also be used for symbols which are not emoji, such as `→`.
+    pub fn insert(&mut self, emoji: impl Into<String>, name: impl Into<String>) {
+        self.names.insert(emoji.into(), name.into());
+    }
+
+    /// The name `sequence` is spoken as, if any.
+    #[must_use]
+    pub fn get(&self, sequence: &str) -> Option<&str> {
+        self.lookup(sequence, self.builtin)
+    }
+
+    fn lookup(&self, sequence: &str, builtin: bool) -> Option<&str> {
+        if let Some(name) = self.names.get(sequence) {
+            return Some(name);
+        }
+
+        if builtin {
+            emojis::get(sequence).map(emojis::Emoji::name)
+        } else {
+            None
+        }
+    }
+
+    /// Replace each emoji in `text` with its name, separated from the surrounding words by spaces.
+    ///
+    /// Custom names are matched anywhere in the text, including within words. Emoji without a
+    /// name are kept as they are.
+    #[must_use]
+    pub fn verbalize<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut verbalized = String::new();
+        let mut copied = 0;
+        let mut position = 0;
+
+        while position < text.len() {
+            let Some((len, name)) = self.longest_match(&text[position..]) else {
+                position += text[position..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+
+            verbalized.push_str(&text[copied..position]);
+            if !verbalized.is_empty() && !verbalized.ends_with(char::is_whitespace) {
+                verbalized.push(' ');
+            }
+
+            verbalized.push_str(name);
+            position += len;
+            copied = position;
+
+            if text[position..].starts_with(|c: char| !c.is_whitespace()) {
+                verbalized.push(' ');
+            }
+        }
+
+        if copied == 0 {
+            return Cow::Borrowed(text);
+        }
+
+        verbalized.push_str(&text[copied..]);
+        Cow::Owned(verbalized)
+    }
+
+    /// The byte length and name of the longest named sequence at the start of `text`.
+    fn longest_match(&self, text: &str) -> Option<(usize, &str)> {
+        let first = text.chars().next()?;
+
+        // ASCII is only part of a built in emoji as a keycap, such as `1️⃣`, so skip looking up words.
+        let builtin =
+            self.builtin && (!first.is_ascii() || text[1..].starts_with(['\u{FE0F}', '\u{20E3}']));
+        if !builtin && self.names.is_empty() {
+            return None;
+        }
+
+        let ends: Vec<usize> = text
+            .char_indices()
+            .skip(1)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .take(MAX_SEQUENCE_CHARS)
+            .collect();
+
+        ends.into_iter()
+            .rev()
+            .find_map(|end| self.lookup(&text[..end], builtin).map(|name| (end, name)))
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut StableHasher) {
+        hasher.write(&[u8::from(self.builtin)]);
+        hasher.write_map(&self.names);
+    }
+}
@@ -1,62 +1,245 @@
 /// An error from this library.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Occured in an espeakng C function.
-    ESpeakNg(ESpeakNgError),
-    /// [crate::initialise] was called when already initialized.
+    ESpeakNg {
+        /// The name of the function which failed, such as `espeak_ng_SetVoiceByName`.
+        func: &'static str,
+        code: ESpeakNgError,
+        /// The message espeakNG decoded from its error context, such as the file and line of a compile error.
+        context: Option<String>,
+    },
+    /// [`crate::SpeakerHandle::new`] was called with different options while another handle is
+    /// still alive.
     AlreadyInit,
+    /// espeak failed to initialise from the prefix it was compiled with, and no `espeak-ng-data`
+    /// directory was found elsewhere, see [`crate::data_path::discover`].
+    DataNotFound { searched: Vec<std::path::PathBuf> },
     /// [crate::Speaker::text_to_phonemes] was called without an mbrola voice selected.
     MbrolaWithoutMbrolaVoice,
+    /// A value outside of the valid range was passed for a [`crate::Parameter`].
+    ParameterOutOfRange {
+        param: crate::Parameter,
+        value: i32,
+        range: std::ops::RangeInclusive<i32>,
+    },
+    /// [`crate::initialise`] has not been called, so there is no [`crate::Speaker`] to lock.
+    NotInitialised,
+    /// The [`crate::Speaker`] could not be locked before the timeout.
+    LockTimeout,
+    /// Synthesis was cancelled before completion.
+    Cancelled,
+    /// The background thread of a [`crate::SpeakerHandle`], or a worker process of a
+    /// [`crate::batch::WorkerPool`], has stopped.
+    WorkerDisconnected,
+    /// A worker process of a [`crate::batch::WorkerPool`] failed to synthesize a text, with the
+    /// error it reported.
+    WorkerFailed(String),
     /// Occured non-espeakng C function, errno is contained if populated.
-    OtherC(Option<errno::Errno>),
-    /// Occured in an unknown Rust location, usually a library bug.
-    Other(Box<dyn std::error::Error + Send + Sync>),
+    OtherC {
+        /// The name of the function which failed, such as `tmpfile`.
+        func: &'static str,
+        errno: Option<errno::Errno>,
+        /// The message espeakNG decoded from its error context, such as the file which failed to open.
+        context: Option<String>,
+    },
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+    /// Output from espeak was not valid UTF-8.
+    Utf8(std::string::FromUtf8Error),
+    /// A path passed to espeak contained a NUL byte.
+    Nul(std::ffi::NulError),
+    /// The text passed could not be spoken as given.
+    InvalidInput(InvalidInput),
 }
 
-impl std::error::Error for Error {}
+/// The reason text was rejected, from [`Error::InvalidInput`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidInput {
+    /// The text contained a NUL character, which would truncate it.
+    ///
+    /// The position is in bytes, or in characters for wide and UTF-16 text.
+    InteriorNul { position: usize },
+    /// The text was empty.
+    Empty,
+    /// The text was longer than the limit set by [`crate::Speaker::set_max_input_length`], in bytes.
+    TooLong { length: usize, limit: usize },
+}
+
+impl std::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InteriorNul { position } => {
+                write!(
+                    f,
+                    "The text contained a NUL character at position {position}"
+                )
+            }
+            Self::Empty => f.write_str("The text was empty"),
+            Self::TooLong { length, limit } => {
+                write!(
+                    f,
+                    "The text was {length} bytes long, over the limit of {limit}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ESpeakNg { code, .. } => Some(code),
+            Self::OtherC {
+                errno: Some(errno), ..
+            } => Some(errno),
+            Self::Io(err) => Some(err),
+            Self::Utf8(err) => Some(err),
+            Self::Nul(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&match self {
             Self::MbrolaWithoutMbrolaVoice => {
                 String::from("eSpeak cannot generate mbrola phonemes without an mbrola voice set!")
             }
-            Self::ESpeakNg(err) => {
-                format!("Failed to execute an internal espeakNG function: {err:?}")
+            Self::DataNotFound { searched } => {
+                format!("Could not find the espeak-ng-data directory, searched: {searched:?}")
             }
+            Self::ParameterOutOfRange {
+                param,
+                value,
+                range,
+            } => format!("{value} is out of the valid range for {param:?}, {range:?}"),
+            Self::ESpeakNg {
+                func,
+                code,
+                context,
+            } => match context {
+                Some(context) => {
+                    format!("Failed to execute the internal espeakNG function {func}: {context}")
+                }
+                None => {
+                    format!("Failed to execute the internal espeakNG function {func}: {code:?}")
+                }
+            },
             Self::AlreadyInit => {
-                String::from("espeakng::initialise was called after already having been called!")
+                String::from("A SpeakerHandle with different options is still alive!")
             }
-            Self::OtherC(err) => format!("Failed to execute an internal C function: {err:?}"),
-            Self::Other(err) => format!("An internal error occurred: {err:?}"),
+            Self::NotInitialised => String::from("espeakng::initialise has not been called!"),
+            Self::LockTimeout => String::from("Timed out waiting for the espeakng::Speaker lock!"),
+            Self::Cancelled => String::from("Synthesis was cancelled before completion!"),
+            Self::WorkerDisconnected => {
+                String::from("The background thread or worker process has stopped!")
+            }
+            Self::WorkerFailed(reason) => format!("A worker process failed: {reason}"),
+            Self::OtherC {
+                func,
+                errno,
+                context,
+            } => match context {
+                Some(context) => {
+                    format!("Failed to execute the internal C function {func}: {context}")
+                }
+                None => format!("Failed to execute the internal C function {func}: {errno:?}"),
+            },
+            Self::Io(_) => String::from("An I/O operation failed!"),
+            Self::Utf8(_) => String::from("espeakNG returned invalid UTF-8!"),
+            Self::Nul(_) => String::from("A path passed to espeakNG contained a NUL byte!"),
+            Self::InvalidInput(reason) => format!("Invalid input text: {reason}!"),
         })
     }
 }
 
-impl From<ESpeakNgError> for Error {
-    fn from(err: ESpeakNgError) -> Self {
-        Self::ESpeakNg(err)
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        use std::io::ErrorKind;
+
+        let kind = match err {
+            Error::Io(err) => return err,
+            Error::OtherC {
+                errno: Some(errno), ..
+            } => return errno.into(),
+            Error::ESpeakNg { code, .. } => match code {
+                ESpeakNgError::VoiceNotFound
+                | ESpeakNgError::MbrolaNotFound
+                | ESpeakNgError::MbrolaVoiceNotFound => ErrorKind::NotFound,
+                ESpeakNgError::NotSupported => ErrorKind::Unsupported,
+                _ => ErrorKind::Other,
+            },
+            Error::DataNotFound { .. } | Error::NotInitialised => ErrorKind::NotFound,
+            Error::MbrolaWithoutMbrolaVoice
+            | Error::ParameterOutOfRange { .. }
+            | Error::Nul(_)
+            | Error::InvalidInput(_) => ErrorKind::InvalidInput,
+            Error::Utf8(_) => ErrorKind::InvalidData,
+            Error::LockTimeout => ErrorKind::TimedOut,
+            Error::Cancelled => ErrorKind::Interrupted,
+            Error::WorkerDisconnected => ErrorKind::BrokenPipe,
+            Error::AlreadyInit | Error::WorkerFailed(_) | Error::OtherC { errno: None, .. } => {
+                ErrorKind::Other
+            }
+        };
+
+        Self::new(kind, err)
     }
 }
 
-macro_rules! generate_unknown_err {
-    ($cause:ty) => {
+macro_rules! generate_wrapped_err {
+    ($cause:ty, $variant:ident) => {
         impl From<$cause> for Error {
             fn from(err: $cause) -> Self {
-                Self::Other(Box::new(err))
+                Self::$variant(err)
             }
         }
     };
 }
 
-generate_unknown_err!(std::io::Error);
-generate_unknown_err!(std::string::FromUtf8Error);
+generate_wrapped_err!(std::io::Error, Io);
+generate_wrapped_err!(std::string::FromUtf8Error, Utf8);
+generate_wrapped_err!(std::ffi::NulError, Nul);
+
+macro_rules! status_codes {
+    ($($variant:ident = $code:literal,)*) => {
+        /// An error from the `espeakNG` C library.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[allow(clippy::module_name_repetitions)]
+        #[non_exhaustive]
+        pub enum ESpeakNgError {
+            $($variant,)*
+            /// A status code not known to this library, such as from a newer espeakNG version.
+            Unknown(u32),
+        }
+
+        impl ESpeakNgError {
+            /// The raw `espeak_ng_STATUS` code of this error.
+            #[must_use]
+            pub fn code(&self) -> u32 {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Unknown(code) => *code,
+                }
+            }
+
+            /// Convert a raw `espeak_ng_STATUS` code into an error, falling back to [`Self::Unknown`].
+            #[must_use]
+            pub fn from_code(code: u32) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    _ => Self::Unknown(code),
+                }
+            }
+        }
+    };
+}
 
-/// An error from the `espeakNG` C library.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::FromRepr)]
-#[allow(clippy::module_name_repetitions)]
-#[repr(u32)]
 #[rustfmt::skip]
-pub enum ESpeakNgError {
+status_codes! {
     CompileError              = 0x1000_01FF,
     VersionMismatch           = 0x1000_02FF,
     FifoBufferFull            = 0x1000_03FF,
@@ -85,7 +268,7 @@ impl std::fmt::Display for ESpeakNgError {
         // if this isn't long enough, internal functions to espeakNG break.
         let status_code_message = unsafe {
             crate::bindings::espeak_ng_GetStatusCodeMessage(
-                *self as u32,
+                self.code(),
                 buffer.as_mut_ptr(),
                 BUFFER_LEN,
             );
@@ -97,13 +280,69 @@ impl std::fmt::Display for ESpeakNgError {
     }
 }
 
-pub(crate) fn handle_error(ret_code: u32) -> Result<(), Error> {
+/// The bit set in every espeakNG specific status code.
+const ESPEAKNG_STATUS_GROUP: u32 = 0x1000_0000;
+
+pub(crate) fn handle_error(func: &'static str, ret_code: u32) -> Result<(), Error> {
     if ret_code == 0 {
         Ok(())
     } else {
-        Err(match ESpeakNgError::from_repr(ret_code) {
-            Some(err) => Error::ESpeakNg(err),
-            None => Error::OtherC(Some(errno::errno())),
+        // Status codes without the espeakNG group bit are errno values, such as from opening files.
+        Err(if ret_code & ESPEAKNG_STATUS_GROUP == 0 {
+            Error::OtherC {
+                func,
+                errno: Some(errno::Errno(ret_code as i32)),
+                context: None,
+            }
+        } else {
+            Error::ESpeakNg {
+                func,
+                code: ESpeakNgError::from_code(ret_code),
+                context: None,
+            }
+        })
+    }
+}
+
+/// An `espeak_ng_ERROR_CONTEXT`, filled in by espeakNG with details such as the file being read.
+pub(crate) struct ErrorContext(crate::bindings::espeak_ng_ERROR_CONTEXT);
+
+impl ErrorContext {
+    pub(crate) fn new() -> Self {
+        Self(std::ptr::null_mut())
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut crate::bindings::espeak_ng_ERROR_CONTEXT {
+        std::ptr::from_mut(&mut self.0)
+    }
+
+    /// See [`handle_error`], attaching the message decoded from this context to the error.
+    pub(crate) fn handle_error(&self, func: &'static str, ret_code: u32) -> Result<(), Error> {
+        handle_error(func, ret_code).map_err(|mut err| {
+            if let Error::ESpeakNg { context, .. } | Error::OtherC { context, .. } = &mut err {
+                *context = self.message(ret_code);
+            }
+
+            err
         })
     }
+
+    /// Decode the message espeakNG prints for `status`, if any context was filled in.
+    fn message(&self, status: u32) -> Option<String> {
+        if self.0.is_null() {
+            return None;
+        }
+
+        let mut file = crate::utils::CFile::temporary().ok()?;
+        unsafe { crate::bindings::espeak_ng_PrintStatusCodeMessage(status, file.as_ptr(), self.0) };
+
+        let message = String::from_utf8(file.read_to_end().ok()?).ok()?;
+        Some(message.trim_end().to_owned())
+    }
+}
+
+impl Drop for ErrorContext {
+    fn drop(&mut self) {
+        unsafe { crate::bindings::espeak_ng_ClearErrorContext(self.as_mut_ptr()) };
+    }
 }
@@ -0,0 +1,176 @@
+use std::io::Write;
+
+use crate::{
+    backend::{EspeakBackend, FfiBackend},
+    bindings,
+    utils::CFile,
+    Error, Parameter, PhonemeTraceMode, Result, Speaker, Voice,
+};
+
+/// Restores the previous values of temporarily changed parameters when dropped.
+///
+/// Created with [`Speaker::scoped_parameters`], this dereferences to the [Speaker] to allow
+/// synthesis with the temporary parameters.
+pub struct ParameterGuard<'a, B: EspeakBackend = FfiBackend> {
+    speaker: &'a mut Speaker<B>,
+    previous: Vec<(Parameter, i32)>,
+}
+
+impl<'a, B: EspeakBackend> ParameterGuard<'a, B> {
+    pub(crate) fn new(speaker: &'a mut Speaker<B>, params: &[(Parameter, i32)]) -> Result<Self> {
+        let mut guard = Self {
+            speaker,
+            previous: Vec::with_capacity(params.len()),
+        };
+
+        for &(param, value) in params {
+            let previous_value = guard.speaker.get_parameter(param, false);
+            guard.speaker.set_parameter(param, value, false)?;
+
+            // Only record once set, so a failure will restore the parameters set so far.
+            guard.previous.push((param, previous_value));
+        }
+
+        Ok(guard)
+    }
+}
+
+impl<B: EspeakBackend> std::ops::Deref for ParameterGuard<'_, B> {
+    type Target = Speaker<B>;
+
+    fn deref(&self) -> &Self::Target {
+        self.speaker
+    }
+}
+
+impl<B: EspeakBackend> std::ops::DerefMut for ParameterGuard<'_, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.speaker
+    }
+}
+
+impl<B: EspeakBackend> Drop for ParameterGuard<'_, B> {
+    fn drop(&mut self) {
+        // Restore in reverse, in case the same parameter was set multiple times.
+        for &(param, value) in self.previous.iter().rev() {
+            let _ = self.speaker.set_parameter(param, value, false);
+        }
+    }
+}
+
+/// Restores the previous voice when dropped.
+///
+/// Created with [`Speaker::scoped_voice`], this dereferences to the [Speaker] to allow
+/// synthesis with the temporary voice.
+pub struct VoiceGuard<'a, B: EspeakBackend = FfiBackend> {
+    speaker: &'a mut Speaker<B>,
+    previous: Option<Voice>,
+}
+
+impl<'a, B: EspeakBackend> VoiceGuard<'a, B> {
+    pub(crate) fn new(speaker: &'a mut Speaker<B>, voice: &Voice) -> Result<Self> {
+        let previous = speaker.try_current_voice();
+        speaker.set_voice(voice)?;
+
+        Ok(Self { speaker, previous })
+    }
+}
+
+impl<B: EspeakBackend> std::ops::Deref for VoiceGuard<'_, B> {
+    type Target = Speaker<B>;
+
+    fn deref(&self) -> &Self::Target {
+        self.speaker
+    }
+}
+
+impl<B: EspeakBackend> std::ops::DerefMut for VoiceGuard<'_, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.speaker
+    }
+}
+
+impl<B: EspeakBackend> Drop for VoiceGuard<'_, B> {
+    fn drop(&mut self) {
+        if let Some(previous) = &self.previous {
+            let _ = self.speaker.set_voice(previous);
+        }
+    }
+}
+
+/// Captures the phoneme trace written by espeak during synthesis, passing it to a sink.
+///
+/// Created with [`Speaker::set_phoneme_trace`], this dereferences to the [Speaker] to allow
+/// synthesis while tracing. The trace is written to the sink and reset by [`PhonemeTraceGuard::finish`],
+/// or when dropped, ignoring any errors.
+pub struct PhonemeTraceGuard<'a, W: Write> {
+    speaker: &'a mut Speaker,
+    trace_file: CFile,
+    sink: Option<W>,
+}
+
+impl<'a, W: Write> PhonemeTraceGuard<'a, W> {
+    pub(crate) fn new(speaker: &'a mut Speaker, mode: PhonemeTraceMode, sink: W) -> Result<Self> {
+        let mbrola_voice = speaker
+            .try_current_voice()
+            .is_some_and(|voice| voice.filename.starts_with("mb/"));
+
+        if mode == PhonemeTraceMode::Mbrola && !mbrola_voice {
+            return Err(Error::MbrolaWithoutMbrolaVoice);
+        };
+
+        // Capture the trace in a C temporary file, as fd/handle tricks are not portable
+        let mut trace_file = CFile::temporary()?;
+        unsafe { bindings::espeak_SetPhonemeTrace(mode.bits(), trace_file.as_ptr()) };
+
+        Ok(Self {
+            speaker,
+            trace_file,
+            sink: Some(sink),
+        })
+    }
+
+    /// Stop tracing, writing the captured trace to the sink and returning it.
+    ///
+    /// # Errors
+    /// If reading the trace or writing to the sink fails.
+    #[allow(clippy::missing_panics_doc)] // The sink is only taken here, which consumes the guard.
+    pub fn finish(mut self) -> Result<W> {
+        self.write_trace()?;
+        Ok(self.sink.take().expect("sink should only be taken once"))
+    }
+
+    fn write_trace(&mut self) -> Result<()> {
+        // Reset the phoneme trace back to stdout, to avoid side effects
+        unsafe { bindings::espeak_SetPhonemeTrace(0, std::ptr::null_mut()) };
+
+        let trace = self.trace_file.read_to_end()?;
+        if let Some(sink) = &mut self.sink {
+            sink.write_all(&trace)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> std::ops::Deref for PhonemeTraceGuard<'_, W> {
+    type Target = Speaker;
+
+    fn deref(&self) -> &Self::Target {
+        self.speaker
+    }
+}
+
+impl<W: Write> std::ops::DerefMut for PhonemeTraceGuard<'_, W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.speaker
+    }
+}
+
+impl<W: Write> Drop for PhonemeTraceGuard<'_, W> {
+    fn drop(&mut self) {
+        if self.sink.is_some() {
+            let _ = self.write_trace();
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use std::{
+    collections::BinaryHeap,
+    sync::{atomic::Ordering, mpsc, Arc, Weak},
+};
+
+use parking_lot::{Condvar, Mutex};
+
+use crate::{Error, InitOptions, Result, Speaker, CANCEL_SYNTHESIS};
+
+type Job = Box<dyn FnOnce(&mut Speaker) + Send>;
+
+/// The handles of the running background thread, shared by every [`SpeakerHandle::new`] call, as
+/// only one thread can hold the [Speaker] lock.
+static ACTIVE_HANDLE: Mutex<Weak<HandleInner>> = Mutex::new(Weak::new());
+
+/// The priority of a request queued on a [`SpeakerHandle`].
+///
+/// Higher priority requests are processed first, and cancel any lower priority synthesis already
+/// in progress, which will return [`Error::Cancelled`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+struct QueuedJob {
+    priority: Priority,
+    sequence: u64,
+    job: Job,
+}
+
+impl PartialEq for QueuedJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for QueuedJob {}
+
+impl PartialOrd for QueuedJob {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedJob {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Highest priority first, then first in first out.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    jobs: BinaryHeap<QueuedJob>,
+    next_sequence: u64,
+    running: Option<Priority>,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Queue {
+    state: Mutex<QueueState>,
+    condvar: Condvar,
+}
+
+impl Queue {
+    fn run_worker(&self, speaker: &mut Speaker) {
+        loop {
+            let job = {
+                let mut state = self.state.lock();
+                state.running = None;
+
+                let job = loop {
+                    if let Some(job) = state.jobs.pop() {
+                        break job;
+                    }
+
+                    if state.closed {
+                        return;
+                    }
+
+                    self.condvar.wait(&mut state);
+                };
+
+                // Reset while locked, so a cancel request for this job cannot be lost.
+                CANCEL_SYNTHESIS.store(false, Ordering::SeqCst);
+                state.running = Some(job.priority);
+                job.job
+            };
+
+            job(speaker);
+        }
+    }
+}
+
+/// Closes the queue once every [`SpeakerHandle`] has been dropped.
+struct HandleInner {
+    queue: Arc<Queue>,
+    /// The options the [Speaker] was initialised with by [`SpeakerHandle::new`].
+    options: InitOptions,
+}
+
+impl Drop for HandleInner {
+    fn drop(&mut self) {
+        self.queue.state.lock().closed = true;
+        self.queue.condvar.notify_all();
+    }
+}
+
+/// A handle to a background thread which holds the [Speaker] lock and processes queued requests.
+///
+/// This can be freely cloned and shared between threads, and never holds the lock in the calling
+/// thread, making it safe to use from async code. The [Speaker] is unlocked once every handle has
+/// been dropped and any queued requests have completed.
+#[derive(Clone)]
+pub struct SpeakerHandle {
+    inner: Arc<HandleInner>,
+}
+
+impl SpeakerHandle {
+    /// Initialise the [Speaker] (see [`crate::initialise`]) and spawn the background thread.
+    ///
+    /// If a handle created with the same `options` is still alive, this returns a clone of it
+    /// instead, sharing its background thread.
+    ///
+    /// # Errors
+    /// [`Error::AlreadyInit`] if a handle created with different `options` is still alive, otherwise
+    /// if initialisation fails, or the background thread cannot be spawned.
+    pub fn new(options: InitOptions) -> Result<Self> {
+        let mut active = ACTIVE_HANDLE.lock();
+        if let Some(inner) = active.upgrade() {
+            return if inner.options == options {
+                Ok(Self { inner })
+            } else {
+                Err(Error::AlreadyInit)
+            };
+        }
+
+        let speaker = crate::initialise(options.clone())?;
+        let queue = Arc::new(Queue::default());
+
+        let worker_queue = Arc::clone(&queue);
+        std::thread::Builder::new()
+            .name(String::from("espeakng-speaker"))
+            .spawn(move || worker_queue.run_worker(&mut speaker.lock()))?;
+
+        let inner = Arc::new(HandleInner { queue, options });
+        *active = Arc::downgrade(&inner);
+
+        Ok(Self { inner })
+    }
+
+    /// Queue `func` to run on the background thread with [`Priority::Normal`], returning immediately.
+    ///
+    /// # Errors
+    /// See [`SpeakerHandle::submit_with_priority`].
+    pub fn submit<T, F>(&self, func: F) -> Result<PendingResult<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Speaker) -> T + Send + 'static,
+    {
+        self.submit_with_priority(Priority::default(), func)
+    }
+
+    /// Queue `func` to run on the background thread, returning immediately.
+    ///
+    /// If a lower priority request is currently synthesizing, it is cancelled.
+    ///
+    /// # Errors
+    /// [`Error::WorkerDisconnected`] if the background thread has stopped.
+    pub fn submit_with_priority<T, F>(
+        &self,
+        priority: Priority,
+        func: F,
+    ) -> Result<PendingResult<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Speaker) -> T + Send + 'static,
+    {
+        let (result_sender, result_receiver) = mpsc::sync_channel(1);
+        let job: Job = Box::new(move |speaker| {
+            // The receiver may have been dropped if the result is not wanted.
+            let _ = result_sender.send(func(speaker));
+        });
+
+        let queue = &self.inner.queue;
+        let mut state = queue.state.lock();
+        if state.closed {
+            return Err(Error::WorkerDisconnected);
+        }
+
+        if state.running.is_some_and(|running| running < priority) {
+            CANCEL_SYNTHESIS.store(true, Ordering::SeqCst);
+        }
+
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        state.jobs.push(QueuedJob {
+            priority,
+            sequence,
+            job,
+        });
+
+        drop(state);
+        queue.condvar.notify_one();
+
+        Ok(PendingResult(result_receiver))
+    }
+
+    /// Run `func` on the background thread with [`Priority::Normal`], blocking until it has completed.
+    ///
+    /// # Errors
+    /// [`Error::WorkerDisconnected`] if the background thread has stopped.
+    pub fn run<T, F>(&self, func: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Speaker) -> T + Send + 'static,
+    {
+        self.submit(func)?.wait()
+    }
+
+    /// Processes the given text into WAV audio data on the background thread.
+    ///
+    /// # Errors
+    /// See [`Speaker::synthesize`] and [`SpeakerHandle::run`].
+    pub fn synthesize(&self, text: impl Into<String>) -> Result<Vec<i16>> {
+        self.synthesize_with_priority(Priority::default(), text)
+    }
+
+    /// Processes the given text into WAV audio data on the background thread, with a given priority.
+    ///
+    /// # Errors
+    /// [`Error::Cancelled`] if a higher priority request preempted this one, otherwise see
+    /// [`Speaker::synthesize`] and [`SpeakerHandle::run`].
+    pub fn synthesize_with_priority(
+        &self,
+        priority: Priority,
+        text: impl Into<String>,
+    ) -> Result<Vec<i16>> {
+        let text = text.into();
+        self.submit_with_priority(priority, move |speaker| speaker.synthesize(&text))?
+            .wait()?
+    }
+}
+
+/// The result of a request queued with [`SpeakerHandle::submit`].
+pub struct PendingResult<T>(mpsc::Receiver<T>);
+
+impl<T> PendingResult<T> {
+    /// Block until the request has completed, returning the result.
+    ///
+    /// # Errors
+    /// [`Error::WorkerDisconnected`] if the background thread stopped before completing the request.
+    pub fn wait(self) -> Result<T> {
+        self.0.recv().map_err(|_| Error::WorkerDisconnected)
+    }
+
+    /// Return the result if the request has completed, without blocking.
+    ///
+    /// # Errors
+    /// [`Error::WorkerDisconnected`] if the background thread stopped before completing the request.
+    pub fn try_wait(&self) -> Result<Option<T>> {
+        match self.0.try_recv() {
+            Ok(result) => Ok(Some(result)),
+            Err(mpsc::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::TryRecvError::Disconnected) => Err(Error::WorkerDisconnected),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Compiling espeak language data from source, such as to build a custom language pack.
+//!
+//! ```no_run
+//! fn main() -> Result<(), espeakng::Error> {
+//!     let mut speaker = espeakng::initialise_and_lock(espeakng::InitOptions::default())?;
+//!
+//!     espeakng::langdata::LanguagePack::from_checkout("espeak-ng", "my-espeak-ng-data")
+//!         .dictionary("en")
+//!         .build(&mut speaker)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::{CompileFlags, Result, Speaker};
+
+/// The data to compile into a data directory, built with [`LanguagePack::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguagePack {
+    dictsource_dir: PathBuf,
+    phsource_dir: Option<PathBuf>,
+    output_dir: PathBuf,
+    dictionaries: Vec<String>,
+    flags: CompileFlags,
+}
+
+/// What was compiled by [`LanguagePack::build`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    /// Whether the phoneme data and intonations were compiled.
+    pub phonemes: bool,
+    /// The dictionaries which were compiled, in order.
+    pub dictionaries: Vec<String>,
+}
+
+impl LanguagePack {
+    /// Compile from an espeak-ng source checkout, using its `dictsource` and `phsource` directories.
+    ///
+    /// The phoneme data and intonations are always compiled, as dictionaries depend on them.
+    #[must_use]
+    pub fn from_checkout(checkout_dir: impl AsRef<Path>, output_dir: impl Into<PathBuf>) -> Self {
+        let checkout_dir = checkout_dir.as_ref();
+        Self {
+            dictsource_dir: checkout_dir.join("dictsource"),
+            phsource_dir: Some(checkout_dir.join("phsource")),
+            output_dir: output_dir.into(),
+            dictionaries: Vec::new(),
+            flags: CompileFlags::empty(),
+        }
+    }
+
+    /// Compile dictionaries only, from a directory of dictionary sources such as `en_rules` and `en_list`.
+    ///
+    /// `output_dir` must already contain compiled phoneme data, such as an installed `espeak-ng-data`.
+    #[must_use]
+    pub fn from_dictsource(
+        dictsource_dir: impl Into<PathBuf>,
+        output_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            dictsource_dir: dictsource_dir.into(),
+            phsource_dir: None,
+            output_dir: output_dir.into(),
+            dictionaries: Vec::new(),
+            flags: CompileFlags::empty(),
+        }
+    }
+
+    /// Add a dictionary to compile, such as `en`.
+    #[must_use]
+    pub fn dictionary(mut self, dict_name: impl Into<String>) -> Self {
+        self.dictionaries.push(dict_name.into());
+        self
+    }
+
+    /// Add every dictionary with a `_rules` file in the dictionary source directory.
+    ///
+    /// # Errors
+    /// If the dictionary source directory cannot be read.
+    pub fn all_dictionaries(mut self) -> Result<Self> {
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(&self.dictsource_dir)? {
+            let file_name = entry?.file_name();
+            if let Some(dict_name) = file_name.to_str().and_then(|n| n.strip_suffix("_rules")) {
+                found.push(dict_name.to_owned());
+            }
+        }
+
+        found.sort();
+        self.dictionaries.extend(found);
+        Ok(self)
+    }
+
+    /// Set the flags used to compile dictionaries.
+    #[must_use]
+    pub fn flags(mut self, flags: CompileFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Compile the language data into the output directory.
+    ///
+    /// espeak compiles dictionaries into the data directory it was initialised with, so `speaker`
+    /// is temporarily reinitialised with the output directory and restored afterwards.
+    ///
+    /// # Errors
+    /// If any compilation step fails, see [`Speaker::compile_phoneme_data`] and [`Speaker::compile_dictionary`].
+    pub fn build(&self, speaker: &mut Speaker) -> Result<BuildReport> {
+        std::fs::create_dir_all(&self.output_dir)?;
+
+        let mut report = BuildReport::default();
+        if let Some(phsource_dir) = &self.phsource_dir {
+            speaker.compile_phoneme_data(phsource_dir, &self.output_dir)?;
+            speaker.compile_intonations(
+                phsource_dir.join("intonation"),
+                self.output_dir.join("intonations"),
+            )?;
+
+            report.phonemes = true;
+        }
+
+        if self.dictionaries.is_empty() {
+            return Ok(report);
+        }
+
+        // The output directory has no voices yet, so the default voice is not selected.
+        let original_options = speaker.options.clone();
+        let build_options = original_options.clone().data_path(&self.output_dir);
+        let result = speaker.reinitialise_library(build_options).and_then(|()| {
+            self.dictionaries.iter().try_for_each(|dict_name| {
+                speaker.compile_dictionary(&self.dictsource_dir, dict_name, self.flags)?;
+                report.dictionaries.push(dict_name.clone());
+                Ok(())
+            })
+        });
+
+        // Restored even if compilation failed, so the speaker is still usable.
+        let restored = speaker.reinitialise(original_options);
+        result?;
+        restored?;
+        Ok(report)
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// A pronunciation override for a single word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LexiconEntry {
+    /// Pronounce the word using these phonemes, in espeak's phoneme notation.
+    Phonemes(String),
+    /// Speak this text in place of the word.
+    Text(String),
+}
+
+/// A set of runtime pronunciation overrides for a language dictionary.
+///
+/// Applied with [`crate::Speaker::apply_lexicon`], which copies the dictionary source files into a
+/// private directory, appends the entries to its `{dict_name}_extra` file, and compiles the
+/// dictionary there. Neither the dictionary source directory nor espeak's data directory is
+/// modified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lexicon {
+    dict_source_dir: PathBuf,
+    dict_name: String,
+    entries: BTreeMap<String, LexiconEntry>,
+}
+
+impl Lexicon {
+    /// Creates an empty lexicon for the dictionary `dict_name` (such as `en`), with the source
+    /// files (`en_rules`, `en_list`) located in `dict_source_dir`.
+    #[must_use]
+    pub fn new(dict_source_dir: impl Into<PathBuf>, dict_name: impl Into<String>) -> Self {
+        Self {
+            dict_source_dir: dict_source_dir.into(),
+            dict_name: dict_name.into(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `word` to be pronounced with `phonemes`, replacing any previous entry.
+    pub fn add_phonemes(&mut self, word: &str, phonemes: &str) -> &mut Self {
+        self.insert(word, LexiconEntry::Phonemes(phonemes.to_owned()))
+    }
+
+    /// Registers `word` to be spoken as `text`, replacing any previous entry.
+    pub fn add_replacement(&mut self, word: &str, text: &str) -> &mut Self {
+        self.insert(word, LexiconEntry::Text(text.to_owned()))
+    }
+
+    /// Registers a [`LexiconEntry`] for `word`, replacing any previous entry.
+    pub fn insert(&mut self, word: &str, entry: LexiconEntry) -> &mut Self {
+        self.entries.insert(word.to_lowercase(), entry);
+        self
+    }
+
+    /// Removes the entry for `word`, returning it if present.
+    pub fn remove(&mut self, word: &str) -> Option<LexiconEntry> {
+        self.entries.remove(&word.to_lowercase())
+    }
+
+    /// Fetches the entry for `word`, if present.
+    #[must_use]
+    pub fn get(&self, word: &str) -> Option<&LexiconEntry> {
+        self.entries.get(&word.to_lowercase())
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the registered words and their entries, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &LexiconEntry)> {
+        self.entries
+            .iter()
+            .map(|(word, entry)| (word.as_str(), entry))
+    }
+
+    pub(crate) fn dict_source_dir(&self) -> &Path {
+        &self.dict_source_dir
+    }
+
+    pub(crate) fn dict_name(&self) -> &str {
+        &self.dict_name
+    }
+
+    /// The names of the source files of the dictionary, as read by espeak's dictionary compiler.
+    pub(crate) fn source_file_names(&self) -> [String; 5] {
+        ["rules", "list", "listx", "emoji", "extra"]
+            .map(|suffix| format!("{}_{suffix}", self.dict_name))
+    }
+}
+
+/// Formats the lexicon in espeak's dictionary list format, as written to the `_extra` file.
+impl std::fmt::Display for Lexicon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (word, entry) in &self.entries {
+            // Multi-word entries must be wrapped in brackets.
+            if word.contains(char::is_whitespace) {
+                write!(f, "({word})")?;
+            } else {
+                f.write_str(word)?;
+            }
+
+            match entry {
+                LexiconEntry::Phonemes(phonemes) => writeln!(f, "\t{phonemes}")?,
+                LexiconEntry::Text(text) => writeln!(f, "\t$text {text}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
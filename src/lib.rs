@@ -9,6 +9,18 @@
 //! The raw bindings are re-exported via the [bindings] module however usage of this is `unsafe`
 //! and all safety guarantees of the [Speaker] object are considered broken if used.
 //!
+//! ## WebAssembly
+//! The crate builds for `wasm32-unknown-emscripten`, with some restrictions:
+//! - Only the retrieval output modes ([`OutputMode::Retrieval`] and [`OutputMode::Synchronous`]) are
+//!   supported, as there is no audio device.
+//! - The `espeak-ng-data` directory must be bundled into the virtual filesystem, such as with
+//!   `--preload-file`, and passed to [`InitOptions::data_path`].
+//! - [`SpeakerHandle`] requires a build with pthreads enabled.
+//!
+//! ## Embedded Data
+//! With the `embed-data` feature, the `espeak-ng-data` directory at `$ESPEAKNG_DATA_DIR` is embedded at
+//! compile time and used when [`InitOptions::data_path`] is not set, see `embedded_data_path`.
+//!
 //! ## Known Issues
 //! - [`Speaker::synthesize`] seems to emit broken WAV audio data, no idea how to fix.
 //!
@@ -16,8 +28,8 @@
 //! Generating phonemes from text:
 //! ```rust
 //! fn main() -> Result<(), espeakng::Error> {
-//!     // Get a reference to the global Speaker singleton, using default voice path and buffer length.
-//!     let mut speaker = espeakng::initialise(None)?.lock();
+//!     // Lock the global Speaker singleton, using default voice path and buffer length.
+//!     let mut speaker = espeakng::initialise_and_lock(espeakng::InitOptions::default())?;
 //!
 //!     // Generate the phonemes in standard mode.
 //!     let phonemes = speaker.text_to_phonemes("Hello World", espeakng::PhonemeGenOptions::Standard {
@@ -37,39 +49,259 @@
 )]
 
 use std::{
-    ffi::CStr,
-    io::{Read, Write},
+    borrow::Cow,
+    io::Write,
     marker::PhantomData,
-    os::unix::prelude::{AsRawFd, FromRawFd},
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
 use once_cell::sync::OnceCell;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, MutexGuard};
 
 pub use espeakng_sys as bindings;
 
+mod abbreviations;
+mod cache;
+mod callback;
+#[cfg(feature = "detect-language")]
+mod detect;
+mod disk_cache;
+mod document;
+#[cfg(feature = "embed-data")]
+mod embedded;
+#[cfg(feature = "emoji")]
+mod emoji;
 mod error;
+mod guards;
+mod handle;
+mod lexicon;
+mod locale;
+mod mixed;
+mod normalize;
+#[cfg(feature = "opus")]
+mod ogg_opus;
+mod overlay;
+mod segment;
+mod ssml;
 mod structs;
+mod synthesis;
+mod timing;
 mod utils;
-
-pub use error::{ESpeakNgError, Error};
+mod utterance;
+mod version;
+mod voice_details;
+mod wav;
+mod words;
+
+pub mod backend;
+pub mod batch;
+pub mod data_path;
+pub mod effects;
+pub mod langdata;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod phoneme;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod testing;
+
+pub use abbreviations::Abbreviations;
+use backend::{EspeakBackend, FfiBackend};
+pub use cache::SynthesisCache;
+pub use callback::{EventId, EventType, RawEvent, SynthCallbackHandler};
+pub use disk_cache::DiskCache;
+pub use document::{DocumentChunk, DocumentSynthesis, ReaderSynthesis};
+#[cfg(feature = "embed-data")]
+pub use embedded::embedded_data_path;
+#[cfg(feature = "emoji")]
+pub use emoji::EmojiNames;
+pub use error::{ESpeakNgError, Error, InvalidInput};
+pub use guards::{ParameterGuard, PhonemeTraceGuard, VoiceGuard};
+pub use handle::{PendingResult, Priority, SpeakerHandle};
+pub use lexicon::{Lexicon, LexiconEntry};
+pub use locale::{Currency, DateOrder, LocaleFormat};
+pub use mixed::VoiceRun;
+#[cfg(feature = "opus")]
+pub use ogg_opus::write_ogg_opus;
+pub use segment::{SayAs, Segment};
+pub use ssml::{VoiceChange, VoiceSelection};
 pub use structs::*;
+pub use synthesis::{Synthesis, SynthesisResult};
+pub use timing::{Highlight, Highlights, SynthesisTiming, WordTiming};
+pub use utterance::Utterance;
+pub use version::{Info, Version};
+pub use voice_details::{Formant, VoiceDetails};
+pub use wav::write_wav;
+pub use words::WordReplacements;
 
-use error::handle_error;
+use error::{handle_error, ErrorContext};
+use overlay::DataOverlay;
 
 use crate::utils::StringFromCPtr;
 
 pub type Result<T> = std::result::Result<T, Error>;
-type AudioBuffer = Mutex<Vec<i16>>;
+type SynthBuffer = Mutex<SynthOutput>;
+
+/// The output collected by the synth callback during a synthesis call.
+#[derive(Default)]
+struct SynthOutput {
+    audio: Vec<i16>,
+    /// Only captured if `Some`, as most calls only need the audio.
+    ///
+    /// Events are copied out within the callback, as the names they point to are freed afterwards.
+    events: Option<Vec<RawEvent>>,
+}
+
+impl SynthCallbackHandler for SynthOutput {
+    fn on_audio(&mut self, audio: &[i16]) {
+        self.audio.extend_from_slice(audio);
+    }
+
+    fn on_event(&mut self, event: RawEvent) {
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+    }
+}
+
+/// Collects only the events of a synthesis call, discarding the audio.
+#[derive(Default)]
+struct EventOutput(Vec<RawEvent>);
+
+impl SynthCallbackHandler for EventOutput {
+    fn on_audio(&mut self, _audio: &[i16]) {}
+
+    fn on_event(&mut self, event: RawEvent) {
+        self.0.push(event);
+    }
+}
+
+/// Counts the samples passed on to a handler, for [`SynthesisStats`].
+struct SampleCounter<'h> {
+    handler: &'h mut dyn SynthCallbackHandler,
+    samples: usize,
+}
+
+impl SynthCallbackHandler for SampleCounter<'_> {
+    fn on_audio(&mut self, audio: &[i16]) {
+        self.samples += audio.len();
+        self.handler.on_audio(audio);
+    }
+
+    fn on_event(&mut self, event: RawEvent) {
+        self.handler.on_event(event);
+    }
+}
+
+/// Where the synth callback sends output, pointed to by the user data of each synthesis call.
+enum CallbackTarget<'a> {
+    /// Collect the output into a buffer, for functions which cannot pass user data, see [`UNTAGGED_OUTPUT`].
+    Buffer(&'a SynthBuffer),
+    /// Collect the audio of an asynchronous utterance, started by [`Speaker::speak`].
+    Utterance(utterance::AsyncUtterance),
+    /// Pass the output straight to a handler without copying, see [`Speaker::synthesize_with_handler`].
+    ///
+    /// Not behind a Mutex, as espeak only calls the synth callback from one thread at a time.
+    Handler(std::cell::UnsafeCell<&'a mut dyn SynthCallbackHandler>),
+}
+
+impl CallbackTarget<'_> {
+    /// Pass a chunk of output from the synth callback to this target.
+    ///
+    /// # Safety
+    /// `user_data` must point to this target, and `events` to a valid event list.
+    unsafe fn receive(
+        &self,
+        user_data: *mut std::ffi::c_void,
+        wav_slice: &[i16],
+        events: *const bindings::espeak_EVENT,
+    ) {
+        match self {
+            CallbackTarget::Handler(handler) => {
+                // SAFETY: The handler is only accessed here, which is never called concurrently.
+                let handler = unsafe { &mut *handler.get() };
+                handler.on_audio(wav_slice);
+
+                for event in unsafe { utils::event_list(events) } {
+                    handler.on_event(RawEvent::from_raw(&event));
+                }
+            }
+            CallbackTarget::Buffer(synth_output) => {
+                let mut synth_output = synth_output.lock();
+                synth_output.audio.extend_from_slice(wav_slice);
+
+                if let Some(captured_events) = &mut synth_output.events {
+                    captured_events.extend(
+                        unsafe { utils::event_list(events) }
+                            .map(|event| RawEvent::from_raw(&event)),
+                    );
+                }
+            }
+            CallbackTarget::Utterance(utterance) => {
+                utterance.audio.lock().extend_from_slice(wav_slice);
+
+                let finished = unsafe { utils::event_list(events) }.any(|event| {
+                    event.type_ == bindings::espeak_EVENT_TYPE_espeakEVENT_MSG_TERMINATED
+                });
+
+                if finished {
+                    // SAFETY: The target was leaked by `Speaker::speak`, and this is the last event.
+                    let target =
+                        unsafe { Box::from_raw(user_data.cast::<CallbackTarget<'static>>()) };
+
+                    if let CallbackTarget::Utterance(utterance) = *target {
+                        utterance.finish();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// If the mbrola voice database `name` is in one of the locations espeak-ng searches.
+fn mbrola_database_installed(data_path: &std::path::Path, name: &str) -> bool {
+    let mut locations = vec![data_path.join("mbrola").join(name)];
+    if cfg!(unix) {
+        let system_mbrola = std::path::Path::new("/usr/share/mbrola");
+        locations.extend([
+            system_mbrola.join(name),
+            system_mbrola.join(name).join(name),
+            system_mbrola.join("voices").join(name),
+        ]);
+    }
+
+    locations.iter().any(|location| location.is_file())
+}
 
 static SPEAKER: OnceCell<Mutex<Speaker>> = OnceCell::new();
 
+/// Set to abort the synthesis in progress, checked in the synth callback.
+static CANCEL_SYNTHESIS: AtomicBool = AtomicBool::new(false);
+
+/// The output of synthesis functions which cannot pass user data to the callback, such as [`Speaker::speak_char`].
+static UNTAGGED_OUTPUT: SynthBuffer = Mutex::new(SynthOutput {
+    audio: Vec::new(),
+    events: None,
+});
+
 /// Initialise the internal espeak-ng library. If already initialised, that [Speaker] is returned.
 ///
 /// # Errors
 /// If any initialisation steps fail, such as initialising `espeakNG` and setting the default voice.
-pub fn initialise(voice_path: Option<&str>) -> Result<&'static Mutex<Speaker>> {
-    SPEAKER.get_or_try_init(|| Speaker::initialise(voice_path).map(Mutex::new))
+pub fn initialise(options: InitOptions) -> Result<&'static Mutex<Speaker>> {
+    SPEAKER.get_or_try_init(|| Speaker::initialise(options).map(Mutex::new))
+}
+
+/// Initialise the internal espeak-ng library if needed, then lock the [Speaker].
+///
+/// The lock is held until the returned guard is dropped, so avoid keeping it longer than needed.
+///
+/// # Errors
+/// See [`initialise`].
+pub fn initialise_and_lock(options: InitOptions) -> Result<MutexGuard<'static, Speaker>> {
+    initialise(options).map(Mutex::lock)
 }
 
 /// Gets the currently initialised [Speaker]. If not set, none is returned.
@@ -77,154 +309,153 @@ pub fn get() -> Option<&'static Mutex<Speaker>> {
     SPEAKER.get()
 }
 
-pub struct Speaker {
-    _marker: PhantomData<std::cell::Cell<()>>,
+/// Initialise the internal espeak-ng library with default options if needed, then run `func` with
+/// the [Speaker] locked, releasing the lock afterwards.
+///
+/// # Errors
+/// See [`initialise`] + any errors returned from `func`.
+pub fn with_speaker<T>(func: impl FnOnce(&mut Speaker) -> Result<T>) -> Result<T> {
+    func(&mut initialise(InitOptions::default())?.lock())
 }
 
-impl Speaker {
-    pub const DEFAULT_VOICE: &'static str = "gmw/en";
-
-    fn initialise(voice_path: Option<&str>) -> Result<Self> {
-        unsafe extern "C" fn synth_callback(
-            wav: *mut i16,
-            sample_count: i32,
-            events: *mut bindings::espeak_EVENT,
-        ) -> i32 {
-            let panic_res = std::panic::catch_unwind(|| {
-                if wav.is_null() || sample_count == 0 {
-                    return 0;
-                }
-
-                let mut new_ptr = events;
+/// Initialise the internal espeak-ng library if needed, then lock the [Speaker], waiting at most `timeout`.
+///
+/// # Errors
+/// - [`Error::LockTimeout`] if the [Speaker] could not be locked within `timeout`.
+/// - See [`initialise`].
+pub fn try_initialise_for(
+    options: InitOptions,
+    timeout: Duration,
+) -> Result<MutexGuard<'static, Speaker>> {
+    initialise(options)?
+        .try_lock_for(timeout)
+        .ok_or(Error::LockTimeout)
+}
 
-                // Loop through this C event until the terminate event, as this contains the pointer to the audio buffer
-                let terminate_event = loop {
-                    let event = unsafe { *new_ptr };
-                    if event.type_ != bindings::espeak_EVENT_TYPE_espeakEVENT_LIST_TERMINATED {
-                        break event;
-                    }
+/// Lock the currently initialised [Speaker], waiting at most `timeout`.
+///
+/// # Errors
+/// - [`Error::NotInitialised`] if [`initialise`] has not been called.
+/// - [`Error::LockTimeout`] if the [Speaker] could not be locked within `timeout`.
+pub fn try_get_for(timeout: Duration) -> Result<MutexGuard<'static, Speaker>> {
+    get()
+        .ok_or(Error::NotInitialised)?
+        .try_lock_for(timeout)
+        .ok_or(Error::LockTimeout)
+}
 
-                    new_ptr = unsafe { new_ptr.add(1) };
-                };
+/// A function run on each sentence of text before it is passed to espeak.
+type TextFilter = Box<dyn FnMut(&str) -> Cow<'_, str> + Send>;
 
-                unsafe {
-                    if let Some(audio_buffer) =
-                        *(terminate_event.user_data as *const Option<&AudioBuffer>)
-                    {
-                        let wav_slice: &[i16] =
-                            std::slice::from_raw_parts_mut(wav, sample_count as usize);
-                        audio_buffer.lock().extend(wav_slice);
-                    }
-                }
+pub struct Speaker<B = FfiBackend> {
+    backend: B,
+    /// The data directory holding custom variants, created by [`Speaker::set_voice_attributes`].
+    ///
+    /// Declared after `backend`, so espeak is terminated before the directory is removed.
+    data_overlay: Option<DataOverlay>,
+    options: InitOptions,
+    text_filter: Option<TextFilter>,
+    normalization: Normalization,
+    abbreviations: Option<Abbreviations>,
+    locale_format: Option<LocaleFormat>,
+    word_replacements: Option<WordReplacements>,
+    #[cfg(feature = "emoji")]
+    emoji_names: Option<EmojiNames>,
+    last_stats: Option<SynthesisStats>,
+    /// Computed by [`Speaker::preprocessing_fingerprint`], and reset by [`Speaker::invalidate_cache`].
+    cache_fingerprint: Option<u64>,
+    /// The variant written by [`Speaker::set_voice_attributes`], part of the cache fingerprint.
+    custom_variant: Option<String>,
+    /// The list set by [`Speaker::set_punctuation_list`], part of the cache fingerprint.
+    punctuation_list: Option<Vec<char>>,
+    /// If libsonic is linked, cached by [`Speaker::sonic_available`].
+    sonic: Option<bool>,
+    /// If speechPlayer is linked, cached by [`Speaker::speech_player_available`].
+    speech_player: Option<bool>,
+    /// The installed voices, cached by [`Speaker::voices`].
+    voices: Option<Vec<Voice>>,
+    max_input_length: Option<usize>,
+    cache: Option<SynthesisCache>,
+    disk_cache: Option<DiskCache>,
+    _marker: PhantomData<std::cell::Cell<()>>,
+}
 
-                0
-            });
+impl<B: EspeakBackend> Speaker<B> {
+    /// The length of a [`Parameter::Wordgap`] unit at [`WordsPerMinute::NORMAL`], in milliseconds.
+    const WORD_GAP_UNIT_MS: u64 = 10;
 
-            panic_res.unwrap_or_else(|err| {
-                eprintln!("Panic during Rust -> C -> Rust callback: {err:?}");
-                std::process::abort()
-            })
+    /// Wrap an alternative backend, such as a fake for testing code built on [Speaker].
+    ///
+    /// The backend is used as if initialised with the default [`InitOptions`].
+    pub fn with_backend(backend: B) -> Self {
+        Self {
+            backend,
+            data_overlay: None,
+            options: InitOptions::default(),
+            text_filter: None,
+            normalization: Normalization::empty(),
+            abbreviations: None,
+            locale_format: None,
+            word_replacements: None,
+            #[cfg(feature = "emoji")]
+            emoji_names: None,
+            last_stats: None,
+            cache_fingerprint: None,
+            custom_variant: None,
+            punctuation_list: None,
+            sonic: None,
+            speech_player: None,
+            voices: None,
+            max_input_length: None,
+            cache: None,
+            disk_cache: None,
+            _marker: PhantomData,
         }
+    }
 
-        let voice_path = voice_path.map(utils::null_term);
-        unsafe {
-            bindings::espeak_SetSynthCallback(Some(synth_callback));
-            bindings::espeak_ng_InitializePath(match voice_path {
-                Some(path) => path.as_ptr(),
-                None => std::ptr::null(),
-            });
-
-            handle_error(bindings::espeak_ng_Initialize(std::ptr::null_mut()))?;
-            handle_error(bindings::espeak_ng_InitializeOutput(1, 0, std::ptr::null()))?;
-        }
+    /// Get the backend, such as to check the text a fake backend was given.
+    #[must_use]
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
 
-        let mut self_ = Self {
-            _marker: PhantomData,
-        };
-        self_.set_voice_raw(Speaker::DEFAULT_VOICE)?;
-        Ok(self_)
+    /// Get the backend mutably, such as to add canned output to a fake backend.
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
     }
 
     /// Fetch and clone the currently set voice.
     ///
     /// # Panics
-    /// Panics if espeak-ng has somehow had the current voice reset, which should not happen.
+    /// Panics if espeak-ng has somehow had the current voice reset, see [`Speaker::try_current_voice`].
     #[must_use]
     pub fn get_current_voice(&self) -> Voice {
-        let voice_ptr = unsafe { bindings::espeak_GetCurrentVoice() };
-        assert!(!voice_ptr.is_null(), "voice should not be null");
-
-        Voice::from(unsafe { *voice_ptr })
+        self.try_current_voice().expect("voice should not be null")
     }
 
-    /// Fetch the espeak voices currently installed.
+    /// Fetch and clone the currently set voice, or [`None`] if espeak-ng has no voice set, such as
+    /// after a failed [`Speaker::reinitialise`].
     #[must_use]
-    pub fn get_voices() -> Vec<Voice> {
-        let mut array = unsafe { bindings::espeak_ListVoices(std::ptr::null_mut()) };
-        let mut buf = Vec::new();
-
-        unsafe {
-            loop {
-                let next = array.read();
-
-                if next.is_null() {
-                    break buf;
-                }
-
-                buf.push(Voice::from(*next));
-                array = array.add(1);
-            }
-        }
+    pub fn try_current_voice(&self) -> Option<Voice> {
+        self.backend.current_voice()
     }
 
-    /// Set the voice for future espeak calls.
+    /// Get the espeak voices currently installed, fetched on first use then cached.
     ///
-    /// # Errors
-    /// See [`Speaker::set_voice_raw`]
-    pub fn set_voice(&mut self, voice: &Voice) -> Result<()> {
-        self.set_voice_raw(&voice.filename)
+    /// The cache is cleared by [`Speaker::reinitialise`] and [`Speaker::refresh_voices`].
+    pub fn voices(&mut self) -> &[Voice] {
+        let backend = &mut self.backend;
+        self.voices.get_or_insert_with(|| backend.list_voices())
     }
 
-    /// Set the voice for future espeak calls based on the filename
-    ///
-    /// # Errors
-    /// [`ESpeakNgError::VoiceNotFound`]
-    pub fn set_voice_raw(&mut self, filename: &str) -> Result<()> {
-        let mbrola_voice = filename.starts_with("mb/");
-
-        // We have to do our own VoiceNotFound check as espeakNG seems to internally fail at that.
-        if mbrola_voice {
-            let mut voice_path = Self::info().1;
-            voice_path.push(format!("voices/{filename}"));
-            if !voice_path.exists() {
-                return Err(Error::ESpeakNg(ESpeakNgError::VoiceNotFound));
-            }
-        }
-
-        let name_null_term = utils::null_term(filename);
-        if mbrola_voice {
-            // Now we are sure the voice is set, we can loop until espeakNG shuts up.
-            while let Err(err) =
-                handle_error(unsafe { bindings::espeak_ng_SetVoiceByName(name_null_term.as_ptr()) })
-            {
-                if let Error::ESpeakNg(espeak_err) = err {
-                    if espeak_err == ESpeakNgError::VoiceNotFound {
-                        continue;
-                    }
-                }
-
-                return Err(err);
-            }
-        } else {
-            handle_error(unsafe { bindings::espeak_ng_SetVoiceByName(name_null_term.as_ptr()) })?;
-        }
-
-        Ok(())
+    /// Fetch the installed voices again, such as after voices have been added to the data directory.
+    pub fn refresh_voices(&mut self) -> &[Voice] {
+        self.voices.insert(self.backend.list_voices())
     }
 
     /// Get the value of either the currently set or default value of a settings parameter.
     pub fn get_parameter(&mut self, param: Parameter, default: bool) -> i32 {
-        unsafe { bindings::espeak_GetParameter(param as u32, i32::from(!default)) }
+        self.backend.get_parameter(param, default)
     }
 
     /// Set a settings parameter for future espeak calls.
@@ -238,191 +469,2330 @@ impl Speaker {
         new_value: i32,
         relative: bool,
     ) -> Result<()> {
-        handle_error(unsafe {
-            bindings::espeak_ng_SetParameter(param as u32, new_value, i32::from(relative))
-        })
+        self.backend.set_parameter(param, new_value, relative)
     }
 
-    /// Get the version string and voice path of the internal C library.
-    #[must_use]
-    pub fn info() -> (String, std::path::PathBuf) {
-        let mut c_voice_path: *const libc::c_char = std::ptr::null();
+    /// Set the speaking rate, rates above 450 require the `sonic` feature, see [`WordsPerMinute::MAX`].
+    ///
+    /// # Errors
+    /// - [`ESpeakNgError::NotSupported`] if the rate is above 450 and [`Speaker::sonic_available`] is false.
+    /// - If the internal C call fails.
+    pub fn set_rate(&mut self, words_per_minute: WordsPerMinute) -> Result<()> {
+        self.check_rate(words_per_minute)?;
+        self.set_parameter(Parameter::Rate, words_per_minute.get().into(), false)
+    }
 
-        unsafe {
-            let version_string = bindings::espeak_Info(std::ptr::addr_of_mut!(c_voice_path));
+    /// Fail if `words_per_minute` would be capped, as libsonic is not linked.
+    fn check_rate(&mut self, words_per_minute: WordsPerMinute) -> Result<()> {
+        if words_per_minute > WordsPerMinute::MAX_WITHOUT_SONIC && !self.sonic_available() {
+            return Err(Error::ESpeakNg {
+                func: "espeak_ng_SetParameter",
+                code: ESpeakNgError::NotSupported,
+                context: None,
+            });
+        }
 
-            (
-                String::from_cptr(version_string),
-                std::path::PathBuf::from(String::from_cptr(c_voice_path)),
-            )
+        Ok(())
+    }
+
+    /// If the linked espeak-ng was built with libsonic, so rates above 450 words per minute are
+    /// spoken faster instead of being capped.
+    ///
+    /// This is found by comparing the length of audio synthesized at 450 and 900 words per minute,
+    /// then cached. If no audio can be retrieved, such as with [`OutputMode::Playback`], the
+    /// `sonic` feature is trusted instead.
+    pub fn sonic_available(&mut self) -> bool {
+        if let Some(sonic) = self.sonic {
+            return sonic;
         }
+
+        let sonic = self
+            .probe_sonic()
+            .unwrap_or(None)
+            .unwrap_or(cfg!(feature = "sonic"));
+
+        self.sonic = Some(sonic);
+        sonic
     }
 
-    fn _synthesize(&mut self, text: &str, user_data: Option<&AudioBuffer>) -> Result<()> {
-        let text_nul_term = utils::null_term(text);
+    /// Compare synthesis at the capped rate and double it, returning [`None`] if no audio was retrieved.
+    fn probe_sonic(&mut self) -> Result<Option<bool>> {
+        const PROBE_TEXT: &str = "The quick brown fox jumps over the lazy dog.";
 
-        handle_error(unsafe {
-            bindings::espeak_ng_Synthesize(
-                text_nul_term.as_ptr().cast::<std::ffi::c_void>(),
-                text_nul_term.len(),
-                0,
-                bindings::espeak_POSITION_TYPE_POS_CHARACTER,
-                0,
-                bindings::espeakCHARS_UTF8,
-                std::ptr::null_mut(),
-                (&user_data.map(|ud| ud as *const _) as *const _) as *mut std::ffi::c_void,
-            )
-        })?;
+        let last_stats = self.last_stats;
+        let synthesize_at = |speaker: &mut Self, rate: i32| {
+            speaker.with_parameters(&[(Parameter::Rate, rate)], |speaker| {
+                speaker.synthesize_with_options(PROBE_TEXT, &SynthesisOptions::default())
+            })
+        };
 
-        // Wait until TTS has finished being generated, could be made concurrent but global state....
-        handle_error(unsafe { bindings::espeak_ng_Synchronize() })?;
+        let max_rate = i32::from(WordsPerMinute::MAX_WITHOUT_SONIC.get());
+        let capped = synthesize_at(self, max_rate);
+        let fast = synthesize_at(self, max_rate * 2);
+        self.last_stats = last_stats;
 
-        Ok(())
+        let (capped, fast) = (capped?, fast?);
+        if capped.is_empty() {
+            return Ok(None);
+        }
+
+        // Without libsonic, both are spoken at the capped rate, so are the same length.
+        Ok(Some(fast.len() * 4 < capped.len() * 3))
     }
 
-    /// Processes the given text into WAV audio data.
+    /// Get the current speaking rate.
+    pub fn rate(&mut self) -> WordsPerMinute {
+        WordsPerMinute::clamped(self.get_parameter(Parameter::Rate, false))
+    }
+
+    /// Set the base pitch.
     ///
     /// # Errors
-    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
-    pub fn synthesize(&mut self, text: &str) -> Result<Vec<i16>> {
-        let audio_buffer: AudioBuffer = Mutex::new(Vec::<i16>::new());
-        self._synthesize(text, Some(&audio_buffer))?;
-        Ok(audio_buffer.into_inner())
+    /// If the internal C call fails.
+    pub fn set_pitch(&mut self, pitch: Pitch) -> Result<()> {
+        self.set_parameter(Parameter::Pitch, pitch.get().into(), false)
     }
 
-    /// Processes the given text into WAV audio data and writes it to a given file.
-    ///
-    /// This handles the `Vec<i16>` to `Vec<u8>` conversion internally.
+    /// Get the current base pitch.
+    pub fn pitch(&mut self) -> Pitch {
+        Pitch::clamped(self.get_parameter(Parameter::Pitch, false))
+    }
+
+    /// Set the volume.
     ///
     /// # Errors
-    /// See [`Speaker::synthesize`] + the file writing failed.
-    pub fn synthesize_to_file(&mut self, file: &mut std::fs::File, text: &str) -> Result<()> {
-        let audio_data_i16 = self.synthesize(text)?;
+    /// If the internal C call fails.
+    pub fn set_volume(&mut self, volume: Volume) -> Result<()> {
+        self.set_parameter(Parameter::Volume, volume.get().into(), false)
+    }
 
-        let audio_data: Vec<u8> = audio_data_i16
-            .into_iter()
-            .flat_map(i16::to_le_bytes)
-            .collect();
-        file.write_all(&audio_data)?;
-        Ok(())
+    /// Get the current volume.
+    pub fn volume(&mut self) -> Volume {
+        Volume::clamped(self.get_parameter(Parameter::Volume, false))
     }
 
-    /// Processes the given text into phonemes, depending on which [`PhonemeGenOptions`] are passed.
-    ///
-    /// This will only return [None] if [`PhonemeGenOptions::MbrolaFile`] is passed.
+    /// Set the pitch range, between 0-100 inclusive.
     ///
     /// # Errors
-    /// If [`PhonemeGenOptions::Mbrola`] or [`PhonemeGenOptions::MbrolaFile`] is passed, internal C calls may fail.
-    pub fn text_to_phonemes(
-        &mut self,
-        text: &str,
-        option: PhonemeGenOptions<'_>,
-    ) -> Result<Option<String>> {
-        let file = match option {
-            PhonemeGenOptions::MbrolaFile(file) => Some(file),
-            _ => None,
-        };
+    /// See [`Speaker::set_parameter_checked`]
+    pub fn set_range(&mut self, range: u8) -> Result<()> {
+        self.set_parameter_checked(Parameter::Range, i32::from(range))
+    }
 
-        match option {
-            PhonemeGenOptions::Standard {
-                text_mode,
-                phoneme_mode,
-            } => Ok(Some(self.text_to_phonemes_standard(
-                text,
-                text_mode,
-                phoneme_mode,
-            ))),
-            PhonemeGenOptions::Mbrola | PhonemeGenOptions::MbrolaFile(_) => {
-                self.text_to_phonemes_mbrola(text, file)
+    /// Set how capital letters are pronounced.
+    ///
+    /// # Errors
+    /// - [`Error::ParameterOutOfRange`] if [`CapitalsMode::RaisePitch`] is below [`CapitalsMode::MIN_RAISE_PITCH`].
+    /// - If the internal C call fails.
+    pub fn set_capitals_mode(&mut self, mode: CapitalsMode) -> Result<()> {
+        if let CapitalsMode::RaisePitch(hz) = mode {
+            if hz < CapitalsMode::MIN_RAISE_PITCH {
+                return Err(Error::ParameterOutOfRange {
+                    param: Parameter::Capitals,
+                    value: i32::from(hz),
+                    range: i32::from(CapitalsMode::MIN_RAISE_PITCH)..=i32::from(u16::MAX),
+                });
             }
         }
+
+        self.set_parameter(Parameter::Capitals, mode.value(), false)
     }
 
-    fn text_to_phonemes_standard(
-        &mut self,
-        text: &str,
-        text_mode: TextMode,
-        phoneme_mode: PhonemeMode,
-    ) -> String {
-        let text_nul_term = utils::null_term(text);
+    /// Get the current [`CapitalsMode`].
+    pub fn capitals_mode(&mut self) -> CapitalsMode {
+        CapitalsMode::from_value(self.get_parameter(Parameter::Capitals, false))
+    }
 
-        let output = unsafe {
-            CStr::from_ptr(bindings::espeak_TextToPhonemes(
-                &mut text_nul_term.as_ptr().cast() as *mut *const std::ffi::c_void,
-                text_mode as i32,
-                phoneme_mode.bits() as i32,
-            ))
+    /// Set the pause between words, rounded to the nearest unit espeak supports at the current rate.
+    ///
+    /// # Errors
+    /// - [`Error::ParameterOutOfRange`] if the gap is too long to be represented.
+    /// - If the internal C call fails.
+    pub fn set_word_gap(&mut self, word_gap: Duration) -> Result<()> {
+        // Word gap units shrink as the rate increases, as espeak scales pauses by the speed.
+        let rate = u64::try_from(self.get_parameter(Parameter::Rate, false)).unwrap_or(0);
+        let divisor = Self::WORD_GAP_UNIT_MS * u64::from(WordsPerMinute::NORMAL.get());
+        let millis = u64::try_from(word_gap.as_millis()).unwrap_or(u64::MAX);
+
+        let units = millis.saturating_mul(rate).saturating_add(divisor / 2) / divisor;
+        let Ok(units) = i32::try_from(units) else {
+            return Err(Error::ParameterOutOfRange {
+                param: Parameter::Wordgap,
+                value: i32::MAX,
+                range: 0..=i32::MAX,
+            });
         };
 
-        output.to_string_lossy().to_string()
+        self.set_parameter(Parameter::Wordgap, units, false)
     }
 
-    fn text_to_phonemes_mbrola(
-        &mut self,
-        text: &str,
-        file: Option<&dyn AsRawFd>,
-    ) -> Result<Option<String>> {
-        if !self.get_current_voice().filename.starts_with("mb/") {
-            return Err(Error::MbrolaWithoutMbrolaVoice);
-        };
-
-        // If file is not passed, generate a fake FD to store the data in
-        let raw_file_fd = match file {
-            Some(file) => file.as_raw_fd(),
-            None => unsafe { libc::memfd_create(c"".as_ptr(), 0) },
-        };
+    /// Get the pause between words, at the current rate.
+    pub fn word_gap(&mut self) -> Duration {
+        let units = u64::try_from(self.get_parameter(Parameter::Wordgap, false)).unwrap_or(0);
+        let rate = u64::try_from(self.get_parameter(Parameter::Rate, false)).unwrap_or(0);
+        if rate == 0 {
+            return Duration::ZERO;
+        }
 
-        // Generate fake C File from this FD
-        let raw_file = unsafe {
-            let raw_file_ptr = bindings::fdopen(raw_file_fd, c"w+".as_ptr());
-            std::ptr::NonNull::new(raw_file_ptr)
-                .ok_or_else(|| Error::OtherC(Some(errno::errno())))?
-        };
+        let normal_rate = u64::from(WordsPerMinute::NORMAL.get());
+        Duration::from_millis(units * Self::WORD_GAP_UNIT_MS * normal_rate / rate)
+    }
 
-        // Set the phoneme output to the stream
-        unsafe {
-            bindings::espeak_SetPhonemeTrace(
-                bindings::espeakPHONEMES_MBROLA as i32,
-                raw_file.as_ptr(),
-            );
+    /// Set a settings parameter for future espeak calls, validating the value against
+    /// [`Parameter::valid_range`] first.
+    ///
+    /// # Errors
+    /// - [`Error::ParameterOutOfRange`] if the value is outside of the parameter's valid range.
+    /// - If the internal C call fails.
+    pub fn set_parameter_checked(&mut self, param: Parameter, new_value: i32) -> Result<()> {
+        if let Some(range) = param.valid_range() {
+            if !range.contains(&new_value) {
+                return Err(Error::ParameterOutOfRange {
+                    param,
+                    value: new_value,
+                    range,
+                });
+            }
         }
 
-        // Generate TTS, this will populate the phoneme trace
-        let result = self._synthesize(text, None);
+        self.set_parameter(param, new_value, false)
+    }
 
-        // Reset the phoneme trace back to stdout, to avoid side effects
-        unsafe { bindings::espeak_SetPhonemeTrace(0, std::ptr::null_mut()) };
+    /// Capture the current value of every parameter in [`ParameterProfile::PARAMETERS`].
+    pub fn snapshot_parameters(&mut self) -> ParameterProfile {
+        ParameterProfile::from_fn(|param| self.get_parameter(param, false))
+    }
 
-        if file.is_none() {
-            let mut file = unsafe {
-                // Seek to the start of the fake_file, now it has been written to
-                bindings::fseek(raw_file.as_ptr(), 0, 0);
+    /// Capture the default value of every parameter in [`ParameterProfile::PARAMETERS`].
+    pub fn default_parameters(&mut self) -> ParameterProfile {
+        ParameterProfile::from_fn(|param| self.get_parameter(param, true))
+    }
 
-                // Transfer FD ownership from C to Rust
-                let dup_fd = libc::dup(raw_file_fd);
-                bindings::fclose(raw_file.as_ptr());
+    /// Set every parameter stored in the profile for future espeak calls.
+    ///
+    /// # Errors
+    /// If any of the internal C calls fail, leaving the parameters before it applied.
+    pub fn apply_parameters(&mut self, profile: &ParameterProfile) -> Result<()> {
+        for (param, value) in profile.iter() {
+            self.set_parameter(param, value, false)?;
+        }
 
-                // SAFETY: ^ must have just occured
-                std::fs::File::from_raw_fd(dup_fd)
-            };
+        Ok(())
+    }
 
-            // Now handle possible errors, as we can return without leak.
-            result?;
+    /// Get the sample rate of the audio output by espeak, in Hz.
+    #[must_use]
+    pub fn sample_rate(&self) -> u32 {
+        self.backend.sample_rate()
+    }
 
-            let mut buf = Vec::new();
-            file.read_to_end(&mut buf)?;
-            Ok(Some(String::from_utf8(buf)?))
-        } else {
-            // Data has been written to the file passed in, close the C version of the file.
-            unsafe { bindings::fclose(raw_file.as_ptr()) };
-            // Now handle possible errors, and if successful get rid of any return value.
-            result.map(|()| None)
+    /// Limit the length of text accepted by synthesis and phoneme generation, in bytes, or
+    /// [`None`] to accept any length, the default.
+    pub fn set_max_input_length(&mut self, limit: Option<usize>) {
+        self.max_input_length = limit;
+    }
+
+    /// Get the limit set by [`Speaker::set_max_input_length`].
+    #[must_use]
+    pub fn max_input_length(&self) -> Option<usize> {
+        self.max_input_length
+    }
+
+    /// Reject empty text, or text longer than [`Speaker::max_input_length`].
+    fn check_input_length(&self, length: usize) -> Result<()> {
+        if length == 0 {
+            return Err(Error::InvalidInput(InvalidInput::Empty));
+        }
+
+        match self.max_input_length {
+            Some(limit) if length > limit => {
+                Err(Error::InvalidInput(InvalidInput::TooLong { length, limit }))
+            }
+            _ => Ok(()),
         }
     }
-}
 
-impl Drop for Speaker {
-    fn drop(&mut self) {
-        unsafe { bindings::espeak_ng_Terminate() };
+    /// Cache the results of [`Speaker::synthesize`] and [`Speaker::text_to_phonemes`], returning
+    /// them without synthesizing when the same text is repeated with the same voice and parameters.
+    /// Pass [`None`] to stop caching.
+    ///
+    /// The cache is cleared when text preprocessing, dictionaries or voice attributes change.
+    pub fn set_synthesis_cache(&mut self, cache: Option<SynthesisCache>) {
+        self.cache = cache;
+    }
+
+    /// Get the cache set by [`Speaker::set_synthesis_cache`], such as to read the hit rate.
+    #[must_use]
+    pub fn synthesis_cache(&self) -> Option<&SynthesisCache> {
+        self.cache.as_ref()
+    }
+
+    /// Get the cache set by [`Speaker::set_synthesis_cache`], such as to clear it.
+    pub fn synthesis_cache_mut(&mut self) -> Option<&mut SynthesisCache> {
+        self.cache.as_mut()
+    }
+
+    /// Keep synthesized audio in a directory, so it is reused by later runs of the program. Pass
+    /// [`None`] to stop using it, without removing the entries.
+    ///
+    /// Results are looked up in any [`SynthesisCache`] first, then the disk cache. Audio is not
+    /// stored on disk while a text filter is set, see [`DiskCache`].
+    pub fn set_disk_cache(&mut self, disk_cache: Option<DiskCache>) {
+        self.disk_cache = disk_cache;
+    }
+
+    /// Get the cache set by [`Speaker::set_disk_cache`], such as to clear it.
+    pub fn disk_cache_mut(&mut self) -> Option<&mut DiskCache> {
+        self.disk_cache.as_mut()
+    }
+
+    /// Remove cached results and miss stored results, after changing state which is not part of
+    /// the cache key, such as text preprocessing.
+    fn invalidate_cache(&mut self) {
+        self.cache_fingerprint = None;
+        if let Some(cache) = &mut self.cache {
+            cache.clear();
+        }
+    }
+
+    /// A fingerprint of the state outside of each [`cache::CacheKey`] which changes the output, such
+    /// as text preprocessing, which is stable between runs so [`DiskCache`] entries are only reused
+    /// with the same configuration.
+    ///
+    /// Text filters are closures, so cannot be included.
+    fn preprocessing_fingerprint(&mut self) -> u64 {
+        if let Some(fingerprint) = self.cache_fingerprint {
+            return fingerprint;
+        }
+
+        let mut hasher = cache::StableHasher::new();
+        hasher.write_u64(u64::from(self.normalization.bits()));
+        hasher.write_option(self.abbreviations.as_ref(), Abbreviations::fingerprint);
+        hasher.write_option(self.locale_format.as_ref(), LocaleFormat::fingerprint);
+        hasher.write_option(
+            self.word_replacements.as_ref(),
+            WordReplacements::fingerprint,
+        );
+        #[cfg(feature = "emoji")]
+        hasher.write_option(self.emoji_names.as_ref(), EmojiNames::fingerprint);
+        hasher.write_option(self.custom_variant.as_ref(), |variant, hasher| {
+            hasher.write_str(variant);
+        });
+        hasher.write_option(self.punctuation_list.as_ref(), |punctuation, hasher| {
+            hasher.write_str(&punctuation.iter().collect::<String>());
+        });
+
+        let fingerprint = hasher.finish();
+        self.cache_fingerprint = Some(fingerprint);
+        fingerprint
+    }
+
+    /// Return the cached result of `generate` for `text`, or run and cache it.
+    fn with_cache<T: cache::Cacheable>(
+        &mut self,
+        output: cache::CacheOutput,
+        text: &str,
+        generate: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        if self.cache.is_none() && self.disk_cache.is_none() {
+            return generate(self);
+        }
+
+        let key = cache::CacheKey {
+            output,
+            voice: self.try_current_voice(),
+            parameters: self.snapshot_parameters(),
+            preprocessing: self.preprocessing_fingerprint(),
+            text: text.to_owned(),
+        };
+
+        let cached = self.cache.as_mut().and_then(|cache| cache.get(&key));
+        if let Some(value) = cached.and_then(T::from_value) {
+            return Ok(value);
+        }
+
+        // A text filter is a closure which cannot be fingerprinted, so is only cached in memory.
+        let use_disk_cache = self.text_filter.is_none();
+        let stored = self
+            .disk_cache
+            .as_ref()
+            .filter(|_| use_disk_cache)
+            .and_then(|cache| cache.get(&key));
+        if let Some(stored) = stored {
+            if let Some(cache) = &mut self.cache {
+                cache.insert(key.clone(), stored.clone());
+            }
+
+            if let Some(value) = T::from_value(stored) {
+                return Ok(value);
+            }
+        }
+
+        let value = generate(self)?;
+        let cached = value.clone().into_value();
+        if let Some(disk_cache) = self.disk_cache.as_mut().filter(|_| use_disk_cache) {
+            disk_cache.insert(&key, &cached);
+        }
+
+        if let Some(cache) = &mut self.cache {
+            cache.insert(key, cached);
+        }
+
+        Ok(value)
+    }
+
+    /// Get the metrics of the last completed synthesis call, or [None] if nothing has been synthesized.
+    #[must_use]
+    pub fn stats(&self) -> Option<SynthesisStats> {
+        self.last_stats
+    }
+}
+
+impl Speaker {
+    pub const DEFAULT_VOICE: &'static str = "gmw/en";
+
+    fn initialise(options: InitOptions) -> Result<Self> {
+        Self::initialise_library(&options)?;
+
+        let mut self_ = Self {
+            backend: FfiBackend::new(),
+            data_overlay: None,
+            options,
+            text_filter: None,
+            normalization: Normalization::empty(),
+            abbreviations: None,
+            locale_format: None,
+            word_replacements: None,
+            #[cfg(feature = "emoji")]
+            emoji_names: None,
+            last_stats: None,
+            cache_fingerprint: None,
+            custom_variant: None,
+            punctuation_list: None,
+            sonic: None,
+            speech_player: None,
+            voices: None,
+            max_input_length: None,
+            cache: None,
+            disk_cache: None,
+            _marker: PhantomData,
+        };
+
+        self_.apply_init_options()?;
+        Ok(self_)
+    }
+
+    /// Terminate and re-initialise the internal espeak-ng library with new options, such as to
+    /// switch the data path or output mode.
+    ///
+    /// The voice is reset to the default voice of `options`.
+    ///
+    /// # Errors
+    /// If any initialisation steps fail, in which case the [Speaker] is left uninitialised and
+    /// further calls will fail with [`ESpeakNgError::NotInitialized`] until re-initialised.
+    pub fn reinitialise(&mut self, options: InitOptions) -> Result<()> {
+        let result = self.reinitialise_library(options);
+
+        // espeak no longer reads from the overlay of the previous data directory.
+        self.data_overlay = None;
+        self.custom_variant = None;
+        result?;
+
+        self.apply_init_options()?;
+
+        // The data directory may have changed, invalidating the audio on disk.
+        self.check_disk_cache()
+    }
+
+    /// Clear the [`DiskCache`] if the espeak installation or its compiled data have changed.
+    fn check_disk_cache(&mut self) -> Result<()> {
+        match &mut self.disk_cache {
+            Some(disk_cache) => disk_cache.check_identity(),
+            None => Ok(()),
+        }
+    }
+
+    /// Terminate and re-initialise the internal espeak-ng library, without selecting the default
+    /// voice or checking the disk cache, such as to compile into a data directory without voices.
+    pub(crate) fn reinitialise_library(&mut self, options: InitOptions) -> Result<()> {
+        unsafe { bindings::espeak_ng_Terminate() };
+        self.punctuation_list = None;
+        Self::initialise_library(&options)?;
+
+        self.options = options;
+        self.voices = None;
+        self.invalidate_cache();
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(output_mode = ?options.output_mode))
+    )]
+    fn initialise_library(options: &InitOptions) -> Result<()> {
+        unsafe extern "C" fn synth_callback(
+            wav: *mut i16,
+            sample_count: i32,
+            events: *mut bindings::espeak_EVENT,
+        ) -> i32 {
+            let panic_res = std::panic::catch_unwind(|| {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(sample_count, "espeak synth callback");
+
+                if events.is_null() {
+                    return i32::from(CANCEL_SYNTHESIS.load(Ordering::SeqCst));
+                }
+
+                // Every event in the list, including the terminate event, contains the pointer to the callback target
+                let user_data = unsafe { (*events).user_data };
+                if CANCEL_SYNTHESIS.load(Ordering::SeqCst) {
+                    if !user_data.is_null() {
+                        let target = unsafe { &*(user_data as *const CallbackTarget<'_>) };
+                        if matches!(target, CallbackTarget::Utterance(_)) {
+                            // SAFETY: The target was leaked by `Speaker::speak` and espeak stops calling back once aborted.
+                            // Dropping it disconnects the Utterance, which reports the cancellation.
+                            drop(unsafe {
+                                Box::from_raw(user_data.cast::<CallbackTarget<'static>>())
+                            });
+                        }
+                    }
+
+                    return 1;
+                }
+                let untagged_target = CallbackTarget::Buffer(&UNTAGGED_OUTPUT);
+                let target = if user_data.is_null() {
+                    &untagged_target
+                } else {
+                    unsafe { &*(user_data as *const CallbackTarget<'_>) }
+                };
+
+                let wav_slice: &[i16] = if wav.is_null() || sample_count <= 0 {
+                    &[]
+                } else {
+                    unsafe { std::slice::from_raw_parts(wav, sample_count as usize) }
+                };
+
+                unsafe { target.receive(user_data, wav_slice, events) };
+
+                0
+            });
+
+            panic_res.unwrap_or_else(|err| {
+                eprintln!("Panic during Rust -> C -> Rust callback: {err:?}");
+                std::process::abort()
+            })
+        }
+
+        // Emscripten builds of espeakNG have no audio device, so only retrieve audio.
+        if cfg!(target_os = "emscripten")
+            && matches!(
+                options.output_mode,
+                OutputMode::Playback | OutputMode::SynchronousPlayback
+            )
+        {
+            return Err(Error::ESpeakNg {
+                func: "espeak_ng_InitializeOutput",
+                code: ESpeakNgError::NotSupported,
+                context: None,
+            });
+        }
+
+        let (data_path, searched) = Self::find_data_path(options)?;
+        unsafe { bindings::espeak_SetSynthCallback(Some(synth_callback)) };
+
+        // Without a configured directory, NULL lets espeak use the prefix it was compiled with.
+        match Self::initialise_path(data_path.as_deref()) {
+            // The data was found but is unusable, which guessing another directory won't fix.
+            Err(
+                err @ Error::ESpeakNg {
+                    code: ESpeakNgError::VersionMismatch,
+                    ..
+                },
+            ) => return Err(err),
+            Err(_) if data_path.is_none() => Self::initialise_installed(searched)?,
+            result => result?,
+        }
+
+        unsafe {
+            handle_error(
+                "espeak_ng_InitializeOutput",
+                bindings::espeak_ng_InitializeOutput(
+                    options.output_mode as u32,
+                    i32::from(options.buffer_length_ms),
+                    std::ptr::null(),
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the data directory the user configured, see [`data_path::discover_configured`].
+    ///
+    /// Returns the directory found, if any, and every directory searched. Nothing being found is not
+    /// an error yet, as espeak may still find its data at the prefix it was compiled with.
+    fn find_data_path(
+        options: &InitOptions,
+    ) -> Result<(Option<std::ffi::CString>, Vec<std::path::PathBuf>)> {
+        let explicit_path = options.data_path.as_deref();
+        #[cfg(feature = "embed-data")]
+        let explicit_path = match explicit_path {
+            Some(path) => Some(path),
+            None => Some(embedded_data_path()?),
+        };
+
+        let report = data_path::discover_configured(explicit_path);
+        let found = match report.found {
+            Some((path, _)) => Some(utils::path_to_cstring(&path)?),
+            None => None,
+        };
+
+        Ok((found, report.searched))
+    }
+
+    /// Initialise espeak from the data directory at `path`, or the prefix it was compiled with.
+    fn initialise_path(path: Option<&std::ffi::CStr>) -> Result<()> {
+        let mut context = ErrorContext::new();
+        unsafe {
+            bindings::espeak_ng_InitializePath(
+                path.map_or(std::ptr::null(), std::ffi::CStr::as_ptr),
+            );
+            let status = bindings::espeak_ng_Initialize(context.as_mut_ptr());
+            context.handle_error("espeak_ng_Initialize", status)
+        }
+    }
+
+    /// Initialise espeak from a guessed install location, see [`data_path::discover_installed`], after
+    /// the configured directories in `searched` and the compiled prefix failed.
+    fn initialise_installed(mut searched: Vec<std::path::PathBuf>) -> Result<()> {
+        let installed = data_path::discover_installed();
+        searched.extend(installed.searched);
+
+        let Some((path, _)) = installed.found else {
+            return Err(Error::DataNotFound { searched });
+        };
+
+        Self::initialise_path(Some(&utils::path_to_cstring(&path)?))
+    }
+
+    /// Apply the options which are set after the library is initialised.
+    fn apply_init_options(&mut self) -> Result<()> {
+        self.reset_phoneme_events()?;
+        self.set_voice_raw(&self.options.default_voice.clone())
+    }
+
+    /// Fetch the espeak voices currently installed, without caching. See [`Speaker::voices`].
+    #[must_use]
+    pub fn get_voices() -> Vec<Voice> {
+        let mut array = unsafe { bindings::espeak_ListVoices(std::ptr::null_mut()) };
+        let mut buf = Vec::new();
+
+        unsafe {
+            loop {
+                let next = array.read();
+
+                if next.is_null() {
+                    break buf;
+                }
+
+                buf.push(Voice::from(*next));
+                array = array.add(1);
+            }
+        }
+    }
+
+    /// Re-select the current voice's language with a different gender, such as a female voice
+    /// instead of a male one.
+    ///
+    /// espeak picks the closest installed voice, applying a variant if none match exactly.
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::VoiceNotFound`] if no voice matches.
+    pub fn set_voice_gender(&mut self, gender: Gender) -> Result<()> {
+        self.set_voice_by_properties(Some(gender), None)
+    }
+
+    /// Re-select the current voice's language with a voice of a different age.
+    ///
+    /// # Errors
+    /// See [`Speaker::set_voice_gender`]
+    pub fn set_voice_age(&mut self, age: u8) -> Result<()> {
+        self.set_voice_by_properties(None, Some(age))
+    }
+
+    fn set_voice_by_properties(&mut self, gender: Option<Gender>, age: Option<u8>) -> Result<()> {
+        let voice = self.try_current_voice().ok_or(Error::ESpeakNg {
+            func: "espeak_GetCurrentVoice",
+            code: ESpeakNgError::VoiceNotFound,
+            context: None,
+        })?;
+
+        let language = voice
+            .languages
+            .first()
+            .map_or("", |language| language.name.as_str());
+        let language = utils::null_term(language)?;
+
+        let mut selector = bindings::espeak_VOICE {
+            name: std::ptr::null(),
+            languages: language.as_ptr(),
+            identifier: std::ptr::null(),
+            gender: gender.or(voice.gender).map_or(0, |gender| gender as u8),
+            age: age.unwrap_or(voice.age),
+            variant: 0,
+            xx1: 0,
+            score: 0,
+            spare: std::ptr::null_mut(),
+        };
+
+        handle_error("espeak_ng_SetVoiceByProperties", unsafe {
+            bindings::espeak_ng_SetVoiceByProperties(std::ptr::addr_of_mut!(selector))
+        })
+    }
+
+    /// Switch the current voice to use a Klatt synthesizer variant.
+    ///
+    /// # Errors
+    /// - [`ESpeakNgError::NotSupported`] if the current voice is an mbrola voice.
+    /// - [`ESpeakNgError::VoiceNotFound`] if the variant is not installed, see [`Speaker::klatt_variants`].
+    pub fn set_klatt_variant(&mut self, variant: KlattVariant) -> Result<()> {
+        self.apply_variant_raw(variant.name())
+    }
+
+    /// Apply custom voice attributes, such as pitch, formants and roughness, to the current voice.
+    ///
+    /// espeak only loads variants from its data directory, so on first use the [Speaker] switches
+    /// to a private temporary copy of the data directory, linking to the original files, where
+    /// `variant` is written. The copy is kept until [`Speaker::reinitialise`] is called or the
+    /// [Speaker] is dropped, and the original data directory is never written to.
+    ///
+    /// # Errors
+    /// - If the temporary data directory could not be created, or the variant written to it.
+    /// - See [`Speaker::set_klatt_variant`]
+    pub fn set_voice_attributes(&mut self, variant: &VoiceDetails) -> Result<()> {
+        const CUSTOM_VARIANT: &str = "espeakng-rs-custom";
+
+        self.invalidate_cache();
+        self.data_overlay()?
+            .write_variant(CUSTOM_VARIANT, &variant.to_string())?;
+
+        self.custom_variant = Some(variant.to_string());
+        self.apply_variant_raw(CUSTOM_VARIANT)
+    }
+
+    /// The [`DataOverlay`] espeak is using, switching to a new one if needed.
+    fn data_overlay(&mut self) -> Result<&DataOverlay> {
+        let overlay = if let Some(overlay) = self.data_overlay.take() {
+            overlay
+        } else {
+            let overlay = DataOverlay::new(&Self::info().data_path)?;
+            self.switch_data_path(overlay.path())?;
+            overlay
+        };
+
+        Ok(self.data_overlay.insert(overlay))
+    }
+
+    /// Re-initialise espeak from a data directory with the same contents, such as a
+    /// [`DataOverlay`], keeping the current voice, parameters and [`InitOptions`].
+    fn switch_data_path(&mut self, data_path: &std::path::Path) -> Result<()> {
+        let voice = self.try_current_voice();
+        let parameters = self.snapshot_parameters();
+        let punctuation_list = self.punctuation_list.clone();
+        let options = self.options.clone();
+
+        if let Err(err) = self.reinitialise_library(options.clone().data_path(data_path)) {
+            self.reinitialise(options)?;
+            return Err(err);
+        }
+
+        // Keep the original data path, so reinitialising with these options does not need the overlay.
+        self.options = options;
+        self.reset_phoneme_events()?;
+        match voice {
+            Some(voice) => self.set_voice_raw(&voice.filename)?,
+            None => self.set_voice_raw(&self.options.default_voice.clone())?,
+        }
+
+        if let Some(punctuation_list) = punctuation_list {
+            self.set_punctuation_list(&punctuation_list)?;
+        }
+
+        self.apply_parameters(&parameters)
+    }
+
+    /// Apply the variant file `variant_name` from `voices/!v` to the current voice.
+    fn apply_variant_raw(&mut self, variant_name: &str) -> Result<()> {
+        let voice = self.try_current_voice().ok_or(Error::ESpeakNg {
+            func: "espeak_GetCurrentVoice",
+            code: ESpeakNgError::VoiceNotFound,
+            context: None,
+        })?;
+
+        if !voice.supports_klatt() {
+            return Err(Error::ESpeakNg {
+                func: "espeak_ng_SetVoiceByName",
+                code: ESpeakNgError::NotSupported,
+                context: None,
+            });
+        }
+
+        // Replace any variant already applied to the voice.
+        let base_voice = voice.filename.split('+').next().unwrap_or(&voice.filename);
+        self.set_voice_raw(&format!("{base_voice}+{variant_name}"))
+    }
+
+    /// The Klatt variants installed in the data directory.
+    #[must_use]
+    pub fn klatt_variants() -> Vec<KlattVariant> {
+        let variants_dir = Self::info().data_path.join("voices").join("!v");
+        KlattVariant::ALL
+            .into_iter()
+            .filter(|variant| variants_dir.join(variant.name()).is_file())
+            .collect()
+    }
+
+    /// If the linked espeak-ng was built with speechPlayer, used by [`KlattVariant::SPEECH_PLAYER`].
+    /// Without it, the variant silently falls back to espeak's own Klatt synthesizer.
+    ///
+    /// This is found by comparing audio synthesized with the current voice using
+    /// [`KlattVariant::SPEECH_PLAYER`] and [`KlattVariant::Klatt`], then cached. If the variants are
+    /// not installed, this is false. If no audio can be retrieved, such as with
+    /// [`OutputMode::Playback`], the `speech-player` feature is trusted instead.
+    pub fn speech_player_available(&mut self) -> bool {
+        if let Some(speech_player) = self.speech_player {
+            return speech_player;
+        }
+
+        let variants = Self::klatt_variants();
+        let speech_player = variants.contains(&KlattVariant::SPEECH_PLAYER)
+            && variants.contains(&KlattVariant::Klatt)
+            && self
+                .probe_speech_player()
+                .unwrap_or(None)
+                .unwrap_or(cfg!(feature = "speech-player"));
+
+        self.speech_player = Some(speech_player);
+        speech_player
+    }
+
+    /// Compare synthesis with the speechPlayer and Klatt variants, returning [`None`] if no audio
+    /// was retrieved.
+    fn probe_speech_player(&mut self) -> Result<Option<bool>> {
+        const PROBE_TEXT: &str = "Hello world";
+
+        let voice = self.try_current_voice();
+        let last_stats = self.last_stats;
+        let synthesize_with = |speaker: &mut Self, variant: KlattVariant| {
+            speaker.set_klatt_variant(variant)?;
+            speaker.synthesize_with_options(PROBE_TEXT, &SynthesisOptions::default())
+        };
+
+        let speech_player = synthesize_with(self, KlattVariant::SPEECH_PLAYER);
+        let klatt = synthesize_with(self, KlattVariant::Klatt);
+        self.last_stats = last_stats;
+        match voice {
+            Some(voice) => self.set_voice_raw(&voice.filename)?,
+            None => self.set_voice_raw(&self.options.default_voice.clone())?,
+        }
+
+        let (speech_player, klatt) = (speech_player?, klatt?);
+        if klatt.is_empty() {
+            return Ok(None);
+        }
+
+        // Without speechPlayer, both are synthesized by espeak's Klatt synthesizer.
+        Ok(Some(speech_player != klatt))
+    }
+
+    /// Switch the current voice to use the speechPlayer synthesizer.
+    ///
+    /// # Errors
+    /// - [`ESpeakNgError::NotSupported`] if [`Speaker::speech_player_available`] is false.
+    /// - See [`Speaker::set_klatt_variant`]
+    pub fn set_speech_player(&mut self) -> Result<()> {
+        if !self.speech_player_available() {
+            return Err(Error::ESpeakNg {
+                func: "espeak_ng_SetVoiceByName",
+                code: ESpeakNgError::NotSupported,
+                context: None,
+            });
+        }
+
+        self.set_klatt_variant(KlattVariant::SPEECH_PLAYER)
+    }
+
+    /// Set the punctuation characters to speak when [`Parameter::Punctuation`] is set to
+    /// [`PunctationType::Some`].
+    ///
+    /// # Errors
+    /// If the internal C call fails.
+    pub fn set_punctuation_list(&mut self, punctuation: &[char]) -> Result<()> {
+        self.invalidate_cache();
+        let punctuation_nul_term = utils::null_term_wide(punctuation)?;
+        handle_error("espeak_ng_SetPunctuationList", unsafe {
+            bindings::espeak_ng_SetPunctuationList(punctuation_nul_term.as_ptr())
+        })?;
+
+        self.punctuation_list = Some(punctuation.to_vec());
+        Ok(())
+    }
+
+    /// Block until all queued speech has finished, for use with the asynchronous output modes.
+    ///
+    /// # Errors
+    /// If the internal C call fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn synchronize(&mut self) -> Result<()> {
+        handle_error("espeak_ng_Synchronize", unsafe {
+            bindings::espeak_ng_Synchronize()
+        })
+    }
+
+    /// Whether audio is still being produced, for use with the asynchronous output modes.
+    #[must_use]
+    pub fn is_speaking(&self) -> bool {
+        unsafe { bindings::espeak_IsPlaying() != 0 }
+    }
+
+    /// Report which optional features of espeak-ng can be used, to hide unsupported features instead
+    /// of failing when they are used.
+    ///
+    /// Most are probed at runtime, such as by synthesizing a short text, so avoid calling this per
+    /// synthesis call. See the fields of [Capabilities] for how each is found.
+    pub fn capabilities(&mut self) -> Capabilities {
+        let data_path = Self::info().data_path;
+        let mbrola = self.voices().iter().any(|voice| {
+            VoiceDetails::for_voice(voice).is_ok_and(|details| {
+                details
+                    .other
+                    .iter()
+                    .filter(|(keyword, _)| keyword == "mbrola")
+                    .filter_map(|(_, args)| args.first())
+                    .any(|database| mbrola_database_installed(&data_path, database))
+            })
+        });
+
+        Capabilities {
+            mbrola,
+            klatt: !Self::klatt_variants().is_empty(),
+            sonic: self.sonic_available(),
+            speech_player: self.speech_player_available(),
+            async_audio: self.probe_playback(),
+            ssml: self.probe_ssml(),
+        }
+    }
+
+    /// If espeak-ng can open the audio device, by initialising playback output then restoring the
+    /// output mode requested in [`InitOptions`].
+    fn probe_playback(&mut self) -> bool {
+        if cfg!(target_os = "emscripten") {
+            return false;
+        }
+
+        // Initialisation would have failed if playback was requested and unavailable.
+        let output_mode = self.options.output_mode;
+        if matches!(
+            output_mode,
+            OutputMode::Playback | OutputMode::SynchronousPlayback
+        ) {
+            return true;
+        }
+
+        let buffer_length = i32::from(self.options.buffer_length_ms);
+        let status = unsafe {
+            bindings::espeak_ng_InitializeOutput(
+                OutputMode::SynchronousPlayback as u32,
+                buffer_length,
+                std::ptr::null(),
+            )
+        };
+
+        // Restoring the mode espeak was initialised with cannot fail for lack of an audio device.
+        unsafe {
+            bindings::espeak_ng_InitializeOutput(
+                output_mode as u32,
+                buffer_length,
+                std::ptr::null(),
+            )
+        };
+
+        status == 0
+    }
+
+    /// If espeak-ng interprets SSML, by synthesizing a `<mark>` and checking for its event.
+    fn probe_ssml(&mut self) -> bool {
+        let options = SynthesisOptions {
+            flags: SynthFlags::Ssml,
+            ..SynthesisOptions::default()
+        };
+
+        // Passed straight to the backend, as preprocessing could change the markup.
+        let mut event_output = EventOutput::default();
+        let result = self.backend.synthesize(
+            br#"<speak><mark name="probe"/>.</speak>"#,
+            &options,
+            &mut event_output,
+        );
+
+        result.is_ok()
+            && event_output.0.iter().any(|event| {
+                event.event_type == EventType::Mark && event.id == EventId::Name("probe".into())
+            })
+    }
+
+    /// Get the version and data path of the internal C library.
+    #[must_use]
+    pub fn info() -> Info {
+        let mut c_voice_path: *const libc::c_char = std::ptr::null();
+
+        let (version_string, data_path) = unsafe {
+            let version_string = bindings::espeak_Info(std::ptr::addr_of_mut!(c_voice_path));
+            (
+                String::from_cptr(version_string),
+                String::from_cptr(c_voice_path),
+            )
+        };
+
+        Info::new(version_string, std::path::PathBuf::from(data_path))
+    }
+
+    /// Compiles the dictionary source files in `dict_source_dir` for the language `dict_name`,
+    /// writing the compiled `{dict_name}_dict` into the espeak-ng data directory.
+    ///
+    /// Compilation messages are written to stderr by espeakNG.
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::CompileError`] if the dictionary source contains errors, or another
+    /// [`ESpeakNgError`] if the source files cannot be read.
+    pub fn compile_dictionary(
+        &mut self,
+        dict_source_dir: impl AsRef<Path>,
+        dict_name: &str,
+        flags: CompileFlags,
+    ) -> Result<()> {
+        self.invalidate_cache();
+
+        // espeakNG concatenates the file names onto the source directory, so needs a trailing slash.
+        let mut dict_source_dir = dict_source_dir.as_ref().as_os_str().to_owned();
+        if !dict_source_dir.as_encoded_bytes().ends_with(b"/") {
+            dict_source_dir.push("/");
+        }
+
+        let dict_source_dir = utils::path_to_cstring(Path::new(&dict_source_dir))?;
+        let dict_name = utils::null_term(dict_name)?;
+
+        let mut context = ErrorContext::new();
+        let status = unsafe {
+            bindings::espeak_ng_CompileDictionary(
+                dict_source_dir.as_ptr(),
+                dict_name.as_ptr(),
+                std::ptr::null_mut(),
+                flags.bits() as i32,
+                context.as_mut_ptr(),
+            )
+        };
+        context.handle_error("espeak_ng_CompileDictionary", status)?;
+
+        // The rebuilt dictionary changes the identity of the data, invalidating the audio on disk.
+        self.check_disk_cache()
+    }
+
+    /// Compiles the intonation definitions in the `intonations` file at `source` into the
+    /// `intonations` data file at `output`.
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::CompileError`] if the source contains errors, or another [`ESpeakNgError`]
+    /// if the files cannot be read or written.
+    pub fn compile_intonations(
+        &mut self,
+        source: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<()> {
+        let source = utils::path_to_cstring(source.as_ref())?;
+        let output = utils::path_to_cstring(output.as_ref())?;
+
+        let mut context = ErrorContext::new();
+        let status = unsafe {
+            bindings::espeak_ng_CompileIntonationPath(
+                source.as_ptr(),
+                output.as_ptr(),
+                std::ptr::null_mut(),
+                context.as_mut_ptr(),
+            )
+        };
+        context.handle_error("espeak_ng_CompileIntonationPath", status)
+    }
+
+    /// Compiles the phoneme tables in the `phsource` directory at `source_path` into the phoneme
+    /// data files in `output_path`, at the current output sample rate.
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::CompileError`] if the source contains errors, or another [`ESpeakNgError`]
+    /// if the files cannot be read or written.
+    pub fn compile_phoneme_data(
+        &mut self,
+        source_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let source_path = utils::path_to_cstring(source_path.as_ref())?;
+        let output_path = utils::path_to_cstring(output_path.as_ref())?;
+
+        let mut context = ErrorContext::new();
+        let status = unsafe {
+            bindings::espeak_ng_CompilePhonemeDataPath(
+                libc::c_long::from(self.sample_rate()),
+                source_path.as_ptr(),
+                output_path.as_ptr(),
+                std::ptr::null_mut(),
+                context.as_mut_ptr(),
+            )
+        };
+        context.handle_error("espeak_ng_CompilePhonemeDataPath", status)
+    }
+
+    /// Compiles the dictionary of `lexicon` with its entries added, and reloads the current voice so
+    /// the overrides take effect immediately.
+    ///
+    /// The dictionary source files are copied into a private copy of the data directory, which
+    /// espeak is switched to, where the entries are appended to any existing `_extra` file and the
+    /// dictionary is compiled. Each call starts again from the original source files.
+    ///
+    /// # Errors
+    /// If the source files cannot be copied, or see [`Speaker::compile_dictionary`].
+    pub fn apply_lexicon(&mut self, lexicon: &Lexicon) -> Result<()> {
+        let source_dir = self.data_overlay()?.write_dictionary_source(lexicon)?;
+        self.compile_dictionary(&source_dir, lexicon.dict_name(), CompileFlags::empty())?;
+
+        // The dictionary is loaded alongside the voice, so reload it to pick up the changes.
+        match self.try_current_voice() {
+            Some(current_voice) => self.set_voice(&current_voice),
+            None => Ok(()),
+        }
+    }
+
+    /// Processes each run of text with its voice into phonemes, joined by spaces.
+    ///
+    /// # Errors
+    /// See [`Speaker::with_voice`] and [`Speaker::text_to_clause_phonemes_batch`].
+    pub fn text_to_phonemes_runs(
+        &mut self,
+        runs: &[VoiceRun<'_>],
+        option: &PhonemeGenOptions<'_>,
+    ) -> Result<String> {
+        let mut phonemes = Vec::with_capacity(runs.len());
+        for run in runs {
+            let mut run_phonemes = match &run.voice {
+                Some(voice) => self.with_voice(voice, |speaker| {
+                    speaker.text_to_clause_phonemes_batch(&[run.text], option)
+                })?,
+                None => self.text_to_clause_phonemes_batch(&[run.text], option)?,
+            };
+
+            phonemes.append(&mut run_phonemes);
+        }
+
+        Ok(phonemes.join(" "))
+    }
+
+    /// Start processing the given text without waiting for it to complete, for use with the
+    /// asynchronous [`OutputMode::Retrieval`] and [`OutputMode::Playback`] modes.
+    ///
+    /// Completion is signalled through the returned [Utterance]. In synchronous output modes, this
+    /// blocks until synthesis has completed, as [`Speaker::synthesize`] does.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails to start, see [`ESpeakNgError`]
+    pub fn speak(&mut self, text: &str) -> Result<Utterance> {
+        let (async_utterance, utterance) = utterance::AsyncUtterance::new();
+
+        if matches!(
+            self.options.output_mode,
+            OutputMode::Synchronous | OutputMode::SynchronousPlayback
+        ) {
+            let mut synth_output = SynthOutput::default();
+            self._synthesize(
+                text.as_bytes(),
+                &SynthesisOptions::default(),
+                &mut synth_output,
+            )?;
+
+            async_utterance.audio.lock().append(&mut synth_output.audio);
+            async_utterance.finish();
+        } else {
+            let options = SynthesisOptions::default();
+            let text = self.preprocess(text.as_bytes(), options.text_mode)?;
+
+            // Leaked until the callback receives the final event of the utterance.
+            let target = Box::into_raw(Box::new(CallbackTarget::Utterance(async_utterance)));
+
+            let result = self.backend.start_synthesis(&text, &options, target);
+            if result.is_err() {
+                drop(unsafe { Box::from_raw(target) });
+            }
+
+            result?;
+        }
+
+        Ok(utterance)
+    }
+
+    /// Processes the name of a single character into WAV audio data.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn speak_char(&mut self, character: char) -> Result<Vec<i16>> {
+        self.synthesize_untagged("espeak_ng_SpeakCharacter", || unsafe {
+            bindings::espeak_ng_SpeakCharacter(character as libc::wchar_t)
+        })
+    }
+
+    /// Processes the given text into WAV audio data read letter by letter, such as for codes and
+    /// confirmation strings, with `gap` of silence between each character.
+    ///
+    /// Whitespace is not spoken, only adding another gap.
+    ///
+    /// # Errors
+    /// See [`Speaker::speak_char`]
+    pub fn spell(&mut self, text: &str, gap: Duration) -> Result<Vec<i16>> {
+        let gap_samples =
+            u64::from(self.sample_rate()) * u64::try_from(gap.as_millis()).unwrap_or(0) / 1000;
+        let gap_samples = usize::try_from(gap_samples).unwrap_or(0);
+
+        let mut audio = Vec::new();
+        for (i, character) in text.chars().enumerate() {
+            if i != 0 {
+                audio.resize(audio.len() + gap_samples, 0);
+            }
+
+            if !character.is_whitespace() {
+                audio.append(&mut self.speak_char(character)?);
+            }
+        }
+
+        Ok(audio)
+    }
+
+    /// Processes the name of a key into WAV audio data. If `key_name` is a single character, it is
+    /// spoken as [`Speaker::speak_char`] would, otherwise it is spoken as text.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn speak_key_name(&mut self, key_name: &str) -> Result<Vec<i16>> {
+        let key_name = utils::null_term(key_name)?;
+        self.synthesize_untagged("espeak_ng_SpeakKeyName", || unsafe {
+            bindings::espeak_ng_SpeakKeyName(key_name.as_ptr())
+        })
+    }
+
+    fn synthesize_untagged(
+        &mut self,
+        func: &'static str,
+        synth_func: impl FnOnce() -> u32,
+    ) -> Result<Vec<i16>> {
+        UNTAGGED_OUTPUT.lock().audio.clear();
+
+        handle_error(func, synth_func())?;
+        self.synchronize()?;
+
+        Ok(std::mem::take(&mut UNTAGGED_OUTPUT.lock().audio))
+    }
+
+    /// Direct the phoneme trace espeak writes during synthesis into `sink`, until the returned guard
+    /// is finished or dropped.
+    ///
+    /// ```no_run
+    /// # fn main() -> espeakng::Result<()> {
+    /// let mut speaker = espeakng::initialise_and_lock(espeakng::InitOptions::default())?;
+    ///
+    /// let mut tracer = speaker.set_phoneme_trace(espeakng::PhonemeTraceMode::Ipa, Vec::new())?;
+    /// tracer.synthesize("Hello world")?;
+    /// let trace = tracer.finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// - [`Error::MbrolaWithoutMbrolaVoice`] if [`PhonemeTraceMode::Mbrola`] is passed without an mbrola voice set.
+    /// - If the temporary file used to capture the trace could not be created.
+    pub fn set_phoneme_trace<W: Write>(
+        &mut self,
+        mode: PhonemeTraceMode,
+        sink: W,
+    ) -> Result<PhonemeTraceGuard<'_, W>> {
+        PhonemeTraceGuard::new(self, mode, sink)
+    }
+}
+
+impl<B: EspeakBackend> Speaker<B> {
+    /// Set the phoneme events back to the state requested in [`InitOptions`].
+    fn reset_phoneme_events(&mut self) -> Result<()> {
+        self.backend
+            .set_phoneme_events(self.options.phoneme_events, self.options.phoneme_events_ipa)
+    }
+
+    /// Set the voice for future espeak calls.
+    ///
+    /// # Errors
+    /// See [`Speaker::set_voice_raw`]
+    pub fn set_voice(&mut self, voice: &Voice) -> Result<()> {
+        self.set_voice_raw(&voice.filename)
+    }
+
+    /// Temporarily set the voice until the returned guard is dropped, when the previous voice will
+    /// be restored.
+    ///
+    /// # Errors
+    /// See [`Speaker::set_voice`]
+    pub fn scoped_voice(&mut self, voice: &Voice) -> Result<VoiceGuard<'_, B>> {
+        VoiceGuard::new(self, voice)
+    }
+
+    /// Run `func` with a temporarily set voice, restoring the previous voice afterwards even if
+    /// `func` fails or panics.
+    ///
+    /// # Errors
+    /// See [`Speaker::scoped_voice`] + any errors returned from `func`.
+    pub fn with_voice<T>(
+        &mut self,
+        voice: &Voice,
+        func: impl FnOnce(&mut Speaker<B>) -> Result<T>,
+    ) -> Result<T> {
+        let mut guard = self.scoped_voice(voice)?;
+        func(&mut guard)
+    }
+
+    /// Set the voice for future espeak calls based on the filename
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::VoiceNotFound`]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), err))]
+    pub fn set_voice_raw(&mut self, filename: &str) -> Result<()> {
+        self.backend.set_voice_by_name(filename)
+    }
+
+    /// Temporarily set parameters until the returned guard is dropped, when the previous values
+    /// will be restored.
+    ///
+    /// # Errors
+    /// If any of the internal C calls fail, any parameters already set are restored.
+    pub fn scoped_parameters(
+        &mut self,
+        params: &[(Parameter, i32)],
+    ) -> Result<ParameterGuard<'_, B>> {
+        ParameterGuard::new(self, params)
+    }
+
+    /// Run `func` with temporarily set parameters, restoring the previous values afterwards even if
+    /// `func` fails or panics.
+    ///
+    /// # Errors
+    /// See [`Speaker::scoped_parameters`] + any errors returned from `func`.
+    pub fn with_parameters<T>(
+        &mut self,
+        params: &[(Parameter, i32)],
+        func: impl FnOnce(&mut Speaker<B>) -> Result<T>,
+    ) -> Result<T> {
+        let mut guard = self.scoped_parameters(params)?;
+        func(&mut guard)
+    }
+
+    /// Set a function to run on each sentence of text before it is passed to espeak, such as to
+    /// strip markup or respell names.
+    ///
+    /// This is used for synthesis and [`PhonemeGenOptions::Standard`] phoneme generation of UTF-8 text.
+    pub fn set_text_filter(&mut self, filter: impl FnMut(&str) -> Cow<'_, str> + Send + 'static) {
+        self.invalidate_cache();
+        self.text_filter = Some(Box::new(filter));
+    }
+
+    /// Remove the function set by [`Speaker::set_text_filter`].
+    pub fn clear_text_filter(&mut self) {
+        self.invalidate_cache();
+        self.text_filter = None;
+    }
+
+    /// Clean up text before it is passed to espeak, such as composing accents typed as combining
+    /// characters, or removing zero width spaces pasted in from web pages.
+    ///
+    /// This is applied before any filter set with [`Speaker::set_text_filter`], and is used in the same places.
+    pub fn set_input_normalization(&mut self, normalization: Normalization) {
+        self.invalidate_cache();
+        self.normalization = normalization;
+    }
+
+    /// Read emoji in the text by their names, such as `thumbs up` for 👍, instead of espeak skipping
+    /// them. Pass [`None`] to stop.
+    ///
+    /// This is applied after [`Speaker::set_input_normalization`] and before any text filter.
+    #[cfg(feature = "emoji")]
+    pub fn set_emoji_names(&mut self, names: Option<EmojiNames>) {
+        self.invalidate_cache();
+        self.emoji_names = names;
+    }
+
+    /// Whether [`Speaker::filter_text`] may change text, so it needs to be decoded first.
+    fn has_text_filter(&self) -> bool {
+        #[cfg(feature = "emoji")]
+        if self.emoji_names.is_some() {
+            return true;
+        }
+
+        self.text_filter.is_some()
+            || self.abbreviations.is_some()
+            || self.locale_format.is_some()
+            || self.word_replacements.is_some()
+            || !self.normalization.is_empty()
+    }
+
+    /// Expand abbreviations in the text before it is passed to espeak, such as `Dr.` to `Doctor`,
+    /// which espeak may otherwise spell out or read as the end of a sentence. Pass [`None`] to stop.
+    ///
+    /// Start from [`Abbreviations::for_language`] for the language of the voice, adding or removing
+    /// entries as needed. This is applied after emoji names, and before any text filter.
+    pub fn set_abbreviations(&mut self, abbreviations: Option<Abbreviations>) {
+        self.invalidate_cache();
+        self.abbreviations = abbreviations;
+    }
+
+    /// Rewrite numbers, amounts of money and numeric dates written in the conventions of a locale
+    /// into words, such as `1.234,56 €` in German, which espeak may otherwise misread. Pass [`None`] to stop.
+    ///
+    /// Use [`LocaleFormat::for_voice`] to match the current voice. This is applied after abbreviations,
+    /// and before any text filter.
+    pub fn set_locale_format(&mut self, format: Option<LocaleFormat>) {
+        self.invalidate_cache();
+        self.locale_format = format;
+    }
+
+    /// Replace or mask words before the text is passed to espeak, such as to fix the pronunciation
+    /// of a name or to bleep profanity. Pass [`None`] to stop.
+    ///
+    /// This is applied after locale formatting, and before any text filter.
+    pub fn set_word_replacements(&mut self, word_replacements: Option<WordReplacements>) {
+        self.invalidate_cache();
+        self.word_replacements = word_replacements;
+    }
+
+    /// Replace each word with its replacement, such as `[("GIF", "jif")]`, adding to any previous
+    /// replacements, see [`Speaker::set_word_replacements`].
+    pub fn replace_words<W: Into<String>, R: Into<String>>(
+        &mut self,
+        replacements: impl IntoIterator<Item = (W, R)>,
+    ) {
+        self.invalidate_cache();
+        self.word_replacements
+            .get_or_insert_with(WordReplacements::new)
+            .extend(replacements);
+    }
+
+    fn filter_text<'t>(&mut self, text: &'t str) -> Cow<'t, str> {
+        let mut text = normalize::normalize(text, self.normalization);
+
+        #[cfg(feature = "emoji")]
+        if let Some(names) = &self.emoji_names {
+            text = utils::then_filter(text, |text| names.verbalize(text));
+        }
+
+        if let Some(abbreviations) = &self.abbreviations {
+            text = utils::then_filter(text, |text| abbreviations.expand(text));
+        }
+
+        if let Some(format) = &self.locale_format {
+            text = utils::then_filter(text, |text| format.apply(text));
+        }
+
+        if let Some(word_replacements) = &self.word_replacements {
+            text = utils::then_filter(text, |text| word_replacements.apply(text));
+        }
+
+        let Some(text_filter) = &mut self.text_filter else {
+            return text;
+        };
+
+        let mut filtered_text = String::with_capacity(text.len());
+        let mut sentence_start = 0;
+        for sentence_end in document::sentence_ends(&text).chain(std::iter::once(text.len())) {
+            if sentence_end > sentence_start {
+                filtered_text.push_str(&text_filter(&text[sentence_start..sentence_end]));
+                sentence_start = sentence_end;
+            }
+        }
+
+        Cow::Owned(filtered_text)
+    }
+
+    /// Check the length of `text`, then run it through the filters if it is UTF-8, ready to be
+    /// passed to the backend.
+    fn preprocess<'t>(&mut self, text: &'t [u8], text_mode: TextMode) -> Result<Cow<'t, [u8]>> {
+        self.check_input_length(text.len())?;
+
+        match (text_mode, std::str::from_utf8(text)) {
+            (TextMode::Utf8 | TextMode::Auto, Ok(text)) if self.has_text_filter() => {
+                Ok(match self.filter_text(text) {
+                    Cow::Borrowed(text) => Cow::Borrowed(text.as_bytes()),
+                    Cow::Owned(text) => Cow::Owned(text.into_bytes()),
+                })
+            }
+            _ => Ok(Cow::Borrowed(text)),
+        }
+    }
+
+    /// Estimate the number of samples synthesizing `text` will generate, from the word count,
+    /// speaking rate and sample rate, unless [`SynthesisOptions::capacity_hint`] is set.
+    fn estimate_samples(&mut self, text: &[u8], options: &SynthesisOptions) -> usize {
+        if let Some(capacity_hint) = options.capacity_hint {
+            return capacity_hint;
+        }
+
+        let words = text
+            .split(u8::is_ascii_whitespace)
+            .filter(|word| !word.is_empty())
+            .count();
+
+        let words_per_minute = match options.rate {
+            Some(rate) => i32::from(rate.get()),
+            None => self.get_parameter(Parameter::Rate, false),
+        };
+
+        let words_per_minute = words_per_minute.max(1) as u64;
+        let samples = words as u64 * 60 * u64::from(self.sample_rate()) / words_per_minute;
+
+        // Estimates too large to allocate are not worth preallocating for.
+        usize::try_from(samples).unwrap_or(0)
+    }
+
+    fn output_buffer(&mut self, text: &[u8], options: &SynthesisOptions) -> SynthOutput {
+        SynthOutput {
+            audio: Vec::with_capacity(self.estimate_samples(text, options)),
+            events: None,
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(text_len = text.len()))
+    )]
+    fn _synthesize(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<()> {
+        if options.has_overrides() {
+            return self.synthesize_with_overrides(text, options, handler);
+        }
+
+        let text = self.preprocess(text, options.text_mode)?;
+        self.with_stats(handler, |backend, handler| {
+            backend.synthesize(&text, options, handler)
+        })
+    }
+
+    /// Run synthesis with the backend, recording the [`SynthesisStats`] of the call.
+    fn with_stats<T>(
+        &mut self,
+        handler: &mut dyn SynthCallbackHandler,
+        synthesize: impl FnOnce(&mut B, &mut dyn SynthCallbackHandler) -> Result<T>,
+    ) -> Result<T> {
+        let start = Instant::now();
+        let mut counter = SampleCounter {
+            handler,
+            samples: 0,
+        };
+
+        let result = synthesize(&mut self.backend, &mut counter);
+
+        self.last_stats = Some(SynthesisStats::new(
+            start.elapsed(),
+            counter.samples,
+            self.sample_rate(),
+        ));
+
+        result
+    }
+
+    /// Apply the voice and parameter overrides of `options` for a single synthesis call.
+    fn synthesize_with_overrides(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<()> {
+        if let Some(rate) = options.rate {
+            self.check_rate(rate)?;
+        }
+
+        // The voice is set first, as changing voice may reset the parameters.
+        let mut voice_guard;
+        let speaker = match &options.voice {
+            Some(voice) => {
+                voice_guard = self.scoped_voice(voice)?;
+                &mut *voice_guard
+            }
+            None => self,
+        };
+
+        let mut speaker = speaker.scoped_parameters(&options.parameter_overrides())?;
+        let options = SynthesisOptions {
+            rate: None,
+            pitch: None,
+            volume: None,
+            voice: None,
+            ..options.clone()
+        };
+
+        speaker._synthesize(text, &options, handler)
+    }
+
+    /// Processes the given text into WAV audio data.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize(&mut self, text: &str) -> Result<Vec<i16>> {
+        self.with_cache(cache::CacheOutput::Audio, text, |speaker| {
+            speaker
+                .synthesize_buffered(text.as_bytes(), &SynthesisOptions::default())
+                .into_result()
+        })
+    }
+
+    /// Processes the given text into WAV audio data, then runs it through `effects`.
+    ///
+    /// # Errors
+    /// See [`Speaker::synthesize`]
+    pub fn synthesize_with_effects(
+        &mut self,
+        text: &str,
+        effects: &mut dyn effects::Processor,
+    ) -> Result<Vec<i16>> {
+        Ok(effects.process(&self.synthesize(text)?))
+    }
+
+    /// Processes the given text into WAV audio data, keeping the audio generated before any
+    /// error or cancellation.
+    pub fn synthesize_partial(
+        &mut self,
+        text: &str,
+        options: &SynthesisOptions,
+    ) -> SynthesisOutcome {
+        self.synthesize_buffered(text.as_bytes(), options)
+    }
+
+    fn synthesize_buffered(&mut self, text: &[u8], options: &SynthesisOptions) -> SynthesisOutcome {
+        let mut synth_output = self.output_buffer(text, options);
+        let result = self._synthesize(text, options, &mut synth_output);
+
+        SynthesisOutcome {
+            audio: synth_output.audio,
+            error: result.err(),
+        }
+    }
+
+    /// Find the installed voice best suited to the language of `text`.
+    ///
+    /// Returns [`None`] if the language could not be reliably detected, or no voice supports it.
+    #[cfg(feature = "detect-language")]
+    pub fn detect_voice(&mut self, text: &str) -> Option<Voice> {
+        detect::detect_voice(self.voices(), text).cloned()
+    }
+
+    /// Processes the given text into WAV audio data, using the voice found by [`Speaker::detect_voice`].
+    ///
+    /// Falls back to the current voice if the language could not be detected, and the previous
+    /// voice is restored after synthesis.
+    ///
+    /// # Errors
+    /// If setting the voice fails, see [`Speaker::set_voice`], or see [`Speaker::synthesize`].
+    #[cfg(feature = "detect-language")]
+    pub fn synthesize_auto(&mut self, text: &str) -> Result<Vec<i16>> {
+        let Some(voice) = self.detect_voice(text) else {
+            return self.synthesize(text);
+        };
+
+        self.synthesize_with_voice(&voice, text)
+    }
+
+    /// Split the given text into runs of sentences in the same language, each with the voice found
+    /// by [`Speaker::detect_voice`].
+    ///
+    /// Sentences where the language could not be detected join the previous run.
+    #[cfg(feature = "detect-language")]
+    pub fn split_language_runs<'t>(&mut self, text: &'t str) -> Vec<VoiceRun<'t>> {
+        mixed::split_runs(self.voices(), text)
+    }
+
+    /// Processes text mixing multiple languages into WAV audio data, switching voices for each
+    /// run found by [`Speaker::split_language_runs`].
+    ///
+    /// # Errors
+    /// See [`Speaker::synthesize_runs`]
+    #[cfg(feature = "detect-language")]
+    pub fn synthesize_mixed(&mut self, text: &str) -> Result<Vec<i16>> {
+        let runs = self.split_language_runs(text);
+        self.synthesize_runs(&runs)
+    }
+
+    /// Processes each run of text with its voice into WAV audio data, joined together.
+    ///
+    /// The previous voice is restored afterwards.
+    ///
+    /// # Errors
+    /// See [`Speaker::with_voice`] and [`Speaker::synthesize`].
+    pub fn synthesize_runs(&mut self, runs: &[VoiceRun<'_>]) -> Result<Vec<i16>> {
+        let mut audio = Vec::new();
+        for run in runs {
+            match &run.voice {
+                Some(voice) => audio.append(&mut self.synthesize_with_voice(voice, run.text)?),
+                None => audio.append(&mut self.synthesize(run.text)?),
+            }
+        }
+
+        Ok(audio)
+    }
+
+    /// Processes the given text into WAV audio data with `voice`, restoring the previous voice afterwards.
+    ///
+    /// # Errors
+    /// See [`Speaker::with_voice`] and [`Speaker::synthesize`].
+    pub fn synthesize_with_voice(&mut self, voice: &Voice, text: &str) -> Result<Vec<i16>> {
+        self.with_voice(voice, |speaker| speaker.synthesize(text))
+    }
+
+    /// Processes the given text into WAV audio data, appended to `output`.
+    ///
+    /// This allows the allocation of `output` to be reused between calls.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]. Audio generated before the
+    /// failure is still appended.
+    pub fn synthesize_into(&mut self, text: &str, output: &mut Vec<i16>) -> Result<()> {
+        output.reserve(self.estimate_samples(text.as_bytes(), &SynthesisOptions::default()));
+        let mut synth_output = SynthOutput {
+            audio: std::mem::take(output),
+            events: None,
+        };
+
+        let result = self._synthesize(
+            text.as_bytes(),
+            &SynthesisOptions::default(),
+            &mut synth_output,
+        );
+
+        *output = synth_output.audio;
+        result
+    }
+
+    /// Processes the given text into WAV audio data, passing each chunk to `sink` as it is
+    /// generated without copying or buffering the audio.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_to_sink(
+        &mut self,
+        text: &str,
+        options: &SynthesisOptions,
+        mut sink: impl FnMut(&[i16]) + Send,
+    ) -> Result<()> {
+        self.synthesize_with_handler(text, options, &mut sink)
+    }
+
+    /// Processes the given text, passing the audio and events to `handler` as they are generated.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_with_handler(
+        &mut self,
+        text: &str,
+        options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<()> {
+        self._synthesize(text.as_bytes(), options, handler)
+    }
+
+    /// Processes the given text into WAV audio data, using the positions and flags given in `options`.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_with_options(
+        &mut self,
+        text: &str,
+        options: &SynthesisOptions,
+    ) -> Result<Vec<i16>> {
+        self.synthesize_buffered(text.as_bytes(), options)
+            .into_result()
+    }
+
+    /// Processes the given segments of text and pauses into WAV audio data, via SSML.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_segments(&mut self, segments: &[Segment<'_>]) -> Result<Vec<i16>> {
+        self.synthesize_with_options(
+            &Segment::to_ssml(segments),
+            &SynthesisOptions {
+                flags: SynthFlags::Ssml,
+                ..SynthesisOptions::default()
+            },
+        )
+    }
+
+    /// Processes the given SSML into WAV audio data, along with the voice changes caused by its
+    /// `<voice>` elements and `xml:lang` attributes.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_ssml_with_voice_changes(
+        &mut self,
+        ssml: &str,
+    ) -> Result<(Vec<i16>, Vec<VoiceChange>)> {
+        // espeak does not report voice changes, so marks are inserted to find their positions.
+        let (marked_ssml, selections) = ssml::mark_voice_changes(ssml);
+        let (audio, events) = self.synthesize_with_events(
+            marked_ssml.as_bytes(),
+            &SynthesisOptions {
+                flags: SynthFlags::Ssml,
+                ..SynthesisOptions::default()
+            },
+        )?;
+
+        let sample_rate = self.sample_rate();
+        let voice_changes = events
+            .iter()
+            .filter_map(|event| {
+                let EventId::Name(mark_name) = &event.id else {
+                    return None;
+                };
+
+                let (text_position, selection) = selections
+                    .get(ssml::voice_change_index(mark_name)?)?
+                    .clone();
+
+                Some(VoiceChange {
+                    selection,
+                    text_position,
+                    sample: utils::ms_to_samples(event.audio_position, sample_rate),
+                })
+            })
+            .collect();
+
+        Ok((audio, voice_changes))
+    }
+
+    /// Processes the given text into WAV audio data, along with the position of each word in both
+    /// the text and the audio, such as for highlighting the word being spoken.
+    ///
+    /// The positions are within the returned text, which differs from `text` if any preprocessing is
+    /// set, such as [`Speaker::set_text_filter`] or [`Speaker::set_input_normalization`].
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_with_word_timings(
+        &mut self,
+        text: &str,
+    ) -> Result<(Vec<i16>, Vec<WordTiming>, String)> {
+        let mut synth_output = SynthOutput {
+            audio: Vec::with_capacity(
+                self.estimate_samples(text.as_bytes(), &SynthesisOptions::default()),
+            ),
+            events: Some(Vec::new()),
+        };
+
+        let text = self.synthesize_preprocessed(text, &mut synth_output)?;
+        let events = synth_output.events.unwrap_or_default();
+
+        let word_timings = timing::word_timings(&text, &events, self.sample_rate());
+        Ok((synth_output.audio, word_timings, text))
+    }
+
+    /// Processes the given text into WAV audio data, along with the text and audio ranges of each
+    /// word and sentence, ready for highlighting the text as it is played.
+    ///
+    /// The ranges are within [`Highlights::text`], which differs from `text` if any preprocessing is
+    /// set, such as [`Speaker::set_text_filter`] or [`Speaker::set_input_normalization`].
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_with_highlights(&mut self, text: &str) -> Result<(Vec<i16>, Highlights)> {
+        let mut synth_output = SynthOutput {
+            audio: Vec::with_capacity(
+                self.estimate_samples(text.as_bytes(), &SynthesisOptions::default()),
+            ),
+            events: Some(Vec::new()),
+        };
+
+        let text = self.synthesize_preprocessed(text, &mut synth_output)?;
+        let events = synth_output.events.unwrap_or_default();
+
+        let audio = synth_output.audio;
+        let highlights = timing::highlights(text, &events, self.sample_rate(), audio.len());
+        Ok((audio, highlights))
+    }
+
+    /// Runs synthesis of the given text without keeping the audio, returning only the word timings
+    /// and length of the audio that would be generated.
+    ///
+    /// This is cheaper than [`Speaker::synthesize_with_word_timings`] when only timing is needed,
+    /// such as for scheduling or captions.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_timing(&mut self, text: &str) -> Result<SynthesisTiming> {
+        let mut event_output = EventOutput::default();
+        let text = self.synthesize_preprocessed(text, &mut event_output)?;
+
+        let sample_rate = self.sample_rate();
+        let stats = self
+            .last_stats
+            .unwrap_or_else(|| SynthesisStats::new(Duration::ZERO, 0, sample_rate));
+
+        Ok(SynthesisTiming {
+            words: timing::word_timings(&text, &event_output.0, sample_rate),
+            text,
+            samples: stats.samples,
+            duration: stats.audio_duration,
+        })
+    }
+
+    /// Preprocess the given UTF-8 text once, then synthesize it into `handler`, returning the
+    /// preprocessed text which the positions of the events refer to.
+    fn synthesize_preprocessed(
+        &mut self,
+        text: &str,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<String> {
+        self.check_input_length(text.len())?;
+        let text = self.filter_text(text).into_owned();
+
+        self.with_stats(handler, |backend, handler| {
+            backend.synthesize(text.as_bytes(), &SynthesisOptions::default(), handler)
+        })?;
+
+        Ok(text)
+    }
+
+    /// Synthesize into a buffer, capturing the events as well as the audio.
+    fn synthesize_with_events(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+    ) -> Result<(Vec<i16>, Vec<RawEvent>)> {
+        let mut synth_output = SynthOutput {
+            audio: Vec::with_capacity(self.estimate_samples(text, options)),
+            events: Some(Vec::new()),
+        };
+
+        self._synthesize(text, options, &mut synth_output)?;
+
+        Ok((synth_output.audio, synth_output.events.unwrap_or_default()))
+    }
+
+    /// Processes the given text, encoded as described by `text_mode`, into WAV audio data.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_encoded(&mut self, text: &[u8], text_mode: TextMode) -> Result<Vec<i16>> {
+        let options = SynthesisOptions {
+            text_mode,
+            ..SynthesisOptions::default()
+        };
+        self.synthesize_buffered(text, &options).into_result()
+    }
+
+    /// Processes the given UTF-16 text into WAV audio data, without transcoding it to UTF-8.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_utf16(&mut self, text: &[u16]) -> Result<Vec<i16>> {
+        let text_nul_term = utils::null_term_utf16(text)?;
+        let text_wide = &text_nul_term[..text_nul_term.len() - 1];
+
+        self.synthesize_encoded(bytemuck::cast_slice(text_wide), TextMode::WideChar)
+    }
+
+    /// Processes a long document into WAV audio data a chunk at a time, split at sentence boundaries.
+    ///
+    /// Each chunk is synthesized when the returned iterator is advanced, using `options` for
+    /// every chunk.
+    pub fn synthesize_document<'s, 't>(
+        &'s mut self,
+        text: &'t str,
+        options: &'t SynthesisOptions,
+    ) -> DocumentSynthesis<'s, 't, B> {
+        DocumentSynthesis::new(self, text, options)
+    }
+
+    /// Processes text from `reader` into WAV audio data a line at a time, without reading the
+    /// whole input into memory.
+    ///
+    /// Each line is read and synthesized when the returned iterator is advanced, using `options`
+    /// for every line. Errors reading the input, such as invalid UTF-8, are yielded as [`Error::Io`].
+    pub fn synthesize_from_reader<'s, 'o, R: std::io::BufRead>(
+        &'s mut self,
+        reader: R,
+        options: &'o SynthesisOptions,
+    ) -> ReaderSynthesis<'s, 'o, R, B> {
+        ReaderSynthesis::new(self, reader, options)
+    }
+
+    /// Processes the given phonemes, in espeak's phoneme notation, into WAV audio data.
+    ///
+    /// This is the inverse of [`Speaker::text_to_phonemes`] with [`PhonemeGenOptions::Standard`].
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_phonemes(&mut self, phonemes: &str) -> Result<Vec<i16>> {
+        let text = format!("[[{phonemes}]]");
+
+        let options = SynthesisOptions {
+            flags: SynthFlags::Phonemes,
+            ..SynthesisOptions::default()
+        };
+
+        self.synthesize_buffered(text.as_bytes(), &options)
+            .into_result()
+    }
+
+    /// Processes the given text into WAV audio data and writes it to a given file.
+    ///
+    /// This handles the `Vec<i16>` to `Vec<u8>` conversion internally.
+    ///
+    /// # Errors
+    /// See [`Speaker::synthesize`] + the file writing failed.
+    pub fn synthesize_to_file(&mut self, file: &mut std::fs::File, text: &str) -> Result<()> {
+        let audio_data = self.synthesize(text)?;
+        utils::write_samples_le(file, &audio_data)?;
+        Ok(())
+    }
+
+    /// Processes the given text into phonemes with `voice`, restoring the previous voice afterwards.
+    ///
+    /// # Errors
+    /// See [`Speaker::with_voice`] and [`Speaker::text_to_phonemes`].
+    pub fn text_to_phonemes_with_voice(
+        &mut self,
+        voice: &Voice,
+        text: &str,
+        option: PhonemeGenOptions<'_>,
+    ) -> Result<Option<String>> {
+        self.with_voice(voice, |speaker| speaker.text_to_phonemes(text, option))
+    }
+
+    /// Processes the given text into phonemes, depending on which [`PhonemeGenOptions`] are passed.
+    ///
+    /// This will only return [None] if [`PhonemeGenOptions::MbrolaFile`] is passed.
+    ///
+    /// # Errors
+    /// If [`PhonemeGenOptions::Mbrola`] or [`PhonemeGenOptions::MbrolaFile`] is passed, internal C calls may fail.
+    pub fn text_to_phonemes(
+        &mut self,
+        text: &str,
+        option: PhonemeGenOptions<'_>,
+    ) -> Result<Option<String>> {
+        match option {
+            PhonemeGenOptions::Standard {
+                text_mode,
+                phoneme_mode,
+            } => Ok(Some(self.text_to_phonemes_standard(
+                text,
+                text_mode,
+                phoneme_mode.bits(),
+            )?)),
+            PhonemeGenOptions::Ipa {
+                text_mode,
+                phoneme_mode,
+            } => Ok(Some(self.text_to_phonemes_standard(
+                text,
+                text_mode,
+                phoneme_mode.bits() | bindings::espeakPHONEMES_IPA,
+            )?)),
+            PhonemeGenOptions::Mbrola => self.text_to_phonemes_mbrola(text, None),
+            PhonemeGenOptions::MbrolaFile(writer) => {
+                self.text_to_phonemes_mbrola(text, Some(writer))
+            }
+        }
+    }
+
+    /// Processes the given text into phonemes, split up into the words they were generated from.
+    ///
+    /// The text ranges of the words are within the returned text, which differs from `text` if any
+    /// preprocessing is set, such as [`Speaker::set_text_filter`].
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn text_to_phonemes_aligned(
+        &mut self,
+        text: &str,
+    ) -> Result<(Vec<phoneme::AlignedWord>, String)> {
+        self.backend.set_phoneme_events(true, false)?;
+        let mut event_output = EventOutput::default();
+        let result = self.synthesize_preprocessed(text, &mut event_output);
+
+        // Reset phoneme events, to avoid the overhead on future synthesis calls
+        self.reset_phoneme_events()?;
+        let text = result?;
+
+        Ok((phoneme::align_words(&text, &event_output.0), text))
+    }
+
+    fn text_to_phonemes_standard(
+        &mut self,
+        text: &str,
+        text_mode: TextMode,
+        phoneme_mode: u32,
+    ) -> Result<String> {
+        let output = cache::CacheOutput::Phonemes {
+            text_mode: text_mode as u32,
+            phoneme_mode,
+        };
+
+        self.with_cache(output, text, |speaker| {
+            speaker.text_to_phonemes_uncached(text, text_mode, phoneme_mode)
+        })
+    }
+
+    fn text_to_phonemes_uncached(
+        &mut self,
+        text: &str,
+        text_mode: TextMode,
+        phoneme_mode: u32,
+    ) -> Result<String> {
+        self.check_input_length(text.len())?;
+        let text = self.filter_text(text);
+
+        self.backend
+            .text_to_phonemes(&text, text_mode, phoneme_mode)
+    }
+
+    /// Processes the given UTF-16 text into phonemes, depending on which [`PhonemeGenOptions`] are passed.
+    ///
+    /// The `text_mode` of [`PhonemeGenOptions::Standard`] and [`PhonemeGenOptions::Ipa`] is ignored.
+    ///
+    /// # Errors
+    /// See [`Speaker::text_to_phonemes`]
+    pub fn text_to_phonemes_utf16(
+        &mut self,
+        text: &[u16],
+        option: PhonemeGenOptions<'_>,
+    ) -> Result<Option<String>> {
+        self.check_input_length(std::mem::size_of_val(text))?;
+
+        // The text is passed to espeak as wide characters, which each hold a whole character.
+        let text = String::from_utf16_lossy(text);
+        match option {
+            PhonemeGenOptions::Standard { phoneme_mode, .. } => Ok(Some(
+                self.backend
+                    .text_to_phonemes(&text, TextMode::WideChar, phoneme_mode.bits())?,
+            )),
+            PhonemeGenOptions::Ipa { phoneme_mode, .. } => {
+                Ok(Some(self.backend.text_to_phonemes(
+                    &text,
+                    TextMode::WideChar,
+                    phoneme_mode.bits() | bindings::espeakPHONEMES_IPA,
+                )?))
+            }
+            // Mbrola phonemes are generated via synthesis, which takes UTF-8.
+            option => self.text_to_phonemes(&text, option),
+        }
+    }
+
+    /// Processes the given text into phonemes, split up into the clauses espeak processes separately.
+    ///
+    /// The text ranges of each clause refer to the text after any filter set with [`Speaker::set_text_filter`].
+    ///
+    /// # Errors
+    /// [`ESpeakNgError::NotSupported`] if [`PhonemeGenOptions::Mbrola`] or [`PhonemeGenOptions::MbrolaFile`]
+    /// is passed, as mbrola phonemes are not generated per clause.
+    pub fn text_to_phonemes_clauses(
+        &mut self,
+        text: &str,
+        option: &PhonemeGenOptions<'_>,
+    ) -> Result<Vec<phoneme::ClausePhonemes>> {
+        let (text_mode, phoneme_mode) = Self::clause_phoneme_modes(option)?;
+        self.check_input_length(text.len())?;
+
+        let text = self.filter_text(text).into_owned();
+
+        let mut clauses = Vec::new();
+        self.backend.text_to_phoneme_clauses(
+            &text,
+            text_mode,
+            phoneme_mode,
+            &mut |range, phonemes| {
+                clauses.push(phoneme::ClausePhonemes {
+                    text_range: range,
+                    phonemes: phonemes.to_owned(),
+                });
+            },
+        )?;
+
+        Ok(clauses)
+    }
+
+    /// Processes each of the given texts into the phonemes of all of its clauses, joined by spaces.
+    ///
+    /// Unlike [`Speaker::text_to_phonemes`], which only processes the first clause, every clause of
+    /// each text is processed, as [`Speaker::text_to_phonemes_clauses`] does. The results are cached
+    /// with [`Speaker::set_synthesis_cache`] separately from [`Speaker::text_to_phonemes`].
+    ///
+    /// # Errors
+    /// See [`Speaker::text_to_phonemes_clauses`].
+    pub fn text_to_clause_phonemes_batch(
+        &mut self,
+        texts: &[&str],
+        option: &PhonemeGenOptions<'_>,
+    ) -> Result<Vec<String>> {
+        let (text_mode, phoneme_mode) = Self::clause_phoneme_modes(option)?;
+        let output = cache::CacheOutput::ClausePhonemes {
+            text_mode: text_mode as u32,
+            phoneme_mode,
+        };
+
+        texts
+            .iter()
+            .map(|text| {
+                self.with_cache(output.clone(), text, |speaker| {
+                    speaker.check_input_length(text.len())?;
+                    let text = speaker.filter_text(text);
+
+                    let mut phonemes = String::new();
+                    speaker.backend.text_to_phoneme_clauses(
+                        &text,
+                        text_mode,
+                        phoneme_mode,
+                        &mut |_, clause| {
+                            if !phonemes.is_empty() {
+                                phonemes.push(' ');
+                            }
+
+                            phonemes.push_str(clause);
+                        },
+                    )?;
+
+                    Ok(phonemes)
+                })
+            })
+            .collect()
+    }
+
+    /// The text and phoneme modes of `option`, for the per clause phoneme functions.
+    fn clause_phoneme_modes(option: &PhonemeGenOptions<'_>) -> Result<(TextMode, u32)> {
+        let not_supported = Error::ESpeakNg {
+            func: "espeak_TextToPhonemes",
+            code: ESpeakNgError::NotSupported,
+            context: None,
+        };
+
+        // Clauses are located by byte ranges of the UTF-8 text, so cannot be found in wide characters.
+        match *option {
+            PhonemeGenOptions::Standard {
+                text_mode: TextMode::WideChar,
+                ..
+            }
+            | PhonemeGenOptions::Ipa {
+                text_mode: TextMode::WideChar,
+                ..
+            }
+            | PhonemeGenOptions::Mbrola
+            | PhonemeGenOptions::MbrolaFile(_) => Err(not_supported),
+            PhonemeGenOptions::Standard {
+                text_mode,
+                phoneme_mode,
+            } => Ok((text_mode, phoneme_mode.bits())),
+            PhonemeGenOptions::Ipa {
+                text_mode,
+                phoneme_mode,
+            } => Ok((
+                text_mode,
+                phoneme_mode.bits() | bindings::espeakPHONEMES_IPA,
+            )),
+        }
+    }
+
+    /// Trace how the pronunciation of each word of the given text was produced, such as which
+    /// dictionary rules matched, to debug why a word is mispronounced.
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn text_to_rule_trace(&mut self, text: &str) -> Result<Vec<phoneme::trace::WordTrace>> {
+        let trace = self.synthesize_traced(text, PhonemeTraceMode::Trace, &mut |_: &[i16]| {})?;
+        Ok(phoneme::trace::parse(&String::from_utf8_lossy(&trace)))
+    }
+
+    /// The words of the given text as espeak translates them, after its own text processing such
+    /// as lowercasing, splitting and abbreviation replacement.
+    ///
+    /// espeak has no direct output of its normalized text, so this is read from the translation
+    /// trace, see [`Speaker::text_to_rule_trace`]. espeak pronounces numbers from `_list` entries
+    /// without writing out their words, so numbers are expanded from those entries with English
+    /// voices, and returned as [`NormalizedWord::Number`] with other voices.
+    ///
+    /// [`NormalizedWord::Number`]: phoneme::trace::NormalizedWord::Number
+    ///
+    /// # Errors
+    /// If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn normalized_text(&mut self, text: &str) -> Result<Vec<phoneme::trace::NormalizedWord>> {
+        let english = self.try_current_voice().is_some_and(|voice| {
+            voice
+                .languages
+                .first()
+                .is_some_and(|language| language.name.starts_with("en"))
+        });
+
+        let words = self.text_to_rule_trace(text)?;
+        Ok(words.iter().map(|word| word.normalized(english)).collect())
+    }
+
+    /// Processes the given text into WAV audio data and the phoneme trace in a single pass.
+    ///
+    /// # Errors
+    /// - [`Error::MbrolaWithoutMbrolaVoice`] if [`PhonemeTraceMode::Mbrola`] is passed without an mbrola voice set.
+    /// - If the internal espeak synthesis fails, see [`ESpeakNgError`]
+    pub fn synthesize_with_phonemes(
+        &mut self,
+        text: &str,
+        mode: PhonemeTraceMode,
+    ) -> Result<(Vec<i16>, String)> {
+        let options = SynthesisOptions::default();
+        let mut synth_output = self.output_buffer(text.as_bytes(), &options);
+        let trace = self.synthesize_traced(text, mode, &mut synth_output)?;
+
+        Ok((synth_output.audio, String::from_utf8(trace)?))
+    }
+
+    /// Synthesize the text into `handler`, returning the phoneme trace written during synthesis.
+    fn synthesize_traced(
+        &mut self,
+        text: &str,
+        mode: PhonemeTraceMode,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<Vec<u8>> {
+        let mbrola_voice = self
+            .try_current_voice()
+            .is_some_and(|voice| voice.filename.starts_with("mb/"));
+
+        if mode == PhonemeTraceMode::Mbrola && !mbrola_voice {
+            return Err(Error::MbrolaWithoutMbrolaVoice);
+        }
+
+        let options = SynthesisOptions::default();
+        let text = self.preprocess(text.as_bytes(), options.text_mode)?;
+        self.with_stats(handler, |backend, handler| {
+            backend.synthesize_traced(&text, &options, mode, handler)
+        })
+    }
+
+    fn text_to_phonemes_mbrola(
+        &mut self,
+        text: &str,
+        writer: Option<&mut dyn Write>,
+    ) -> Result<Option<String>> {
+        let trace = self.synthesize_traced(text, PhonemeTraceMode::Mbrola, &mut |_: &[i16]| {})?;
+        if let Some(writer) = writer {
+            writer.write_all(&trace)?;
+            Ok(None)
+        } else {
+            Ok(Some(String::from_utf8(trace)?))
+        }
     }
 }
@@ -0,0 +1,381 @@
+use std::borrow::Cow;
+
+use crate::cache::StableHasher;
+
+#[rustfmt::skip]
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+#[rustfmt::skip]
+const GERMAN_MONTHS: [&str; 12] = [
+    "Januar", "Februar", "März", "April", "Mai", "Juni",
+    "Juli", "August", "September", "Oktober", "November", "Dezember",
+];
+
+#[rustfmt::skip]
+const FRENCH_MONTHS: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin",
+    "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+];
+
+#[rustfmt::skip]
+const SPANISH_MONTHS: [&str; 12] = [
+    "enero", "febrero", "marzo", "abril", "mayo", "junio",
+    "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre",
+];
+
+/// The order of the fields in numeric dates such as `3/4/2024`.
+///
+/// Dates starting with a four digit year, such as `2024-04-03`, are always read as year, month, day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+/// A currency symbol, and the names it is read as.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Currency {
+    /// The symbol written before or after the amount, such as `€`.
+    pub symbol: String,
+    /// The name read after an amount of exactly 1.
+    pub singular: String,
+    pub plural: String,
+}
+
+impl Currency {
+    fn new(symbol: &str, singular: &str, plural: &str) -> Self {
+        Self {
+            symbol: symbol.to_owned(),
+            singular: singular.to_owned(),
+            plural: plural.to_owned(),
+        }
+    }
+}
+
+/// How numbers, amounts of money and dates are written in a locale, used by
+/// [`crate::Speaker::set_locale_format`] to rewrite them into words espeak reads correctly.
+///
+/// Numbers are rewritten without digit grouping, with the fractional part read digit by digit after
+/// `decimal_word`, such as `1.234,56 €` to `1234 Komma 5 6 Euro` in German. Numeric dates are
+/// rewritten with the month name, such as `3/4/2024` to `3 April 2024` in British English.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocaleFormat {
+    /// The separator between the whole and fractional parts, such as `,` in German.
+    pub decimal_separator: char,
+    /// The separators between groups of three digits, such as `.` in German.
+    pub group_separators: Vec<char>,
+    /// The word read for the decimal separator, such as `point`.
+    pub decimal_word: String,
+    pub currencies: Vec<Currency>,
+    pub date_order: DateOrder,
+    /// The names of the months, from January.
+    pub month_names: [String; 12],
+    /// The text a date is read as, with `{day}`, `{month}` and `{year}` replaced.
+    pub date_template: String,
+}
+
+impl LocaleFormat {
+    /// The format for `language`, such as `en-us` or `de`, if a default is included.
+    ///
+    /// Defaults are included for English, German, French and Spanish. As the `en` voice is British
+    /// English, only `en-us` reads dates as month, day, year.
+    #[must_use]
+    pub fn for_language(language: &str) -> Option<Self> {
+        let language = language.to_ascii_lowercase().replace('_', "-");
+        let base = language.split('-').next().unwrap_or_default();
+
+        let format = match base {
+            "en" => {
+                let us = language == "en-us";
+                Self::new(
+                    '.',
+                    &[','],
+                    "point",
+                    vec![
+                        Currency::new("$", "dollar", "dollars"),
+                        Currency::new("€", "euro", "euros"),
+                        Currency::new("£", "pound", "pounds"),
+                        Currency::new("¥", "yen", "yen"),
+                    ],
+                    if us {
+                        DateOrder::MonthDayYear
+                    } else {
+                        DateOrder::DayMonthYear
+                    },
+                    ENGLISH_MONTHS,
+                    if us {
+                        "{month} {day}, {year}"
+                    } else {
+                        "{day} {month} {year}"
+                    },
+                )
+            }
+            "de" => Self::new(
+                ',',
+                &['.', '\u{A0}', '\u{202F}'],
+                "Komma",
+                vec![
+                    Currency::new("€", "Euro", "Euro"),
+                    Currency::new("$", "Dollar", "Dollar"),
+                    Currency::new("£", "Pfund", "Pfund"),
+                    Currency::new("CHF", "Franken", "Franken"),
+                ],
+                DateOrder::DayMonthYear,
+                GERMAN_MONTHS,
+                "{day}. {month} {year}",
+            ),
+            // Plain spaces are not used as group separators, as they also separate lists of numbers.
+            "fr" => Self::new(
+                ',',
+                &['.', '\u{A0}', '\u{202F}'],
+                "virgule",
+                vec![
+                    Currency::new("€", "euro", "euros"),
+                    Currency::new("$", "dollar", "dollars"),
+                    Currency::new("£", "livre", "livres"),
+                ],
+                DateOrder::DayMonthYear,
+                FRENCH_MONTHS,
+                "{day} {month} {year}",
+            ),
+            "es" => Self::new(
+                ',',
+                &['.', '\u{A0}', '\u{202F}'],
+                "coma",
+                vec![
+                    Currency::new("€", "euro", "euros"),
+                    Currency::new("$", "dólar", "dólares"),
+                    Currency::new("£", "libra", "libras"),
+                ],
+                DateOrder::DayMonthYear,
+                SPANISH_MONTHS,
+                "{day} de {month} de {year}",
+            ),
+            _ => return None,
+        };
+
+        Some(format)
+    }
+
+    /// The format for the first language of `voice` with a default, see [`LocaleFormat::for_language`].
+    #[must_use]
+    pub fn for_voice(voice: &crate::Voice) -> Option<Self> {
+        voice
+            .languages
+            .iter()
+            .find_map(|language| Self::for_language(&language.name))
+    }
+
+    fn new(
+        decimal_separator: char,
+        group_separators: &[char],
+        decimal_word: &str,
+        currencies: Vec<Currency>,
+        date_order: DateOrder,
+        month_names: [&str; 12],
+        date_template: &str,
+    ) -> Self {
+        Self {
+            decimal_separator,
+            group_separators: group_separators.to_vec(),
+            decimal_word: decimal_word.to_owned(),
+            currencies,
+            date_order,
+            month_names: month_names.map(str::to_owned),
+            date_template: date_template.to_owned(),
+        }
+    }
+
+    /// Rewrite the numbers, amounts of money and numeric dates in `text` into words.
+    ///
+    /// Plain integers, which espeak already reads correctly, are kept as they are.
+    #[must_use]
+    pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut applied = String::new();
+        let mut copied = 0;
+        let mut position = 0;
+
+        while position < text.len() {
+            let rest = &text[position..];
+            let at_word_start = !text[..position].ends_with(char::is_alphanumeric);
+            let read = at_word_start
+                .then(|| self.read_date(rest).or_else(|| self.read_amount(rest)))
+                .flatten()
+                .filter(|(len, _)| !rest[*len..].starts_with(char::is_alphanumeric));
+
+            if let Some((len, spoken)) = read {
+                applied.push_str(&text[copied..position]);
+                applied.push_str(&spoken);
+                position += len;
+                copied = position;
+            } else {
+                position += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+
+        if copied == 0 {
+            return Cow::Borrowed(text);
+        }
+
+        applied.push_str(&text[copied..]);
+        Cow::Owned(applied)
+    }
+
+    /// Read a numeric date at the start of `text`, returning its length and the spoken text.
+    fn read_date(&self, text: &str) -> Option<(usize, String)> {
+        let first = leading_digits(text);
+        let separator = text[first.len()..].chars().next()?;
+        if !matches!(separator, '/' | '.' | '-') {
+            return None;
+        }
+
+        let rest = &text[first.len() + 1..];
+        let second = leading_digits(rest);
+        let rest = rest[second.len()..].strip_prefix(separator)?;
+        let third = leading_digits(rest);
+
+        let (day, month, year) = match (first.len(), self.date_order) {
+            (4, _) | (_, DateOrder::YearMonthDay) => (third, second, first),
+            (_, DateOrder::DayMonthYear) => (first, second, third),
+            (_, DateOrder::MonthDayYear) => (second, first, third),
+        };
+
+        if !(1..=2).contains(&day.len())
+            || !(1..=2).contains(&month.len())
+            || !matches!(year.len(), 2 | 4)
+        {
+            return None;
+        }
+
+        let day: u8 = day.parse().ok().filter(|day| (1..=31).contains(day))?;
+        let month: usize = month
+            .parse()
+            .ok()
+            .filter(|month| (1..=12).contains(month))?;
+
+        let spoken = self
+            .date_template
+            .replace("{day}", &day.to_string())
+            .replace("{month}", &self.month_names[month - 1])
+            .replace("{year}", year);
+
+        let len = first.len() + second.len() + third.len() + 2 * separator.len_utf8();
+        Some((len, spoken))
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut StableHasher) {
+        let mut char_buf = [0; 4];
+        hasher.write_str(self.decimal_separator.encode_utf8(&mut char_buf));
+        hasher.write_str(&self.group_separators.iter().collect::<String>());
+        hasher.write_str(&self.decimal_word);
+
+        hasher.write_u64(self.currencies.len() as u64);
+        for currency in &self.currencies {
+            hasher.write_str(&currency.symbol);
+            hasher.write_str(&currency.singular);
+            hasher.write_str(&currency.plural);
+        }
+
+        hasher.write(&[self.date_order as u8]);
+        for month_name in &self.month_names {
+            hasher.write_str(month_name);
+        }
+
+        hasher.write_str(&self.date_template);
+    }
+
+    /// Read a number with grouped digits, a fractional part or a currency symbol at the start of
+    /// `text`, returning its length and the spoken text.
+    fn read_amount(&self, text: &str) -> Option<(usize, String)> {
+        let prefix = self
+            .currencies
+            .iter()
+            .find(|currency| text.starts_with(&currency.symbol));
+
+        let mut len = prefix.map_or(0, |currency| currency.symbol.len());
+        if prefix.is_some() && text[len..].starts_with([' ', '\u{A0}']) {
+            len += 1;
+        }
+
+        let whole_start = len;
+        let mut whole = String::from(leading_digits(&text[len..]));
+        if whole.is_empty() {
+            return None;
+        }
+
+        len += whole.len();
+        while let Some(separator) = text[len..]
+            .chars()
+            .next()
+            .filter(|c| self.group_separators.contains(c))
+        {
+            let group = leading_digits(&text[len + separator.len_utf8()..]);
+            if group.len() != 3 {
+                break;
+            }
+
+            whole.push_str(group);
+            len += separator.len_utf8() + group.len();
+        }
+
+        let mut fraction = "";
+        if let Some(rest) = text[len..].strip_prefix(self.decimal_separator) {
+            fraction = leading_digits(rest);
+            if !fraction.is_empty() {
+                len += self.decimal_separator.len_utf8() + fraction.len();
+            }
+        }
+
+        let currency = prefix.or_else(|| {
+            let rest = text[len..]
+                .strip_prefix([' ', '\u{A0}'])
+                .unwrap_or(&text[len..]);
+            let currency = self
+                .currencies
+                .iter()
+                .find(|currency| rest.starts_with(&currency.symbol))?;
+
+            len = text.len() - rest.len() + currency.symbol.len();
+            Some(currency)
+        });
+
+        // Plain integers are read correctly by espeak already.
+        if currency.is_none() && fraction.is_empty() && len - whole_start == whole.len() {
+            return None;
+        }
+
+        let mut spoken = whole.clone();
+        if !fraction.is_empty() {
+            spoken.push(' ');
+            spoken.push_str(&self.decimal_word);
+            for digit in fraction.chars() {
+                spoken.push(' ');
+                spoken.push(digit);
+            }
+        }
+
+        if let Some(currency) = currency {
+            spoken.push(' ');
+            spoken.push_str(if whole == "1" && fraction.is_empty() {
+                &currency.singular
+            } else {
+                &currency.plural
+            });
+        }
+
+        Some((len, spoken))
+    }
+}
+
+/// The ASCII digits at the start of `text`.
+fn leading_digits(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+
+    &text[..end]
+}
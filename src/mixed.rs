@@ -0,0 +1,47 @@
+use crate::Voice;
+
+/// A run of text spoken with a single voice, used to speak text mixing multiple languages.
+///
+/// See [`crate::Speaker::synthesize_runs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoiceRun<'a> {
+    pub text: &'a str,
+    /// The voice to speak the text with, or the current voice if [`None`].
+    pub voice: Option<Voice>,
+}
+
+/// Split `text` into runs of sentences detected as the same language.
+#[cfg(feature = "detect-language")]
+pub(crate) fn split_runs<'a>(voices: &[Voice], text: &'a str) -> Vec<VoiceRun<'a>> {
+    let mut runs: Vec<(std::ops::Range<usize>, Option<Voice>)> = Vec::new();
+
+    let mut start = 0;
+    let sentence_ends = crate::document::sentence_ends(text).chain(std::iter::once(text.len()));
+    for end in sentence_ends {
+        if end <= start {
+            continue;
+        }
+
+        let sentence = &text[start..end];
+        let voice = if sentence.trim().is_empty() {
+            None
+        } else {
+            crate::detect::detect_voice(voices, sentence).cloned()
+        };
+
+        match runs.last_mut() {
+            // Sentences which could not be detected, such as short ones, join the previous run.
+            Some((range, run_voice)) if voice.is_none() || *run_voice == voice => range.end = end,
+            _ => runs.push((start..end, voice)),
+        }
+
+        start = end;
+    }
+
+    runs.into_iter()
+        .map(|(range, voice)| VoiceRun {
+            text: &text[range],
+            voice,
+        })
+        .collect()
+}
@@ -0,0 +1,228 @@
+//! A fake backend returning canned output, for testing code built on [Speaker] where espeak-ng-data
+//! is not installed, such as in CI.
+//!
+//! Only the backend is faked, so the [Speaker] methods run as they would with espeak-ng, including
+//! text preprocessing, caching and voice or parameter overrides.
+//!
+//! ```rust
+//! use espeakng::mock::{MockBackend, MockSpeaker};
+//!
+//! let mut speaker = MockSpeaker::with_backend(MockBackend::new().phonemes("Hello", "h@l'oU"));
+//!
+//! let phonemes = speaker.text_to_phonemes("Hello", espeakng::PhonemeGenOptions::Standard {
+//!     phoneme_mode: espeakng::PhonemeMode::default(),
+//!     text_mode: espeakng::TextMode::default(),
+//! });
+//!
+//! assert_eq!(phonemes.unwrap().as_deref(), Some("h@l'oU"));
+//! assert!(!speaker.synthesize("Hello world").unwrap().is_empty());
+//! assert_eq!(speaker.backend().synthesized(), ["Hello world"]);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    backend::EspeakBackend, ESpeakNgError, Error, Language, Parameter, PhonemeTraceMode, Result,
+    Speaker, SynthCallbackHandler, SynthesisOptions, TextMode, Voice,
+};
+
+/// A [Speaker] using [`MockBackend`].
+pub type MockSpeaker = Speaker<MockBackend>;
+
+/// A backend which stores parameters in memory and returns canned phonemes and audio.
+pub struct MockBackend {
+    /// The current and default value of each parameter set or read.
+    parameters: Vec<(Parameter, i32, i32)>,
+    voices: Vec<Voice>,
+    current_voice: Option<Voice>,
+    phonemes: HashMap<String, String>,
+    audio: HashMap<String, Vec<i16>>,
+    samples_per_word: usize,
+    synthesized: Vec<String>,
+}
+
+impl MockBackend {
+    /// The sample rate reported by the mock, matching espeak's.
+    pub const SAMPLE_RATE: u32 = 22050;
+
+    /// Create a mock with espeak's default parameters and a single English voice, set as current.
+    #[must_use]
+    pub fn new() -> Self {
+        let voice = Voice {
+            name: String::from("English (Great Britain)"),
+            filename: String::from(Speaker::DEFAULT_VOICE),
+            languages: vec![Language {
+                name: String::from("en-gb"),
+                priority: 2,
+            }],
+            gender: None,
+            age: 0,
+        };
+
+        Self {
+            parameters: Vec::new(),
+            voices: vec![voice.clone()],
+            current_voice: Some(voice),
+            phonemes: HashMap::new(),
+            audio: HashMap::new(),
+            samples_per_word: Self::SAMPLE_RATE as usize / 4,
+            synthesized: Vec::new(),
+        }
+    }
+
+    /// Return `phonemes` when generating the phonemes of exactly `text`.
+    ///
+    /// Other text generates itself, lowercased, as its phonemes.
+    #[must_use]
+    pub fn phonemes(mut self, text: &str, phonemes: &str) -> Self {
+        self.phonemes.insert(text.to_owned(), phonemes.to_owned());
+        self
+    }
+
+    /// Return `audio` when synthesizing exactly `text`.
+    ///
+    /// Other text generates silence, with the length set by [`MockBackend::samples_per_word`].
+    #[must_use]
+    pub fn audio(mut self, text: &str, audio: Vec<i16>) -> Self {
+        self.audio.insert(text.to_owned(), audio);
+        self
+    }
+
+    /// Set the number of samples of silence generated per word, a quarter second by default.
+    #[must_use]
+    pub fn samples_per_word(mut self, samples_per_word: usize) -> Self {
+        self.samples_per_word = samples_per_word;
+        self
+    }
+
+    /// The text passed to each synthesis call, in order.
+    #[must_use]
+    pub fn synthesized(&self) -> &[String] {
+        &self.synthesized
+    }
+
+    fn default_value(param: Parameter) -> i32 {
+        match param {
+            Parameter::Rate => 175,
+            Parameter::Volume | Parameter::SsmlBreakMul => 100,
+            Parameter::Pitch | Parameter::Range => 50,
+            _ => 0,
+        }
+    }
+
+    fn parameter_mut(&mut self, param: Parameter) -> &mut (Parameter, i32, i32) {
+        if let Some(index) = self.parameters.iter().position(|(p, ..)| *p == param) {
+            return &mut self.parameters[index];
+        }
+
+        let default = Self::default_value(param);
+        self.parameters.push((param, default, default));
+        let index = self.parameters.len() - 1;
+
+        &mut self.parameters[index]
+    }
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EspeakBackend for MockBackend {
+    fn get_parameter(&mut self, param: Parameter, default: bool) -> i32 {
+        let (_, current, default_value) = *self.parameter_mut(param);
+        if default {
+            default_value
+        } else {
+            current
+        }
+    }
+
+    fn set_parameter(&mut self, param: Parameter, value: i32, relative: bool) -> Result<()> {
+        let (_, current, _) = self.parameter_mut(param);
+        if relative {
+            *current += value;
+        } else {
+            *current = value;
+        }
+
+        Ok(())
+    }
+
+    fn list_voices(&mut self) -> Vec<Voice> {
+        self.voices.clone()
+    }
+
+    fn current_voice(&self) -> Option<Voice> {
+        self.current_voice.clone()
+    }
+
+    fn set_voice_by_name(&mut self, filename: &str) -> Result<()> {
+        // The variant does not change the canned output, so only the voice is checked.
+        let name = filename.split('+').next().unwrap_or(filename);
+        let Some(voice) = self.voices.iter().find(|voice| voice.filename == name) else {
+            return Err(Error::ESpeakNg {
+                func: "espeak_ng_SetVoiceByName",
+                code: ESpeakNgError::VoiceNotFound,
+                context: None,
+            });
+        };
+
+        self.current_voice = Some(voice.clone());
+        Ok(())
+    }
+
+    fn sample_rate(&self) -> u32 {
+        Self::SAMPLE_RATE
+    }
+
+    /// Pass the canned audio for `text` to `handler`, recording the call.
+    fn synthesize(
+        &mut self,
+        text: &[u8],
+        _options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<()> {
+        let text = String::from_utf8_lossy(text).into_owned();
+        match self.audio.get(&text) {
+            Some(audio) => handler.on_audio(audio),
+            None => handler.on_audio(&vec![
+                0;
+                text.split_whitespace().count() * self.samples_per_word
+            ]),
+        }
+
+        self.synthesized.push(text);
+        Ok(())
+    }
+
+    /// Synthesize as [`MockBackend`] does, with an empty phoneme trace.
+    fn synthesize_traced(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        _mode: PhonemeTraceMode,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> Result<Vec<u8>> {
+        self.synthesize(text, options, handler)?;
+        Ok(Vec::new())
+    }
+
+    /// Return the canned phonemes for `text`, regardless of the modes.
+    fn text_to_phonemes(
+        &mut self,
+        text: &str,
+        _text_mode: TextMode,
+        _phoneme_mode: u32,
+    ) -> Result<String> {
+        match self.phonemes.get(text) {
+            Some(phonemes) => Ok(phonemes.clone()),
+            None => Ok(text.to_lowercase()),
+        }
+    }
+
+    fn set_phoneme_events(&mut self, _enabled: bool, _ipa: bool) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use std::borrow::Cow;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::Normalization;
+
+/// Whether `c` is an invisible formatting character which espeak may read out or split words on.
+///
+/// The zero width joiner and non-joiner are kept, as they change the spelling of words in scripts
+/// such as Persian and Devanagari.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        // Zero width space, word joiner and byte order mark.
+        '\u{200B}' | '\u{2060}' | '\u{FEFF}'
+        // Soft hyphen.
+        | '\u{00AD}'
+        // Bidirectional marks, embeddings, overrides and isolates.
+        | '\u{200E}' | '\u{200F}' | '\u{061C}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2066}'..='\u{2069}'
+    )
+}
+
+/// Apply `normalization` to `text`, borrowing it if nothing needs to change.
+pub(crate) fn normalize(text: &str, normalization: Normalization) -> Cow<'_, str> {
+    let mut text = Cow::Borrowed(text);
+    if normalization.contains(Normalization::StripInvisible) && text.contains(is_invisible) {
+        text = Cow::Owned(text.chars().filter(|c| !is_invisible(*c)).collect());
+    }
+
+    if normalization.contains(Normalization::Nfc) && !unicode_normalization::is_nfc(&text) {
+        text = Cow::Owned(text.nfc().collect());
+    }
+
+    text
+}
@@ -0,0 +1,101 @@
+use std::io::Write;
+
+use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+
+use crate::effects::{Processor, Speed};
+
+/// The sample rate audio is encoded at, as Opus only accepts a few fixed rates.
+const OPUS_SAMPLE_RATE: u32 = 48_000;
+/// The number of samples in each packet, 20ms at [`OPUS_SAMPLE_RATE`] as recommended for speech.
+const FRAME_LEN: usize = 960;
+/// The largest packet the encoder may produce, as recommended by libopus.
+const MAX_PACKET_LEN: usize = 4000;
+/// The serial number of the only logical stream in the file.
+const STREAM_SERIAL: u32 = 1;
+
+/// Write mono 16-bit PCM audio, as generated by [`crate::Speaker::synthesize`], as an Ogg Opus file.
+///
+/// Opus only encodes a few fixed sample rates, so the audio is first resampled to 48kHz with linear
+/// resampling. The original sample rate is recorded in the header for decoders which resample.
+///
+/// # Errors
+/// If writing or encoding fails.
+pub fn write_ogg_opus(
+    writer: &mut impl Write,
+    sample_rate: u32,
+    audio: &[i16],
+) -> std::io::Result<()> {
+    let mut encoder = opus::Encoder::new(
+        OPUS_SAMPLE_RATE,
+        opus::Channels::Mono,
+        opus::Application::Voip,
+    )
+    .map_err(std::io::Error::other)?;
+
+    // The samples of encoder delay at the start of the stream, which decoders skip.
+    let pre_skip = encoder.get_lookahead().map_err(std::io::Error::other)?;
+    let pre_skip = u16::try_from(pre_skip).map_err(std::io::Error::other)?;
+
+    let mut packets = PacketWriter::new(writer);
+    packets.write_packet(
+        opus_head(sample_rate, pre_skip),
+        STREAM_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+    packets.write_packet(opus_tags(), STREAM_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+    #[allow(clippy::cast_precision_loss)] // Sample rates are small enough to be exact.
+    let mut samples = Speed(sample_rate as f32 / OPUS_SAMPLE_RATE as f32).process(audio);
+    let end_position = u64::from(pre_skip) + samples.len() as u64;
+
+    // Pad the last frame with silence, which the final granule position trims off again.
+    let frame_count = samples.len().div_ceil(FRAME_LEN).max(1);
+    samples.resize(frame_count * FRAME_LEN, 0);
+
+    for (index, frame) in samples.chunks_exact(FRAME_LEN).enumerate() {
+        let packet = encoder
+            .encode_vec(frame, MAX_PACKET_LEN)
+            .map_err(std::io::Error::other)?;
+
+        let (end_info, position) = if index + 1 == frame_count {
+            (PacketWriteEndInfo::EndStream, end_position)
+        } else {
+            let decoded = ((index + 1) * FRAME_LEN) as u64;
+            (
+                PacketWriteEndInfo::NormalPacket,
+                u64::from(pre_skip) + decoded,
+            )
+        };
+
+        packets.write_packet(packet, STREAM_SERIAL, end_info, position)?;
+    }
+
+    Ok(())
+}
+
+/// The identification header, the first packet of an Ogg Opus stream.
+fn opus_head(sample_rate: u32, pre_skip: u16) -> Vec<u8> {
+    let mut head = Vec::with_capacity(19);
+    head.extend_from_slice(b"OpusHead");
+    head.push(1); // Version
+    head.push(1); // Mono
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&sample_rate.to_le_bytes());
+    head.extend_from_slice(&0_i16.to_le_bytes()); // Output gain
+    head.push(0); // Channel mapping family for mono and stereo
+    head
+}
+
+/// The comment header, the second packet of an Ogg Opus stream, without any comments.
+fn opus_tags() -> Vec<u8> {
+    const VENDOR: &str = concat!("espeakng-rs ", env!("CARGO_PKG_VERSION"));
+
+    let mut tags = Vec::with_capacity(16 + VENDOR.len());
+    tags.extend_from_slice(b"OpusTags");
+    #[allow(clippy::cast_possible_truncation)] // The vendor string is a few bytes long.
+    tags.extend_from_slice(&(VENDOR.len() as u32).to_le_bytes());
+    tags.extend_from_slice(VENDOR.as_bytes());
+    tags.extend_from_slice(&0_u32.to_le_bytes()); // Comment count
+    tags
+}
@@ -0,0 +1,169 @@
+use std::{
+    fs,
+    io::{ErrorKind, Write as _},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::Lexicon;
+
+/// A private view of an espeak data directory in a temporary directory, allowing files to be added
+/// without writing to the original, which is often a read only system directory.
+///
+/// Every entry is linked to the original, except `voices/!v`, which is a real directory so custom
+/// variants can be added, and dictionaries compiled into the overlay. The directory is removed when dropped.
+pub(crate) struct DataOverlay {
+    path: PathBuf,
+}
+
+impl DataOverlay {
+    /// Create an overlay of `data_path` in a new directory, readable only by the current user.
+    pub(crate) fn new(data_path: &Path) -> std::io::Result<Self> {
+        let overlay = Self {
+            path: create_unique_dir(&std::env::temp_dir())?,
+        };
+
+        for entry in fs::read_dir(data_path)? {
+            let entry = entry?;
+            let target = overlay.path.join(entry.file_name());
+            if entry.file_name() == "voices" {
+                overlay_dir(&entry.path(), &target, |name| name == "!v")?;
+            } else {
+                link(&entry.path(), &target)?;
+            }
+        }
+
+        fs::create_dir_all(overlay.variants_dir())?;
+
+        Ok(overlay)
+    }
+
+    /// The path to initialise espeak with.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write a voice variant, loaded by espeak with `+name` after the voice name.
+    pub(crate) fn write_variant(&self, name: &str, contents: &str) -> std::io::Result<()> {
+        fs::write(self.variants_dir().join(name), contents)
+    }
+
+    /// Copy the source files of `lexicon`'s dictionary into the overlay, appending its entries to
+    /// the `_extra` file, and remove the link to the compiled dictionary so compiling it writes a
+    /// new file instead of through the link. Returns the directory the source files were copied to.
+    pub(crate) fn write_dictionary_source(&self, lexicon: &Lexicon) -> std::io::Result<PathBuf> {
+        let source_dir = self.path.join("dictsource");
+        fs::create_dir_all(&source_dir)?;
+
+        for file_name in lexicon.source_file_names() {
+            let target = source_dir.join(&file_name);
+            match fs::copy(lexicon.dict_source_dir().join(&file_name), &target) {
+                Ok(_) => {}
+                // Only the rules and list are required, which the compiler reports if missing.
+                Err(err) if err.kind() == ErrorKind::NotFound => remove_file(&target)?,
+                Err(err) => return Err(err),
+            }
+        }
+
+        let extra_path = source_dir.join(format!("{}_extra", lexicon.dict_name()));
+        let mut extra = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(extra_path)?;
+        write!(extra, "\n{lexicon}")?;
+
+        remove_file(&self.path.join(format!("{}_dict", lexicon.dict_name())))?;
+        Ok(source_dir)
+    }
+
+    fn variants_dir(&self) -> PathBuf {
+        self.path.join("voices").join("!v")
+    }
+}
+
+impl Drop for DataOverlay {
+    fn drop(&mut self) {
+        // Links are removed without touching the original entries.
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Create `target` as a real directory, linking each entry of `source` except those where
+/// `is_overlaid` is true, which are overlaid in turn.
+fn overlay_dir(
+    source: &Path,
+    target: &Path,
+    is_overlaid: impl Fn(&std::ffi::OsStr) -> bool + Copy,
+) -> std::io::Result<()> {
+    fs::create_dir(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let entry_target = target.join(entry.file_name());
+        if is_overlaid(&entry.file_name()) {
+            overlay_dir(&entry.path(), &entry_target, is_overlaid)?;
+        } else {
+            link(&entry.path(), &entry_target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Make `original` available at `link`, as a symlink where supported, otherwise as a copy.
+fn link(original: &Path, link: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(original, link)
+    }
+
+    #[cfg(not(unix))]
+    {
+        // Symlinks need extra privileges on Windows, so the data is copied instead.
+        if original.is_dir() {
+            fs::create_dir(link)?;
+            for entry in fs::read_dir(original)? {
+                let entry = entry?;
+                self::link(&entry.path(), &link.join(entry.file_name()))?;
+            }
+
+            Ok(())
+        } else {
+            fs::copy(original, link).map(drop)
+        }
+    }
+}
+
+/// Remove the file or link at `path`, if present.
+fn remove_file(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Create a new directory within `parent`, readable only by the current user, returning its path.
+fn create_unique_dir(parent: &Path) -> std::io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.subsec_nanos());
+
+    let mut attempt = 0_u32;
+    loop {
+        let path = parent.join(format!(
+            "espeakng-rs-overlay.{}.{nanos}.{attempt}",
+            std::process::id()
+        ));
+
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        match builder.create(&path) {
+            Ok(()) => return Ok(path),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists && attempt < 16 => {
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Utilities for working with the phonemes generated by [`crate::Speaker::text_to_phonemes`].
+
+use std::ops::Range;
+
+use crate::{bindings, EventId, EventType, RawEvent};
+
+pub mod convert;
+pub mod trace;
+
+/// The phonemes generated for a single word of the input text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlignedWord {
+    /// The byte range of the word within the text after preprocessing.
+    pub text_range: Range<usize>,
+    /// The phonemes of the word, in espeak's phoneme notation.
+    pub phonemes: Vec<String>,
+}
+
+/// The phonemes generated for a single clause of the input text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClausePhonemes {
+    /// The byte range of the clause within the input text, including trailing punctuation.
+    pub text_range: Range<usize>,
+    /// The phonemes of the clause, in the notation requested.
+    pub phonemes: String,
+}
+
+/// Converts a 1-based character position and length from espeak into a byte range of `text`.
+pub(crate) fn char_range_to_byte_range(text: &str, position: i32, length: i32) -> Range<usize> {
+    let start_char = (position.max(1) - 1) as usize;
+    let mut char_offsets = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(text.len()));
+
+    let start = char_offsets.nth(start_char).unwrap_or(text.len());
+    let end = if length > 0 {
+        char_offsets.nth(length as usize - 1).unwrap_or(text.len())
+    } else {
+        start
+    };
+
+    start..end
+}
+
+/// Reads the phoneme mnemonic stored inline in a phoneme event.
+pub(crate) fn event_phoneme_name(event: &bindings::espeak_EVENT) -> String {
+    let name = unsafe { event.id.string };
+    let bytes: Vec<u8> = name
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect();
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Groups the phoneme events of a synthesis call under the preceding word events.
+pub(crate) fn align_words(text: &str, events: &[RawEvent]) -> Vec<AlignedWord> {
+    let mut words: Vec<AlignedWord> = Vec::new();
+    for event in events {
+        match (&event.event_type, &event.id) {
+            (EventType::Word, _) => words.push(AlignedWord {
+                text_range: char_range_to_byte_range(text, event.text_position, event.length),
+                phonemes: Vec::new(),
+            }),
+            (EventType::Phoneme, EventId::Phoneme(name)) => {
+                // Pauses are not part of any word
+                if let Some(word) = words.last_mut().filter(|_| !name.starts_with('_')) {
+                    word.phonemes.push(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    words
+}
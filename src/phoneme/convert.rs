@@ -0,0 +1,140 @@
+//! Conversion between the phonetic alphabets used by espeak and downstream tools.
+//!
+//! Conversion is table based and covers the phonemes used by espeak's English voices, along with
+//! stress and length marks. Any unrecognised characters are passed through unchanged.
+//!
+//! ```rust
+//! use espeakng::phoneme::convert::{convert, Alphabet};
+//!
+//! assert_eq!(convert("h@l'oU", Alphabet::Espeak, Alphabet::Ipa), "həlˈoʊ");
+//! assert_eq!(convert("həlˈoʊ", Alphabet::Ipa, Alphabet::XSampa), "h@l\"oU");
+//! ```
+
+/// A phonetic alphabet which phonemes can be converted between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// espeak's internal ASCII phoneme names, as generated by [`crate::PhonemeGenOptions::Standard`].
+    Espeak,
+    /// The International Phonetic Alphabet, as generated by [`crate::PhonemeGenOptions::Ipa`].
+    Ipa,
+    /// The Extended Speech Assessment Methods Phonetic Alphabet.
+    XSampa,
+    /// The Kirshenbaum ASCII-IPA alphabet.
+    Kirshenbaum,
+}
+
+#[rustfmt::skip]
+/// Rows of equivalent phonemes, in the order `Espeak`, `Ipa`, `XSampa`, `Kirshenbaum`.
+const PHONEMES: &[[&str; 4]] = &[
+    // Vowels
+    ["a",  "æ",  "{",  "&"],
+    ["a#", "ɐ",  "6",  "a#"],
+    ["A:", "ɑː", "A:", "A:"],
+    ["A",  "ɑ",  "A",  "A"],
+    ["0",  "ɒ",  "Q",  "A."],
+    ["@",  "ə",  "@",  "@"],
+    ["3:", "ɜː", "3:", "V\":"],
+    ["3",  "ɜ",  "3",  "V\""],
+    ["E",  "ɛ",  "E",  "E"],
+    ["e",  "e",  "e",  "e"],
+    ["I",  "ɪ",  "I",  "I"],
+    ["i:", "iː", "i:", "i:"],
+    ["i",  "i",  "i",  "i"],
+    ["O:", "ɔː", "O:", "O:"],
+    ["O",  "ɔ",  "O",  "O"],
+    ["o",  "o",  "o",  "o"],
+    ["U",  "ʊ",  "U",  "U"],
+    ["u:", "uː", "u:", "u:"],
+    ["u",  "u",  "u",  "u"],
+    ["V",  "ʌ",  "V",  "V"],
+    // Diphthongs
+    ["aI", "aɪ", "aI", "aI"],
+    ["aU", "aʊ", "aU", "aU"],
+    ["eI", "eɪ", "eI", "eI"],
+    ["e@", "eə", "e@", "e@"],
+    ["I@", "ɪə", "I@", "I@"],
+    ["OI", "ɔɪ", "OI", "OI"],
+    ["oU", "oʊ", "oU", "oU"],
+    ["U@", "ʊə", "U@", "U@"],
+    // Consonants
+    ["b",  "b",  "b",  "b"],
+    ["d",  "d",  "d",  "d"],
+    ["D",  "ð",  "D",  "D"],
+    ["dZ", "dʒ", "dZ", "dZ"],
+    ["f",  "f",  "f",  "f"],
+    ["g",  "ɡ",  "g",  "g"],
+    ["h",  "h",  "h",  "h"],
+    ["j",  "j",  "j",  "j"],
+    ["k",  "k",  "k",  "k"],
+    ["l",  "l",  "l",  "l"],
+    ["m",  "m",  "m",  "m"],
+    ["n",  "n",  "n",  "n"],
+    ["N",  "ŋ",  "N",  "N"],
+    ["p",  "p",  "p",  "p"],
+    ["r",  "ɹ",  "r\\", "r"],
+    ["s",  "s",  "s",  "s"],
+    ["S",  "ʃ",  "S",  "S"],
+    ["t",  "t",  "t",  "t"],
+    ["T",  "θ",  "T",  "T"],
+    ["tS", "tʃ", "tS", "tS"],
+    ["*",  "ɾ",  "4",  "*"],
+    ["v",  "v",  "v",  "v"],
+    ["w",  "w",  "w",  "w"],
+    ["x",  "x",  "x",  "x"],
+    ["z",  "z",  "z",  "z"],
+    ["Z",  "ʒ",  "Z",  "Z"],
+    ["?",  "ʔ",  "?",  "?"],
+    // Suprasegmentals
+    ["'",  "ˈ",  "\"", "'"],
+    [",",  "ˌ",  "%",  ","],
+    [":",  "ː",  ":",  ":"],
+];
+
+impl Alphabet {
+    fn column(self) -> usize {
+        match self {
+            Self::Espeak => 0,
+            Self::Ipa => 1,
+            Self::XSampa => 2,
+            Self::Kirshenbaum => 3,
+        }
+    }
+}
+
+/// Finds the row with the longest phoneme in `column` that `input` starts with.
+fn longest_match(input: &str, column: usize) -> Option<&'static [&'static str; 4]> {
+    let mut best: Option<&[&str; 4]> = None;
+    for row in PHONEMES {
+        let phoneme = row[column];
+        if input.starts_with(phoneme) && best.is_none_or(|best| phoneme.len() > best[column].len())
+        {
+            best = Some(row);
+        }
+    }
+
+    best
+}
+
+/// Converts a phoneme string from one [`Alphabet`] to another.
+#[must_use]
+pub fn convert(input: &str, from: Alphabet, to: Alphabet) -> String {
+    if from == to {
+        return input.to_owned();
+    }
+
+    let (from, to) = (from.column(), to.column());
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(next_char) = rest.chars().next() {
+        if let Some(row) = longest_match(rest, from) {
+            output.push_str(row[to]);
+            rest = &rest[row[from].len()..];
+        } else {
+            output.push(next_char);
+            rest = &rest[next_char.len_utf8()..];
+        }
+    }
+
+    output
+}
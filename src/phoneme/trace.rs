@@ -0,0 +1,205 @@
+//! Parsing of espeak's translation trace, explaining which dictionary entry or rules produced the
+//! pronunciation of each word.
+//!
+//! ```rust
+//! use espeakng::phoneme::trace::{parse, PronunciationSource};
+//!
+//! let words = parse("Translate 'hello'\n  1\th        [h]\n\n 22\t@) l (lo [l]\n\nh@l'oU\n");
+//! assert_eq!(words[0].word, "hello");
+//! assert_eq!(words[0].phonemes, "h@l'oU");
+//! assert!(matches!(&words[0].source, PronunciationSource::Rules(rules) if rules.len() == 2));
+//! ```
+
+/// A single spelling rule which matched part of a word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleMatch {
+    /// The score espeak gave the rule, where the highest scoring rule is used.
+    pub score: i32,
+    /// The rule as written in the `_rules` file, such as `@) l (lo`.
+    pub rule: String,
+    /// The phonemes the rule produced.
+    pub phonemes: String,
+}
+
+/// Where the pronunciation of a word came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PronunciationSource {
+    /// An entry in the `_list` file of the dictionary.
+    List,
+    /// The spelling rules in the `_rules` file of the dictionary, in the order they matched.
+    Rules(Vec<RuleMatch>),
+}
+
+/// The translation trace of a single word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordTrace {
+    /// The word being translated, as espeak saw it.
+    pub word: String,
+    pub source: PronunciationSource,
+    /// The phonemes of the word, in espeak's phoneme notation.
+    pub phonemes: String,
+    /// The `_list` entries found while translating the word, in order, such as `_4X` and `_2` for
+    /// the number `42`.
+    pub entries: Vec<String>,
+}
+
+impl WordTrace {
+    /// The word as [`crate::Speaker::normalized_text`] returns it, expanding numbers into words
+    /// if `english`, as the words of the number entries are only known for English.
+    pub(crate) fn normalized(&self, english: bool) -> NormalizedWord {
+        if !self.word.starts_with(|c: char| c.is_ascii_digit()) {
+            return NormalizedWord::Word(self.word.clone());
+        }
+
+        let words: Option<Vec<&str>> = english
+            .then(|| {
+                self.entries
+                    .iter()
+                    .filter(|entry| entry.starts_with('_'))
+                    .map(|entry| english_number_word(entry))
+                    .collect()
+            })
+            .flatten();
+
+        match words {
+            Some(words) if !words.is_empty() => NormalizedWord::Word(words.join(" ")),
+            _ => NormalizedWord::Number(self.word.clone()),
+        }
+    }
+}
+
+/// A word of the text returned by [`crate::Speaker::normalized_text`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NormalizedWord {
+    /// A word as espeak translated it, or a number expanded into words.
+    Word(String),
+    /// The digits of a number which could not be expanded into words, as the words of the `_list`
+    /// entries espeak pronounced it with are not known for the language of the voice.
+    Number(String),
+}
+
+/// The English word of a number entry of the `en_list` file, such as `_4X` for `forty`.
+fn english_number_word(entry: &str) -> Option<&'static str> {
+    const UNITS: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    let word = match entry {
+        "_0C" => "hundred",
+        "_0M1" => "thousand",
+        "_0M2" => "million",
+        "_0M3" => "billion",
+        "_0M4" => "trillion",
+        "_0and" => "and",
+        "_dpt" => "point",
+        entry => {
+            let digits = entry.strip_prefix('_')?;
+            if let Some(tens) = digits.strip_suffix('X') {
+                TENS.get(tens.parse::<usize>().ok()?)
+                    .copied()
+                    .filter(|tens| !tens.is_empty())?
+            } else {
+                UNITS.get(digits.parse::<usize>().ok()?).copied()?
+            }
+        }
+    };
+
+    Some(word)
+}
+
+/// Parse the trace written by espeak with [`crate::PhonemeTraceMode::Trace`].
+///
+/// Lines which are not understood are skipped.
+#[must_use]
+pub fn parse(trace: &str) -> Vec<WordTrace> {
+    let mut words: Vec<WordTrace> = Vec::new();
+    for line in trace.lines() {
+        if let Some(word) = quoted(line, "Translate '") {
+            words.push(WordTrace {
+                word: word.to_owned(),
+                source: PronunciationSource::Rules(Vec::new()),
+                phonemes: String::new(),
+                entries: Vec::new(),
+            });
+
+            continue;
+        }
+
+        let Some(current) = words.last_mut() else {
+            continue;
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(found) = line.strip_prefix("Found: '") {
+            current.source = PronunciationSource::List;
+            if let Some((entry, _)) = found.split_once('\'') {
+                current.entries.push(entry.to_owned());
+            }
+
+            if let Some(phonemes) = bracketed(found) {
+                phonemes.clone_into(&mut current.phonemes);
+            }
+        } else if let Some(rule_match) = parse_rule(line) {
+            if let PronunciationSource::Rules(rules) = &mut current.source {
+                rules.push(rule_match);
+            }
+        } else if !line.contains(char::is_whitespace) && current.phonemes.is_empty() {
+            // The final pronunciation is printed alone, once the rules have all matched.
+            line.clone_into(&mut current.phonemes);
+        }
+    }
+
+    words
+}
+
+/// Parse a rule line, such as ` 22\t@) l (lo [l]`.
+fn parse_rule(line: &str) -> Option<RuleMatch> {
+    let (score, rest) = line.split_once('\t')?;
+    let score = score.trim().parse().ok()?;
+
+    let phonemes_start = rest.rfind('[')?;
+    Some(RuleMatch {
+        score,
+        rule: rest[..phonemes_start].trim().to_owned(),
+        phonemes: bracketed(&rest[phonemes_start..])?.to_owned(),
+    })
+}
+
+/// The text between `prefix` and the following `'`.
+fn quoted<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = line.trim().strip_prefix(prefix)?;
+    Some(&rest[..rest.find('\'')?])
+}
+
+/// The text within the first pair of square brackets.
+fn bracketed(text: &str) -> Option<&str> {
+    let start = text.find('[')? + 1;
+    let end = start + text[start..].find(']')?;
+    Some(&text[start..end])
+}
@@ -0,0 +1,106 @@
+use std::{fmt::Write, time::Duration};
+
+/// A piece of speech, used to express pauses and reading modes without assembling SSML by hand.
+///
+/// See [`crate::Speaker::synthesize_segments`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// Text to speak, which is escaped so is never interpreted as SSML.
+    Text(&'a str),
+    /// A pause, compiled to an SSML `<break>` element.
+    Pause(Duration),
+    /// Text to speak in a specific way, such as reading a phone number digit by digit.
+    SayAs(&'a str, SayAs),
+}
+
+/// How to read the text of a [`Segment::SayAs`], overriding espeak's own number expansion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SayAs {
+    /// Read numbers one digit at a time, such as for IDs and phone numbers.
+    Digits,
+    /// Read every character individually, such as for codes.
+    Characters,
+    /// Read four digit numbers as a year, such as `1984` as "nineteen eighty four".
+    Year,
+    /// Read numbers as English ordinals, such as `3` as "third".
+    Ordinal,
+}
+
+impl Segment<'_> {
+    /// Compile the segments into an SSML document, to be synthesized with [`crate::SynthFlags::Ssml`].
+    #[must_use]
+    pub fn to_ssml(segments: &[Self]) -> String {
+        let mut ssml = String::from("<speak>");
+        for segment in segments {
+            match segment {
+                Self::Text(text) => escape_into(&mut ssml, text),
+                Self::Pause(duration) => {
+                    write!(ssml, "<break time=\"{}ms\"/>", duration.as_millis())
+                        .expect("writing to a String should not fail");
+                }
+                Self::SayAs(text, SayAs::Digits) => {
+                    ssml.push_str("<say-as interpret-as=\"tts:digits\" detail=\"1\">");
+                    escape_into(&mut ssml, text);
+                    ssml.push_str("</say-as>");
+                }
+                Self::SayAs(text, SayAs::Characters) => {
+                    ssml.push_str("<say-as interpret-as=\"characters\">");
+                    escape_into(&mut ssml, text);
+                    ssml.push_str("</say-as>");
+                }
+                // espeak does not support these in SSML, so the text is rewritten instead.
+                Self::SayAs(text, SayAs::Year) => escape_into(&mut ssml, &year(text)),
+                Self::SayAs(text, SayAs::Ordinal) => escape_into(&mut ssml, &ordinal(text)),
+            }
+        }
+
+        ssml.push_str("</speak>");
+        ssml
+    }
+}
+
+/// Split a four digit year into two numbers, which espeak reads in the usual way.
+fn year(text: &str) -> String {
+    let is_year = text.len() == 4 && text.bytes().all(|b| b.is_ascii_digit());
+
+    // Years such as 2005 and 1900 would be read as "twenty five" and "nineteen zero zero" if split.
+    if !is_year || text[1..3] == *"00" || text.ends_with("00") {
+        return text.to_owned();
+    }
+
+    format!("{} {}", &text[..2], &text[2..])
+}
+
+/// Add an English ordinal suffix to a number, which espeak reads as an ordinal.
+fn ordinal(text: &str) -> String {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return text.to_owned();
+    }
+
+    let last_two = text.get(text.len().saturating_sub(2)..);
+    let suffix = if matches!(last_two, Some("11" | "12" | "13")) {
+        "th"
+    } else {
+        match text.as_bytes()[text.len() - 1] {
+            b'1' => "st",
+            b'2' => "nd",
+            b'3' => "rd",
+            _ => "th",
+        }
+    };
+
+    format!("{text}{suffix}")
+}
+
+fn escape_into(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '&' => output.push_str("&amp;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            c => output.push(c),
+        }
+    }
+}
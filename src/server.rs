@@ -0,0 +1,179 @@
+//! A small HTTP text-to-speech service built on axum, enabled with the `server` feature.
+//!
+//! Requests are queued onto a [`SpeakerHandle`], so the [`crate::Speaker`] is only used by one
+//! request at a time. The endpoints are:
+//! - `POST /synthesize`, with UTF-8 text as the body, returning `audio/wav`, or `audio/ogg` encoded
+//!   with Opus if the `Accept` header lists `audio/ogg` or `audio/opus`.
+//! - `POST /phonemes`, with UTF-8 text as the body, returning the IPA phonemes as `text/plain`.
+//!
+//! [`serve`] handles a limited number of connections at once, each for a single request, closing
+//! connections which send too many headers or are not answered within a deadline, such as clients
+//! sending their request slowly. Use [`router`] to add the endpoints to a larger application.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let handle = espeakng::SpeakerHandle::new(espeakng::InitOptions::default())?;
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+//! espeakng::server::serve(listener, handle).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use hyper::server::conn::http1;
+use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+use tokio::{net::TcpListener, sync::Semaphore};
+
+use crate::{Error, PhonemeGenOptions, PhonemeMode, SpeakerHandle, TextMode};
+
+/// The largest request body accepted, in bytes.
+const MAX_BODY_LEN: usize = 1024 * 1024;
+/// The largest number of headers accepted in a request.
+const MAX_HEADERS: usize = 64;
+/// The largest request line and headers accepted, in bytes.
+const MAX_HEAD_LEN: usize = 16 * 1024;
+/// The number of connections handled at once, further connections wait to be accepted.
+const MAX_CONNECTIONS: usize = 16;
+/// How long a connection may take to send its request and read the response before it is closed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The audio formats returned by `/synthesize`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AudioFormat {
+    Wav,
+    OggOpus,
+}
+
+impl AudioFormat {
+    /// The format listed in the `Accept` header, or WAV by default.
+    fn accepted(headers: &HeaderMap) -> Self {
+        let accepts_opus = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|media_type| media_type.split(';').next())
+            .any(|media_type| matches!(media_type.trim(), "audio/ogg" | "audio/opus"));
+
+        if accepts_opus {
+            Self::OggOpus
+        } else {
+            Self::Wav
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Wav => "audio/wav",
+            Self::OggOpus => "audio/ogg; codecs=opus",
+        }
+    }
+}
+
+/// The endpoints described in the [module docs](self), using `handle` for all requests.
+pub fn router(handle: SpeakerHandle) -> Router {
+    Router::new()
+        .route("/synthesize", post(synthesize))
+        .route("/phonemes", post(phonemes))
+        .layer(DefaultBodyLimit::max(MAX_BODY_LEN))
+        .with_state(handle)
+}
+
+/// Accept and handle connections on `listener` forever, see the [module docs](self).
+///
+/// # Errors
+/// If accepting a connection fails.
+pub async fn serve(listener: TcpListener, handle: SpeakerHandle) -> std::io::Result<()> {
+    let router = router(handle);
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+
+    loop {
+        // Only accept once a connection slot is free, so further connections wait in the backlog.
+        let permit = Arc::clone(&connections)
+            .acquire_owned()
+            .await
+            .map_err(std::io::Error::other)?;
+
+        let (stream, _) = listener.accept().await?;
+        let service = TowerToHyperService::new(router.clone());
+
+        tokio::spawn(async move {
+            let connection = http1::Builder::new()
+                .max_headers(MAX_HEADERS)
+                .max_buf_size(MAX_HEAD_LEN)
+                .keep_alive(false)
+                .serve_connection(TokioIo::new(stream), service);
+
+            // The client may be too slow or have disconnected, which there is nobody to report to.
+            let _ = tokio::time::timeout(REQUEST_TIMEOUT, connection).await;
+            drop(permit);
+        });
+    }
+}
+
+async fn synthesize(
+    State(handle): State<SpeakerHandle>,
+    headers: HeaderMap,
+    text: String,
+) -> Response {
+    let format = AudioFormat::accepted(&headers);
+    run_blocking(move || {
+        let (audio, sample_rate) = handle
+            .run(move |speaker| Ok((speaker.synthesize(&text)?, speaker.sample_rate())))
+            .and_then(|result| result)?;
+
+        // Encode outside of the handle, so other requests are not queued behind it.
+        let mut body = Vec::new();
+        match format {
+            AudioFormat::Wav => crate::write_wav(&mut body, sample_rate, &audio)?,
+            AudioFormat::OggOpus => crate::write_ogg_opus(&mut body, sample_rate, &audio)?,
+        }
+
+        Ok(([(header::CONTENT_TYPE, format.content_type())], body).into_response())
+    })
+    .await
+}
+
+async fn phonemes(State(handle): State<SpeakerHandle>, text: String) -> Response {
+    run_blocking(move || {
+        let phonemes = handle
+            .run(move |speaker| {
+                speaker.text_to_phonemes(
+                    &text,
+                    PhonemeGenOptions::Ipa {
+                        text_mode: TextMode::Utf8,
+                        phoneme_mode: PhonemeMode::default(),
+                    },
+                )
+            })
+            .and_then(|result| result)?;
+
+        Ok(phonemes.unwrap_or_default().into_response())
+    })
+    .await
+}
+
+/// Run `func` on a thread where blocking is allowed, as waiting for the [`SpeakerHandle`] would
+/// otherwise stall the async runtime.
+async fn run_blocking(func: impl FnOnce() -> crate::Result<Response> + Send + 'static) -> Response {
+    let err = match tokio::task::spawn_blocking(func).await {
+        Ok(Ok(response)) => return response,
+        Ok(Err(err)) => err,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Request panicked").into_response(),
+    };
+
+    let status = match err {
+        Error::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    (status, err.to_string()).into_response()
+}
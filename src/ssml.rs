@@ -0,0 +1,136 @@
+use std::fmt::Write;
+
+/// The prefix of the SSML `<mark>` names inserted to locate voice changes.
+const MARK_PREFIX: &str = "espeakng-rs-voice-";
+
+/// The voice attributes requested by SSML elements, such as `<voice gender="female">`.
+///
+/// Nested elements inherit any attributes they do not set from the enclosing element.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VoiceSelection {
+    pub name: Option<String>,
+    /// The language, from the `xml:lang` attribute.
+    pub language: Option<String>,
+    pub gender: Option<String>,
+    pub age: Option<String>,
+    pub variant: Option<String>,
+}
+
+/// A voice change caused by an SSML element, from [`crate::Speaker::synthesize_ssml_with_voice_changes`].
+///
+/// Changes are reported both at the start of the element, and at its end where the voice reverts to
+/// the enclosing selection, which is empty outside of any element for the current voice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoiceChange {
+    /// The voice which became active.
+    pub selection: VoiceSelection,
+    /// The byte offset of the opening or closing tag in the SSML.
+    pub text_position: usize,
+    /// The offset in the generated audio, in samples.
+    pub sample: usize,
+}
+
+/// Insert a `<mark>` after every tag which changes the voice, returning the selection and tag
+/// position for each mark, in order.
+///
+/// espeak reads `xml:lang` on `<speak>`, `<voice>`, `<p>` and `<s>`, while `<voice>` also
+/// selects by name, gender, age and variant. Other elements, such as `<lang>`, are not supported.
+pub(crate) fn mark_voice_changes(ssml: &str) -> (String, Vec<(usize, VoiceSelection)>) {
+    let mut marked = String::with_capacity(ssml.len());
+    let mut changes = Vec::new();
+    let mut stack: Vec<(&str, VoiceSelection)> = Vec::new();
+
+    let mut copied = 0;
+    let mut position = 0;
+    while let Some(start) = ssml[position..].find('<').map(|i| position + i) {
+        let Some(end) = ssml[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+
+        position = end;
+        let tag = &ssml[start + 1..end - 1];
+        if tag.starts_with(['!', '?']) || tag.ends_with('/') {
+            continue;
+        }
+
+        let selection = if let Some(closing) = tag.strip_prefix('/') {
+            // The root element cannot be followed by a mark, and has nothing after it to speak.
+            if stack.last().map(|(name, _)| *name) != Some(closing.trim())
+                || closing.trim() == "speak"
+            {
+                continue;
+            }
+
+            stack.pop();
+            stack
+                .last()
+                .map(|(_, selection)| selection.clone())
+                .unwrap_or_default()
+        } else {
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            let attributes = parse_attributes(&tag[name.len()..]);
+            let is_voice = name == "voice";
+            let sets_language = matches!(name, "speak" | "p" | "s")
+                && attributes.iter().any(|(key, _)| *key == "xml:lang");
+
+            if !(is_voice || sets_language) {
+                continue;
+            }
+
+            let mut selection = stack
+                .last()
+                .map(|(_, selection)| selection.clone())
+                .unwrap_or_default();
+            for (key, value) in attributes {
+                let field = match key {
+                    "xml:lang" => &mut selection.language,
+                    "name" if is_voice => &mut selection.name,
+                    "gender" if is_voice => &mut selection.gender,
+                    "age" if is_voice => &mut selection.age,
+                    "variant" if is_voice => &mut selection.variant,
+                    _ => continue,
+                };
+
+                *field = Some(value.to_owned());
+            }
+
+            stack.push((name, selection.clone()));
+            selection
+        };
+
+        marked.push_str(&ssml[copied..end]);
+        write!(marked, "<mark name=\"{MARK_PREFIX}{}\"/>", changes.len())
+            .expect("writing to a String should not fail");
+
+        copied = end;
+        changes.push((start, selection));
+    }
+
+    marked.push_str(&ssml[copied..]);
+    (marked, changes)
+}
+
+/// The index of a mark inserted by [`mark_voice_changes`], or [`None`] for other marks.
+pub(crate) fn voice_change_index(mark_name: &str) -> Option<usize> {
+    mark_name.strip_prefix(MARK_PREFIX)?.parse().ok()
+}
+
+/// Parse the `key="value"` attributes of a tag, which may also be quoted with `'`.
+fn parse_attributes(mut attributes: &str) -> Vec<(&str, &str)> {
+    let mut parsed = Vec::new();
+    while let Some((key, rest)) = attributes.split_once('=') {
+        let rest = rest.trim_start();
+        let Some(quote) = rest.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+            break;
+        };
+
+        let Some((value, rest)) = rest[1..].split_once(quote) else {
+            break;
+        };
+
+        parsed.push((key.trim(), value));
+        attributes = rest;
+    }
+
+    parsed
+}
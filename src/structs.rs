@@ -1,30 +1,293 @@
-use std::os::unix::prelude::AsRawFd;
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use bitflags::bitflags;
 
 use crate::utils::StringFromCPtr;
 use crate::{bindings, utils};
 
-#[derive(Clone, Copy)]
+/// How espeak outputs the audio it generates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum OutputMode {
+    /// Audio is passed back asynchronously, returned by synthesis functions.
+    Retrieval = 0,
+    /// Audio is passed back synchronously, returned by synthesis functions.
+    #[default]
+    Synchronous = 1,
+    /// Audio is played asynchronously through the audio device, unsupported on emscripten.
+    Playback = 2,
+    /// Audio is played synchronously through the audio device, unsupported on emscripten.
+    SynchronousPlayback = 3,
+}
+
+/// Options for [`crate::initialise`], constructed via builder methods.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InitOptions {
+    pub(crate) data_path: Option<PathBuf>,
+    pub(crate) output_mode: OutputMode,
+    pub(crate) buffer_length_ms: u16,
+    pub(crate) phoneme_events: bool,
+    pub(crate) phoneme_events_ipa: bool,
+    pub(crate) default_voice: String,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            data_path: None,
+            output_mode: OutputMode::default(),
+            buffer_length_ms: 0,
+            phoneme_events: false,
+            phoneme_events_ipa: false,
+            default_voice: String::from(crate::Speaker::DEFAULT_VOICE),
+        }
+    }
+}
+
+impl InitOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the path of the `espeak-ng-data` directory, instead of the default install location
+    /// or the embedded data with the `embed-data` feature.
+    #[must_use]
+    pub fn data_path(mut self, data_path: impl AsRef<Path>) -> Self {
+        self.data_path = Some(data_path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set how espeak outputs generated audio, default [`OutputMode::Synchronous`].
+    #[must_use]
+    pub fn output_mode(mut self, output_mode: OutputMode) -> Self {
+        self.output_mode = output_mode;
+        self
+    }
+
+    /// Set the length of the audio buffers passed back by espeak in milliseconds, 0 uses espeak's default of 60.
+    #[must_use]
+    pub fn buffer_length_ms(mut self, buffer_length_ms: u16) -> Self {
+        self.buffer_length_ms = buffer_length_ms;
+        self
+    }
+
+    /// Enable phoneme events during synthesis, with phoneme names optionally in IPA.
+    #[must_use]
+    pub fn phoneme_events(mut self, enabled: bool, ipa: bool) -> Self {
+        self.phoneme_events = enabled;
+        self.phoneme_events_ipa = ipa;
+        self
+    }
+
+    /// Set the voice selected after initialisation, default [`crate::Speaker::DEFAULT_VOICE`].
+    #[must_use]
+    pub fn default_voice(mut self, filename: impl Into<String>) -> Self {
+        self.default_voice = filename.into();
+        self
+    }
+}
+
+/// The unit that synthesis positions are measured in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PositionType {
+    #[default]
+    Character = 1,
+    Word = 2,
+    Sentence = 3,
+}
+
+/// Options for [`crate::Speaker::synthesize_with_options`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SynthesisOptions {
+    /// The position in the text to start speaking from, 0 starts from the beginning.
+    pub position: u32,
+    /// The unit that `position` is measured in.
+    pub position_type: PositionType,
+    /// The character position in the text to stop speaking at, 0 speaks to the end.
+    pub end_position: u32,
+    /// The encoding of the text.
+    pub text_mode: TextMode,
+    /// Flags changing how the text is interpreted.
+    pub flags: SynthFlags,
+    /// The number of samples to preallocate the output for, estimated from the text and speaking rate if [None].
+    pub capacity_hint: Option<usize>,
+    /// The speaking rate to use for this call only, restored afterwards.
+    pub rate: Option<WordsPerMinute>,
+    /// The pitch to use for this call only, restored afterwards.
+    pub pitch: Option<Pitch>,
+    /// The volume to use for this call only, restored afterwards.
+    pub volume: Option<Volume>,
+    /// The voice to use for this call only, restored afterwards.
+    pub voice: Option<Voice>,
+}
+
+impl SynthesisOptions {
+    /// If any parameters or the voice are overridden for the call.
+    pub(crate) fn has_overrides(&self) -> bool {
+        self.rate.is_some() || self.pitch.is_some() || self.volume.is_some() || self.voice.is_some()
+    }
+
+    /// The parameters overridden for the call, to be set after the voice.
+    pub(crate) fn parameter_overrides(&self) -> Vec<(Parameter, i32)> {
+        [
+            (Parameter::Rate, self.rate.map(|rate| i32::from(rate.get()))),
+            (
+                Parameter::Pitch,
+                self.pitch.map(|pitch| i32::from(pitch.get())),
+            ),
+            (
+                Parameter::Volume,
+                self.volume.map(|volume| i32::from(volume.get())),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(param, value)| Some((param, value?)))
+        .collect()
+    }
+}
+
+/// The result of [`crate::Speaker::synthesize_partial`], keeping the audio generated before any
+/// error or cancellation.
+#[derive(Debug)]
+pub struct SynthesisOutcome {
+    /// The audio data generated, which is incomplete if `error` is set.
+    pub audio: Vec<i16>,
+    /// The error which stopped synthesis, such as [`crate::Error::Cancelled`].
+    pub error: Option<crate::Error>,
+}
+
+impl SynthesisOutcome {
+    /// If synthesis completed without error.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Discard the partial audio if synthesis failed.
+    ///
+    /// # Errors
+    /// The error which stopped synthesis.
+    pub fn into_result(self) -> crate::Result<Vec<i16>> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.audio),
+        }
+    }
+}
+
+/// Metrics of a single synthesis call, returned by [`crate::Speaker::stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SynthesisStats {
+    /// The wall-clock time taken to synthesize the audio.
+    pub elapsed: Duration,
+    /// The number of audio samples generated.
+    pub samples: usize,
+    /// The length of the generated audio when played.
+    pub audio_duration: Duration,
+}
+
+impl SynthesisStats {
+    #[allow(clippy::cast_precision_loss)] // Sample counts will not exceed 2^52.
+    pub(crate) fn new(elapsed: Duration, samples: usize, sample_rate: u32) -> Self {
+        let audio_duration = if sample_rate == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(samples as f64 / f64::from(sample_rate))
+        };
+
+        Self {
+            elapsed,
+            samples,
+            audio_duration,
+        }
+    }
+
+    /// The time taken to synthesize the audio relative to its length, below 1.0 is faster than real time.
+    ///
+    /// Returns [None] if no audio was generated.
+    #[must_use]
+    pub fn real_time_factor(&self) -> Option<f64> {
+        (!self.audio_duration.is_zero())
+            .then(|| self.elapsed.as_secs_f64() / self.audio_duration.as_secs_f64())
+    }
+}
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct SynthFlags: u32 {
+        /// interpret SSML markup in the text.
+        const Ssml = bindings::espeakSSML;
+        /// interpret phoneme input enclosed in `[[ ]]`.
+        const Phonemes = bindings::espeakPHONEMES;
+        /// add a sentence pause at the end of the text.
+        const EndPause = bindings::espeakENDPAUSE;
+        /// keep the named data, such as SSML voice names, from previous calls.
+        const KeepNameData = bindings::espeakKEEP_NAMEDATA;
+    }
+}
+
 pub enum PhonemeGenOptions<'a> {
     /// Generate phonemes using the standard espeak style
     Standard {
         text_mode: TextMode,
         phoneme_mode: PhonemeMode,
     },
+    /// Generate phonemes using the International Phonetic Alphabet
+    Ipa {
+        text_mode: TextMode,
+        phoneme_mode: PhonemeMode,
+    },
     /// Generate phonemes using the mbrola style
     Mbrola,
-    /// Generate phonemes using the mbrola style and write them in a file
-    MbrolaFile(&'a dyn AsRawFd),
+    /// Generate phonemes using the mbrola style and write them to a writer, such as a file or `Vec<u8>`
+    MbrolaFile(&'a mut dyn std::io::Write),
+}
+
+/// The format of the phoneme trace written during synthesis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhonemeTraceMode {
+    /// Phonemes in the standard espeak style.
+    Standard,
+    /// Phonemes in the International Phonetic Alphabet.
+    Ipa,
+    /// Phonemes and durations in the mbrola `.pho` style, requires an mbrola voice.
+    Mbrola,
+    /// Phonemes along with the translation rules used to produce them, for debugging pronunciation.
+    Trace,
+}
+
+impl PhonemeTraceMode {
+    pub(crate) fn bits(self) -> i32 {
+        (match self {
+            Self::Standard => bindings::espeakPHONEMES_SHOW,
+            Self::Ipa => bindings::espeakPHONEMES_SHOW | bindings::espeakPHONEMES_IPA,
+            Self::Mbrola => bindings::espeakPHONEMES_MBROLA,
+            Self::Trace => bindings::espeakPHONEMES_SHOW | bindings::espeakPHONEMES_TRACE,
+        }) as i32
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[repr(u32)]
 /// Type of character codes
 pub enum TextMode {
+    /// Detect UTF8 encoding, otherwise falling back to the 8 bit character set of the current voice
+    Auto = 0,
     /// UTF8 encoding
     #[default]
     Utf8 = 1,
+    /// The 8 bit character set of the current voice, such as ISO-8859-1 for English
+    EightBit = 2,
+    /// Native endian `wchar_t` characters, UTF-16 on Windows and UTF-32 elsewhere.
+    ///
+    /// [`crate::Speaker::synthesize_utf16`] handles this conversion from UTF-16.
+    WideChar = 3,
 }
 
 bitflags! {
@@ -39,20 +302,100 @@ bitflags! {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, strum_macros::FromRepr)]
+bitflags! {
+    /// Cleanup applied to text before it is passed to espeak, see [`crate::Speaker::set_input_normalization`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Normalization: u32 {
+        /// compose characters into Unicode Normalization Form C, so combining accents are read as one letter.
+        const Nfc = 1;
+        /// remove zero width spaces, soft hyphens and bidirectional control characters.
+        const StripInvisible = 2;
+    }
+}
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct CompileFlags: u32 {
+        /// include source line number information, for debugging dictionary rules.
+        const DebugLineNumbers = 1;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, strum_macros::FromRepr)]
 #[repr(u8)]
 pub enum Gender {
     Male = 1,
     Female = 2,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// A variant of espeak's Klatt formant synthesizer, applied on top of a voice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KlattVariant {
+    Klatt,
+    Klatt2,
+    Klatt3,
+    Klatt4,
+    Klatt5,
+    /// Uses the speechPlayer synthesizer from NVDA if espeak-ng is built with it, otherwise falling
+    /// back to espeak's Klatt synthesizer, see
+    /// [`crate::Speaker::speech_player_available`].
+    Klatt6,
+}
+
+impl KlattVariant {
+    /// Every Klatt variant, in order.
+    pub const ALL: [Self; 6] = [
+        Self::Klatt,
+        Self::Klatt2,
+        Self::Klatt3,
+        Self::Klatt4,
+        Self::Klatt5,
+        Self::Klatt6,
+    ];
+
+    /// The variant which uses speechPlayer, if espeak-ng is built with it.
+    pub const SPEECH_PLAYER: Self = Self::Klatt6;
+
+    /// The name of the variant file, such as `klatt2`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Klatt => "klatt",
+            Self::Klatt2 => "klatt2",
+            Self::Klatt3 => "klatt3",
+            Self::Klatt4 => "klatt4",
+            Self::Klatt5 => "klatt5",
+            Self::Klatt6 => "klatt6",
+        }
+    }
+}
+
+/// The optional features available in the internal espeak-ng library, from [`crate::Speaker::capabilities`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Each feature is independent, so bitflags would add nothing.
+pub struct Capabilities {
+    /// An mbrola voice has its voice database installed, so can be used.
+    pub mbrola: bool,
+    /// The Klatt voice variants are installed, see [`crate::Speaker::klatt_variants`].
+    pub klatt: bool,
+    /// espeak-ng is built with libsonic, see [`crate::Speaker::sonic_available`].
+    pub sonic: bool,
+    /// espeak-ng is built with speechPlayer, see [`crate::Speaker::speech_player_available`].
+    pub speech_player: bool,
+    /// Audio can be played through the audio device, with [`OutputMode::Playback`], found by
+    /// initialising playback output.
+    pub async_audio: bool,
+    /// SSML input is supported, found by synthesizing SSML with a `<mark>` and checking its event.
+    pub ssml: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Language {
     pub name: String,
     pub priority: i8,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 #[non_exhaustive] // Keep Voice private constructable to keep set_voice safe.
 pub struct Voice {
     pub name: String,
@@ -62,6 +405,14 @@ pub struct Voice {
     pub age: u8,
 }
 
+impl Voice {
+    /// If a [`KlattVariant`] can be applied to this voice, which is true of all but mbrola voices.
+    #[must_use]
+    pub fn supports_klatt(&self) -> bool {
+        !self.filename.starts_with("mb/")
+    }
+}
+
 impl From<bindings::espeak_VOICE> for Voice {
     fn from(voice: bindings::espeak_VOICE) -> Self {
         unsafe {
@@ -79,9 +430,10 @@ impl From<bindings::espeak_VOICE> for Voice {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum Parameter {
-    /// Words per minute. Values must be between 80-450 inclusive.
+    /// Words per minute. Values must be between 80-450 inclusive, or 80-1000 with the `sonic` feature.
     Rate = 1,
-    /// Volume of speech. Values should be 0-100 as greater values may produce amplitude compression or distortion.
+    /// Volume of speech, default 100. Values must be between 0-200 inclusive, as values above 100 may
+    /// produce amplitude compression or distortion.
     Volume = 2,
     /// Base pitch, default 50. Values must be between 0-100 inclusive.
     Pitch = 3,
@@ -89,14 +441,231 @@ pub enum Parameter {
     Range = 4,
     /// The punctuation characters to speak. Value must be [PunctationType].
     Punctuation = 5,
-    /// How to pronounce capital letters.
-    /// - 0 = none
-    /// - 1 = sound icon
-    /// - 2 = spelling
-    /// - 3 or higher, by raising pitch. The value is the amount of Hz by which the pitch of each capitalised word is raised.
+    /// How to pronounce capital letters, see [`CapitalsMode`].
     Capitals = 6,
     /// The units of how long to pause between words. At default speed, this is units of of 10mS.
     Wordgap = 7,
+    /// Internal option flags, such as the pronunciation of `r` for some English voices.
+    Options = 8,
+    /// The intonation tunes used for each clause type, selected per voice by default.
+    Intonation = 9,
+    /// The percentage to multiply the duration of SSML `<break>` elements by, default 100.
+    SsmlBreakMul = 10,
+    /// The level of emphasis, as set by SSML `<emphasis>` elements.
+    Emphasis = 12,
+    /// Lines shorter than this many characters are treated as the end of a clause, 0 disables this.
+    LineLength = 13,
+    /// The type of voice currently in use, used internally by espeak.
+    VoiceType = 14,
+}
+
+impl Parameter {
+    /// The range of values espeak documents as valid for this parameter, if limited.
+    #[must_use]
+    pub fn valid_range(self) -> Option<RangeInclusive<i32>> {
+        match self {
+            Self::Rate => {
+                Some(i32::from(WordsPerMinute::MIN.get())..=i32::from(WordsPerMinute::MAX.get()))
+            }
+            Self::Volume => Some(0..=200),
+            Self::Pitch | Self::Range => Some(0..=100),
+            Self::Punctuation => Some(0..=2),
+            Self::Capitals
+            | Self::Wordgap
+            | Self::Options
+            | Self::Intonation
+            | Self::SsmlBreakMul
+            | Self::Emphasis
+            | Self::LineLength
+            | Self::VoiceType => None,
+        }
+    }
+}
+
+macro_rules! parameter_newtype {
+    ($(#[$meta:meta])* $name:ident($inner:ty) => $param:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name($inner);
+
+        impl $name {
+            #[doc = concat!("Validate a value against the range of [`Parameter::", stringify!($param), "`].")]
+            ///
+            /// # Errors
+            /// [`crate::Error::ParameterOutOfRange`] if the value is outside of the valid range.
+            pub fn new(value: $inner) -> Result<Self, crate::Error> {
+                let range = Self::range();
+                if range.contains(&i32::from(value)) {
+                    Ok(Self(value))
+                } else {
+                    Err(crate::Error::ParameterOutOfRange {
+                        param: Parameter::$param,
+                        value: i32::from(value),
+                        range,
+                    })
+                }
+            }
+
+            /// The validated value.
+            #[must_use]
+            pub fn get(self) -> $inner {
+                self.0
+            }
+
+            /// Convert a value from espeak, clamping it into the valid range.
+            pub(crate) fn clamped(value: i32) -> Self {
+                let range = Self::range();
+                let value = value.clamp(*range.start(), *range.end());
+                Self(<$inner>::try_from(value).expect("valid range should fit in the inner type"))
+            }
+
+            fn range() -> RangeInclusive<i32> {
+                Parameter::$param
+                    .valid_range()
+                    .expect("parameter should have a valid range")
+            }
+        }
+
+        impl TryFrom<$inner> for $name {
+            type Error = crate::Error;
+
+            fn try_from(value: $inner) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+parameter_newtype! {
+    /// A speaking rate in words per minute, between 80-450 inclusive, or 80-1000 with the `sonic` feature.
+    WordsPerMinute(u16) => Rate
+}
+
+impl WordsPerMinute {
+    /// The slowest rate espeak supports.
+    pub const MIN: Self = Self(80);
+    /// The fastest rate espeak supports.
+    ///
+    /// With the `sonic` feature, rates above [`WordsPerMinute::MAX_WITHOUT_SONIC`] are sped up by
+    /// libsonic time-stretching, which keeps the pitch of the voice unchanged. This is only possible
+    /// if espeak-ng was built with libsonic, see [`crate::Speaker::sonic_available`].
+    pub const MAX: Self = Self(if cfg!(feature = "sonic") { 1000 } else { 450 });
+    /// The fastest rate espeak supports without libsonic, faster rates are capped to this.
+    pub const MAX_WITHOUT_SONIC: Self = Self(450);
+    /// The default rate of espeak voices.
+    pub const NORMAL: Self = Self(175);
+}
+
+parameter_newtype! {
+    /// A base pitch, between 0-100 inclusive.
+    Pitch(u8) => Pitch
+}
+
+parameter_newtype! {
+    /// A volume, between 0-200 inclusive, where values above 100 may distort.
+    Volume(u8) => Volume
+}
+
+/// A snapshot of the values of every user-facing [`Parameter`].
+///
+/// Created with [`crate::Speaker::snapshot_parameters`] and restored with [`crate::Speaker::apply_parameters`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParameterProfile {
+    values: [i32; Self::PARAMETERS.len()],
+}
+
+impl ParameterProfile {
+    /// The parameters captured in a profile, internal parameters are excluded.
+    pub const PARAMETERS: [Parameter; 11] = [
+        Parameter::Rate,
+        Parameter::Volume,
+        Parameter::Pitch,
+        Parameter::Range,
+        Parameter::Punctuation,
+        Parameter::Capitals,
+        Parameter::Wordgap,
+        Parameter::Options,
+        Parameter::Intonation,
+        Parameter::SsmlBreakMul,
+        Parameter::LineLength,
+    ];
+
+    pub(crate) fn from_fn(mut get_value: impl FnMut(Parameter) -> i32) -> Self {
+        Self {
+            values: Self::PARAMETERS.map(&mut get_value),
+        }
+    }
+
+    fn index_of(param: Parameter) -> Option<usize> {
+        Self::PARAMETERS.iter().position(|p| *p == param)
+    }
+
+    /// Get the stored value of a parameter, or [None] if the parameter is not captured in profiles.
+    #[must_use]
+    pub fn get(&self, param: Parameter) -> Option<i32> {
+        Self::index_of(param).map(|index| self.values[index])
+    }
+
+    /// Change the stored value of a parameter, returning the old value.
+    ///
+    /// Returns [None] and does nothing if the parameter is not captured in profiles.
+    pub fn set(&mut self, param: Parameter, value: i32) -> Option<i32> {
+        Self::index_of(param).map(|index| std::mem::replace(&mut self.values[index], value))
+    }
+
+    /// Iterate through each parameter and its stored value.
+    pub fn iter(&self) -> impl Iterator<Item = (Parameter, i32)> + '_ {
+        Self::PARAMETERS
+            .into_iter()
+            .zip(self.values.iter().copied())
+    }
+}
+
+/// How to pronounce capital letters, the value of [`Parameter::Capitals`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CapitalsMode {
+    /// Capital letters are not indicated.
+    #[default]
+    None,
+    /// A sound icon is played before each capitalised word.
+    SoundIcon,
+    /// Capitalised words are spelt out.
+    Spell,
+    /// The pitch of each capitalised word is raised by this many Hz, which must be at least 3.
+    RaisePitch(u16),
+}
+
+impl CapitalsMode {
+    /// The lowest value of [`CapitalsMode::RaisePitch`], as lower values select the other modes.
+    pub const MIN_RAISE_PITCH: u16 = 3;
+
+    /// Convert a raw [`Parameter::Capitals`] value into a mode.
+    #[must_use]
+    pub fn from_value(value: i32) -> Self {
+        match value {
+            i32::MIN..=0 => Self::None,
+            1 => Self::SoundIcon,
+            2 => Self::Spell,
+            hz => Self::RaisePitch(u16::try_from(hz).unwrap_or(u16::MAX)),
+        }
+    }
+
+    /// The raw [`Parameter::Capitals`] value of this mode.
+    #[must_use]
+    pub fn value(self) -> i32 {
+        match self {
+            Self::None => 0,
+            Self::SoundIcon => 1,
+            Self::Spell => 2,
+            Self::RaisePitch(hz) => i32::from(hz),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
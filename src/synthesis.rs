@@ -0,0 +1,148 @@
+use crate::{
+    backend::EspeakBackend, Pitch, RawEvent, Result, Speaker, SynthCallbackHandler, SynthFlags,
+    SynthesisOptions, SynthesisStats, TextMode, Voice, Volume, WordsPerMinute,
+};
+
+/// A builder for a single synthesis call, combining the options of the `synthesize_*` methods.
+///
+/// ```rust,no_run
+/// # fn main() -> espeakng::Result<()> {
+/// let mut speaker = espeakng::initialise_and_lock(Default::default())?;
+/// let result = espeakng::Synthesis::text("<speak>Hello <break/> world</speak>")
+///     .rate(180)
+///     .ssml(true)
+///     .events(true)
+///     .run(&mut speaker)?;
+///
+/// println!("{} samples, {} events", result.audio.len(), result.events.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Synthesis<'t> {
+    text: &'t str,
+    options: SynthesisOptions,
+    rate: Option<u16>,
+    pitch: Option<u8>,
+    volume: Option<u8>,
+    events: bool,
+}
+
+/// Collects the audio and events of a synthesis call, for [`Synthesis::events`].
+#[derive(Default)]
+struct EventCollector {
+    audio: Vec<i16>,
+    events: Vec<RawEvent>,
+}
+
+impl SynthCallbackHandler for EventCollector {
+    fn on_audio(&mut self, audio: &[i16]) {
+        self.audio.extend_from_slice(audio);
+    }
+
+    fn on_event(&mut self, event: RawEvent) {
+        self.events.push(event);
+    }
+}
+
+/// The output of [`Synthesis::run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SynthesisResult {
+    pub audio: Vec<i16>,
+    /// The events generated, only captured if enabled with [`Synthesis::events`].
+    pub events: Vec<RawEvent>,
+    pub stats: Option<SynthesisStats>,
+}
+
+impl<'t> Synthesis<'t> {
+    /// Start building a synthesis call of `text`.
+    pub fn text(text: &'t str) -> Self {
+        Self {
+            text,
+            options: SynthesisOptions::default(),
+            rate: None,
+            pitch: None,
+            volume: None,
+            events: false,
+        }
+    }
+
+    /// Speak with `voice` for this call only.
+    pub fn voice(mut self, voice: &Voice) -> Self {
+        self.options.voice = Some(voice.clone());
+        self
+    }
+
+    /// Speak at `words_per_minute` for this call only, checked when run.
+    pub fn rate(mut self, words_per_minute: u16) -> Self {
+        self.rate = Some(words_per_minute);
+        self
+    }
+
+    /// Speak with `pitch`, from 0 to 100, for this call only, checked when run.
+    pub fn pitch(mut self, pitch: u8) -> Self {
+        self.pitch = Some(pitch);
+        self
+    }
+
+    /// Speak with `volume`, from 0 to 200, for this call only, checked when run.
+    pub fn volume(mut self, volume: u8) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+
+    /// Interpret SSML markup in the text.
+    pub fn ssml(mut self, enabled: bool) -> Self {
+        self.options.flags.set(SynthFlags::Ssml, enabled);
+        self
+    }
+
+    /// Interpret phoneme input enclosed in `[[ ]]`.
+    pub fn phonemes(mut self, enabled: bool) -> Self {
+        self.options.flags.set(SynthFlags::Phonemes, enabled);
+        self
+    }
+
+    /// Set how the text is encoded, such as [`TextMode::EightBit`].
+    pub fn text_mode(mut self, text_mode: TextMode) -> Self {
+        self.options.text_mode = text_mode;
+        self
+    }
+
+    /// Capture the events generated during synthesis into [`SynthesisResult::events`].
+    pub fn events(mut self, enabled: bool) -> Self {
+        self.events = enabled;
+        self
+    }
+
+    /// Run the synthesis with `speaker`, restoring any overridden voice and parameters afterwards.
+    ///
+    /// # Errors
+    /// [`crate::Error::ParameterOutOfRange`] if the rate, pitch or volume is invalid, or if the
+    /// internal espeak synthesis fails, see [`crate::ESpeakNgError`].
+    pub fn run<B: EspeakBackend>(self, speaker: &mut Speaker<B>) -> Result<SynthesisResult> {
+        let options = SynthesisOptions {
+            rate: self.rate.map(WordsPerMinute::new).transpose()?,
+            pitch: self.pitch.map(Pitch::new).transpose()?,
+            volume: self.volume.map(Volume::new).transpose()?,
+            ..self.options
+        };
+
+        let (audio, events) = if self.events {
+            // Events are copied out within the callback, as their pointers are only valid there.
+            let mut collector = EventCollector::default();
+            speaker.synthesize_with_handler(self.text, &options, &mut collector)?;
+            (collector.audio, collector.events)
+        } else {
+            let audio = speaker.synthesize_with_options(self.text, &options)?;
+            (audio, Vec::new())
+        };
+
+        Ok(SynthesisResult {
+            audio,
+            events,
+            stats: speaker.stats(),
+        })
+    }
+}
@@ -0,0 +1,120 @@
+//! Pronunciation regression testing, such as to check dictionaries after recompiling them.
+//!
+//! ```no_run
+//! fn main() -> Result<(), espeakng::Error> {
+//!     let mut speaker = espeakng::initialise_and_lock(espeakng::InitOptions::default())?;
+//!     let cases = espeakng::testing::parse_cases("hello\thəlˈəʊ\nworld\twˈɜːld\n");
+//!
+//!     let report = espeakng::testing::run(&mut speaker, &cases, &espeakng::PhonemeGenOptions::Ipa {
+//!         text_mode: espeakng::TextMode::Utf8,
+//!         phoneme_mode: espeakng::PhonemeMode::empty(),
+//!     })?;
+//!
+//!     assert!(report.is_success(), "{report}");
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::{PhonemeGenOptions, Result, Speaker};
+
+/// A single text and the phonemes it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCase {
+    pub text: String,
+    pub expected: String,
+}
+
+/// A [`TestCase`] which produced different phonemes than expected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub text: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// The results of [`run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// The filename of the voice the cases were run against.
+    pub voice: Option<String>,
+    /// The number of cases which produced the expected phonemes.
+    pub passed: usize,
+    /// The cases which did not produce the expected phonemes, in order.
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl Report {
+    /// If every case produced the expected phonemes.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.passed + self.mismatches.len();
+        let voice = self.voice.as_deref().unwrap_or("unknown voice");
+        writeln!(f, "{}/{total} passed with {voice}", self.passed)?;
+
+        for mismatch in &self.mismatches {
+            writeln!(f, "{}:", mismatch.text)?;
+            writeln!(f, "  - {}", mismatch.expected)?;
+            writeln!(f, "  + {}", mismatch.actual)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse test cases from tab separated lines of text and expected phonemes.
+///
+/// Blank lines, lines starting with `//` and lines without a tab are skipped.
+#[must_use]
+pub fn parse_cases(source: &str) -> Vec<TestCase> {
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(text, expected)| TestCase {
+            text: text.trim().to_owned(),
+            expected: expected.trim().to_owned(),
+        })
+        .collect()
+}
+
+/// Run each case against the current voice, comparing the phonemes with surrounding whitespace trimmed.
+///
+/// # Errors
+/// See [`Speaker::text_to_clause_phonemes_batch`].
+pub fn run(
+    speaker: &mut Speaker,
+    cases: &[TestCase],
+    options: &PhonemeGenOptions<'_>,
+) -> Result<Report> {
+    let texts: Vec<&str> = cases.iter().map(|case| case.text.as_str()).collect();
+    let results = speaker.text_to_clause_phonemes_batch(&texts, options)?;
+
+    let mut report = Report {
+        voice: speaker.try_current_voice().map(|voice| voice.filename),
+        passed: 0,
+        mismatches: Vec::new(),
+    };
+
+    for (case, actual) in cases.iter().zip(results) {
+        let actual = actual.trim();
+        if actual == case.expected.trim() {
+            report.passed += 1;
+        } else {
+            report.mismatches.push(Mismatch {
+                text: case.text.clone(),
+                expected: case.expected.clone(),
+                actual: actual.to_owned(),
+            });
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,143 @@
+use std::{ops::Range, time::Duration};
+
+use crate::{phoneme::char_range_to_byte_range, utils, EventType, RawEvent};
+
+/// The position of a synthesized word within both the input text and the generated audio.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordTiming {
+    /// The byte range of the word within the text after preprocessing.
+    pub text_range: Range<usize>,
+    /// The character range of the word within the text after preprocessing.
+    pub char_range: Range<usize>,
+    /// The offset in the generated audio at which the word starts, in samples.
+    pub sample: usize,
+}
+
+/// The timing of a synthesis call, without the audio, from [`crate::Speaker::synthesize_timing`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SynthesisTiming {
+    /// The text the word positions refer to, after any preprocessing such as a text filter.
+    pub text: String,
+    pub words: Vec<WordTiming>,
+    /// The number of audio samples which were generated.
+    pub samples: usize,
+    /// The length of the generated audio when played.
+    pub duration: Duration,
+}
+
+/// Collect the word events of a synthesis call of `text`, in order.
+pub(crate) fn word_timings(text: &str, events: &[RawEvent], sample_rate: u32) -> Vec<WordTiming> {
+    let char_count = text.chars().count();
+
+    events
+        .iter()
+        .filter(|event| event.event_type == EventType::Word)
+        .map(|event| {
+            // espeak's positions are 1-based character offsets.
+            let start = usize::try_from(event.text_position - 1)
+                .unwrap_or(0)
+                .min(char_count);
+            let length = usize::try_from(event.length).unwrap_or(0);
+
+            WordTiming {
+                text_range: char_range_to_byte_range(text, event.text_position, event.length),
+                char_range: start..(start + length).min(char_count),
+                sample: utils::ms_to_samples(event.audio_position, sample_rate),
+            }
+        })
+        .collect()
+}
+
+/// A range of the input text paired with the range of audio speaking it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Highlight {
+    /// The byte range within [`Highlights::text`].
+    pub text_range: Range<usize>,
+    /// The range of samples within the generated audio.
+    pub audio_range: Range<usize>,
+}
+
+/// The words and sentences of an utterance with their audio, for spoken word highlighting.
+///
+/// Created by [`crate::Speaker::synthesize_with_highlights`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Highlights {
+    /// The text the ranges refer to, after any preprocessing such as a text filter.
+    pub text: String,
+    pub words: Vec<Highlight>,
+    /// The sentences, including any trailing punctuation.
+    pub sentences: Vec<Highlight>,
+}
+
+/// Pair the words and sentences of a synthesis call of `owned_text` with their audio.
+pub(crate) fn highlights(
+    owned_text: String,
+    events: &[RawEvent],
+    sample_rate: u32,
+    audio_len: usize,
+) -> Highlights {
+    let text = owned_text.as_str();
+    let mut highlights = Highlights::default();
+    let (mut word_open, mut sentence_open) = (false, false);
+
+    for event in events {
+        let sample = utils::ms_to_samples(event.audio_position, sample_rate).min(audio_len);
+        let is_sentence = event.event_type == EventType::Sentence;
+
+        // Words end at the next word, or the end of their clause, so exclude the following pause.
+        if word_open
+            && matches!(
+                event.event_type,
+                EventType::Word | EventType::Sentence | EventType::End | EventType::MsgTerminated
+            )
+        {
+            if let Some(word) = highlights.words.last_mut() {
+                word.audio_range.end = sample;
+            }
+            word_open = false;
+        }
+
+        if sentence_open && (is_sentence || event.event_type == EventType::MsgTerminated) {
+            if let Some(sentence) = highlights.sentences.last_mut() {
+                sentence.audio_range.end = sample;
+            }
+            sentence_open = false;
+        }
+
+        if event.event_type == EventType::Word {
+            highlights.words.push(Highlight {
+                text_range: char_range_to_byte_range(text, event.text_position, event.length),
+                audio_range: sample..audio_len,
+            });
+            word_open = true;
+        } else if is_sentence {
+            let start = char_range_to_byte_range(text, event.text_position, 0).start;
+            highlights.sentences.push(Highlight {
+                text_range: start..text.len(),
+                audio_range: sample..audio_len,
+            });
+            sentence_open = true;
+        }
+    }
+
+    // Sentence events only carry their start, so each sentence runs until the next one.
+    let sentence_starts: Vec<usize> = highlights
+        .sentences
+        .iter()
+        .map(|sentence| sentence.text_range.start)
+        .collect();
+
+    for (sentence, next_start) in highlights.sentences.iter_mut().zip(
+        sentence_starts
+            .into_iter()
+            .skip(1)
+            .chain(std::iter::once(text.len())),
+    ) {
+        let start = sentence.text_range.start;
+        let end = next_start.max(start);
+        sentence.text_range = start..start + text[start..end].trim_end().len();
+    }
+
+    highlights.text = owned_text;
+    highlights
+}
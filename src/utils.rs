@@ -1,10 +1,90 @@
-use std::ffi::CStr;
+use std::{
+    borrow::Cow,
+    ffi::{CStr, CString, NulError},
+    path::Path,
+    ptr::NonNull,
+};
 
-pub(crate) fn null_term(s: &str) -> Vec<libc::c_char> {
-    let mut nul_term_s: Vec<libc::c_char> = Vec::with_capacity(s.len());
-    nul_term_s.extend(s.as_bytes().iter().map(|i| *i as libc::c_char));
-    nul_term_s.push(0);
-    nul_term_s
+use crate::bindings;
+
+/// Convert text for espeak, rejecting interior NULs which would silently truncate it.
+pub(crate) fn null_term(s: &str) -> crate::Result<CString> {
+    null_term_bytes(s.as_bytes())
+}
+
+pub(crate) fn null_term_bytes(s: &[u8]) -> crate::Result<CString> {
+    CString::new(s).map_err(|err| interior_nul(err.nul_position()))
+}
+
+/// Convert text for espeak as [`null_term`] does, into `buffer` to reuse its allocation.
+pub(crate) fn null_term_into<'b>(buffer: &'b mut Vec<u8>, s: &str) -> crate::Result<&'b CStr> {
+    if let Some(position) = s.bytes().position(|byte| byte == 0) {
+        return Err(interior_nul(position));
+    }
+
+    buffer.clear();
+    buffer.extend_from_slice(s.as_bytes());
+    buffer.push(0);
+
+    // SAFETY: The buffer ends with the only NUL, as the text was checked for any above.
+    Ok(unsafe { CStr::from_bytes_with_nul_unchecked(buffer) })
+}
+
+fn interior_nul(position: usize) -> crate::Error {
+    crate::Error::InvalidInput(crate::InvalidInput::InteriorNul { position })
+}
+
+/// Reject NUL terminated wide text containing an interior NUL, which would silently truncate it.
+fn check_wide_nul(nul_term_chars: Vec<libc::wchar_t>) -> crate::Result<Vec<libc::wchar_t>> {
+    let text = &nul_term_chars[..nul_term_chars.len() - 1];
+    match text.iter().position(|c| *c == 0) {
+        Some(position) => Err(interior_nul(position)),
+        None => Ok(nul_term_chars),
+    }
+}
+
+pub(crate) fn path_to_cstring(path: &Path) -> Result<CString, NulError> {
+    CString::new(path.as_os_str().as_encoded_bytes())
+}
+
+/// Convert UTF-16 into native `wchar_t`s, which are UTF-16 on Windows and UTF-32 elsewhere.
+///
+/// Unpaired surrogates are replaced with U+FFFD when converting to UTF-32.
+pub(crate) fn null_term_utf16(text: &[u16]) -> crate::Result<Vec<libc::wchar_t>> {
+    let mut nul_term_chars: Vec<libc::wchar_t> = Vec::with_capacity(text.len() + 1);
+
+    #[cfg(windows)]
+    nul_term_chars.extend_from_slice(text);
+    #[cfg(not(windows))]
+    nul_term_chars.extend(
+        char::decode_utf16(text.iter().copied())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER) as libc::wchar_t),
+    );
+
+    nul_term_chars.push(0);
+    check_wide_nul(nul_term_chars)
+}
+
+/// Reinterpret native endian `wchar_t` bytes, such as from [`crate::TextMode::WideChar`] input.
+///
+/// Any trailing bytes which do not make up a full character are ignored.
+pub(crate) fn null_term_wide_bytes(bytes: &[u8]) -> crate::Result<Vec<libc::wchar_t>> {
+    let chunks = bytes.chunks_exact(std::mem::size_of::<libc::wchar_t>());
+
+    let mut nul_term_chars: Vec<libc::wchar_t> = Vec::with_capacity(chunks.len() + 1);
+    nul_term_chars.extend(chunks.map(|chunk| {
+        libc::wchar_t::from_ne_bytes(chunk.try_into().expect("chunk should be wchar_t sized"))
+    }));
+
+    nul_term_chars.push(0);
+    check_wide_nul(nul_term_chars)
+}
+
+pub(crate) fn null_term_wide(chars: &[char]) -> crate::Result<Vec<libc::wchar_t>> {
+    let mut nul_term_chars: Vec<libc::wchar_t> = Vec::with_capacity(chars.len() + 1);
+    nul_term_chars.extend(chars.iter().map(|c| *c as libc::wchar_t));
+    nul_term_chars.push(0);
+    check_wide_nul(nul_term_chars)
 }
 
 pub(crate) unsafe fn parse_lang_array(ptr: *const libc::c_char) -> Vec<crate::Language> {
@@ -40,6 +120,119 @@ pub(crate) unsafe fn parse_lang_array(ptr: *const libc::c_char) -> Vec<crate::La
     languages
 }
 
+/// Write audio samples as little endian bytes, without copying on little endian targets.
+pub(crate) fn write_samples_le(
+    writer: &mut impl std::io::Write,
+    samples: &[i16],
+) -> std::io::Result<()> {
+    if cfg!(target_endian = "little") {
+        return writer.write_all(bytemuck::cast_slice(samples));
+    }
+
+    let mut buffer = [0_u8; 4096];
+    for chunk in samples.chunks(buffer.len() / 2) {
+        let bytes = &mut buffer[..chunk.len() * 2];
+        for (sample, dest) in chunk.iter().zip(bytes.chunks_exact_mut(2)) {
+            dest.copy_from_slice(&sample.to_le_bytes());
+        }
+
+        writer.write_all(bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Iterate through an espeak event list, up to the terminate event.
+///
+/// # Safety
+/// `events` must point to a valid event list, which is not freed while iterating.
+pub(crate) unsafe fn event_list(
+    mut events: *const crate::bindings::espeak_EVENT,
+) -> impl Iterator<Item = crate::bindings::espeak_EVENT> {
+    std::iter::from_fn(move || {
+        let event = unsafe { *events };
+        if event.type_ == crate::bindings::espeak_EVENT_TYPE_espeakEVENT_LIST_TERMINATED {
+            return None;
+        }
+
+        events = unsafe { events.add(1) };
+        Some(event)
+    })
+}
+
+/// Run a libc function, capturing errno immediately after the call if `failed` reports it failed.
+pub(crate) fn libc_call<T>(
+    func: &'static str,
+    call: impl FnOnce() -> T,
+    failed: impl FnOnce(&T) -> bool,
+) -> Result<T, crate::Error> {
+    let value = call();
+    if failed(&value) {
+        Err(crate::Error::OtherC {
+            func,
+            errno: Some(errno::errno()),
+            context: None,
+        })
+    } else {
+        Ok(value)
+    }
+}
+
+/// An owned C `FILE` stream, closed on drop.
+pub(crate) struct CFile(NonNull<bindings::FILE>);
+
+impl CFile {
+    /// Open an anonymous temporary file, deleted once closed.
+    pub(crate) fn temporary() -> Result<Self, crate::Error> {
+        let file_ptr = libc_call(
+            "tmpfile",
+            || unsafe { libc::tmpfile() },
+            |ptr| ptr.is_null(),
+        )?;
+        Ok(Self(
+            NonNull::new(file_ptr.cast()).expect("checked for null"),
+        ))
+    }
+
+    pub(crate) fn as_ptr(&mut self) -> *mut bindings::FILE {
+        self.0.as_ptr()
+    }
+
+    /// Read the entire contents of the file, from the start.
+    pub(crate) fn read_to_end(&mut self) -> Result<Vec<u8>, crate::Error> {
+        let stream = self.as_ptr();
+        libc_call(
+            "fseek",
+            || unsafe { bindings::fseek(stream, 0, libc::SEEK_SET) },
+            |ret| *ret != 0,
+        )?;
+
+        let mut contents = Vec::new();
+        let mut buffer = [0_u8; 4096];
+        loop {
+            let read = libc_call(
+                "fread",
+                || unsafe {
+                    libc::fread(buffer.as_mut_ptr().cast(), 1, buffer.len(), stream.cast())
+                },
+                |read| *read == 0 && unsafe { libc::ferror(stream.cast()) } != 0,
+            )?;
+
+            if read == 0 {
+                return Ok(contents);
+            }
+
+            contents.extend_from_slice(&buffer[..read]);
+        }
+    }
+}
+
+impl Drop for CFile {
+    fn drop(&mut self) {
+        unsafe { bindings::fclose(self.as_ptr()) };
+    }
+}
+
 pub(crate) trait StringFromCPtr {
     unsafe fn from_cptr(ptr: *const libc::c_char) -> Self;
 }
@@ -51,3 +244,20 @@ impl StringFromCPtr for String {
             .into_owned()
     }
 }
+
+/// Convert an event's audio position in milliseconds into a sample offset.
+pub(crate) fn ms_to_samples(audio_position: i32, sample_rate: u32) -> usize {
+    let samples = u64::try_from(audio_position).unwrap_or(0) * u64::from(sample_rate) / 1000;
+    usize::try_from(samples).unwrap_or(usize::MAX)
+}
+
+/// Run a step of text preprocessing on text which may have been changed by an earlier step.
+pub(crate) fn then_filter(
+    text: Cow<'_, str>,
+    filter: impl FnOnce(&str) -> Cow<'_, str>,
+) -> Cow<'_, str> {
+    match text {
+        Cow::Borrowed(text) => filter(text),
+        Cow::Owned(text) => Cow::Owned(filter(&text).into_owned()),
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::mpsc;
+
+use parking_lot::Mutex;
+
+use crate::{Error, Result};
+
+/// The state of an utterance started by [`crate::Speaker::speak`], owned by the synth callback.
+pub(crate) struct AsyncUtterance {
+    pub(crate) audio: Mutex<Vec<i16>>,
+    finished: mpsc::SyncSender<Vec<i16>>,
+}
+
+impl AsyncUtterance {
+    pub(crate) fn new() -> (Self, Utterance) {
+        let (finished, receiver) = mpsc::sync_channel(1);
+        let async_utterance = Self {
+            audio: Mutex::new(Vec::new()),
+            finished,
+        };
+
+        (async_utterance, Utterance(receiver))
+    }
+
+    pub(crate) fn finish(self) {
+        // The Utterance may have been dropped if completion is not wanted.
+        let _ = self.finished.send(self.audio.into_inner());
+    }
+}
+
+/// An utterance started by [`crate::Speaker::speak`], which may still be in progress.
+pub struct Utterance(mpsc::Receiver<Vec<i16>>);
+
+impl Utterance {
+    /// Block until the utterance has finished, returning the generated audio data.
+    ///
+    /// The audio data is empty for the playback output modes.
+    ///
+    /// # Errors
+    /// [`Error::Cancelled`] if espeak stopped the utterance before it finished.
+    pub fn wait(self) -> Result<Vec<i16>> {
+        self.0.recv().map_err(|_| Error::Cancelled)
+    }
+
+    /// Return the generated audio data if the utterance has finished, without blocking.
+    ///
+    /// # Errors
+    /// [`Error::Cancelled`] if espeak stopped the utterance before it finished.
+    pub fn try_wait(&self) -> Result<Option<Vec<i16>>> {
+        match self.0.try_recv() {
+            Ok(audio) => Ok(Some(audio)),
+            Err(mpsc::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::TryRecvError::Disconnected) => Err(Error::Cancelled),
+        }
+    }
+}
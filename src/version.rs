@@ -0,0 +1,106 @@
+use std::{
+    fmt,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// A `major.minor.patch` version number, ordered so minimum versions can be required at runtime.
+///
+/// ```rust
+/// use espeakng::Version;
+///
+/// assert_eq!(Version::parse("1.52-dev"), Version::new(1, 52, 0));
+/// assert!(Version::parse("1.51.1") >= Version::new(1, 51, 0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    #[must_use]
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse the leading `major.minor.patch` of a version string, such as `1.51.1` or `1.52-dev`.
+    ///
+    /// Missing or unparsable components are treated as 0, so this never fails.
+    #[must_use]
+    pub fn parse(version: &str) -> Self {
+        let mut components = version.trim().split('.').map(|component| {
+            let digits = component
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(component, |end| &component[..end]);
+
+            digits.parse().unwrap_or(0)
+        });
+
+        let mut next = || components.next().unwrap_or(0);
+        Self::new(next(), next(), next())
+    }
+
+    /// Decode the version stored at the start of `phondata`, such as `0x014801` for 1.48.1.
+    fn from_phondata(version: u32) -> Self {
+        // Each byte stores two decimal digits.
+        let byte = |shift: u32| {
+            let byte = (version >> shift) & 0xFF;
+            (byte >> 4) * 10 + (byte & 0xF)
+        };
+
+        Self::new(byte(16), byte(8), byte(0))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Information about the internal C library, returned by [`crate::Speaker::info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Info {
+    /// The version of the library, parsed from `version_string`.
+    pub version: Version,
+    /// The version string reported by the library, which may include a suffix such as `-dev`.
+    pub version_string: String,
+    /// The path to the espeak-ng-data directory in use.
+    pub data_path: PathBuf,
+    /// The version of the compiled phoneme data format, or [`None`] if it could not be read.
+    pub data_version: Option<Version>,
+}
+
+impl Info {
+    pub(crate) fn new(version_string: String, data_path: PathBuf) -> Self {
+        let data_version = read_phondata_version(&data_path)
+            .ok()
+            .map(Version::from_phondata);
+
+        Self {
+            version: Version::parse(&version_string),
+            version_string,
+            data_path,
+            data_version,
+        }
+    }
+
+    /// If the library is at least `version`.
+    #[must_use]
+    pub fn is_at_least(&self, version: Version) -> bool {
+        self.version >= version
+    }
+}
+
+/// Read the version from the header of `phondata`, the first 4 bytes, without reading the rest.
+fn read_phondata_version(data_path: &Path) -> std::io::Result<u32> {
+    let mut header = [0; 4];
+    std::fs::File::open(data_path.join("phondata"))?.read_exact(&mut header)?;
+    Ok(u32::from_le_bytes(header))
+}
@@ -0,0 +1,213 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{Gender, Language, Result, Speaker, Voice};
+
+/// The adjustment of a single formant, from a `formant` line of a voice file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Formant {
+    /// The formant number, from 0 to 8.
+    pub index: u8,
+    /// The frequency, as a percentage of the default.
+    pub frequency: i32,
+    /// The strength, as a percentage of the default.
+    pub height: i32,
+    /// The bandwidth, as a percentage of the default.
+    pub width: i32,
+    /// A frequency in Hz added after scaling by `frequency`, if given.
+    pub frequency_add: Option<i32>,
+}
+
+/// The settings of an espeak voice definition file, such as `lang/gmw/en` or a variant in `voices/!v`.
+///
+/// Attributes which are not parsed into a field are kept in `other`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VoiceDetails {
+    pub name: Option<String>,
+    pub languages: Vec<Language>,
+    pub gender: Option<Gender>,
+    pub age: Option<u8>,
+    /// The base pitch and pitch range, in Hz.
+    pub pitch: Option<(i32, i32)>,
+    pub formants: Vec<Formant>,
+    /// The name of the dictionary used, such as `en` for `en_dict`.
+    pub dictionary: Option<String>,
+    /// The conditional rule numbers enabled in the dictionary.
+    pub dictrules: Vec<i32>,
+    /// The percentage to multiply the speaking rate by.
+    pub speed: Option<i32>,
+    pub flutter: Option<i32>,
+    pub roughness: Option<i32>,
+    /// Any other attributes, as the keyword followed by its arguments.
+    pub other: Vec<(String, Vec<String>)>,
+}
+
+impl VoiceDetails {
+    /// Parse the contents of a voice file. Comments, starting with `//`, and unknown lines are ignored.
+    #[must_use]
+    pub fn parse(source: &str) -> Self {
+        let mut details = Self::default();
+        for line in source.lines() {
+            let line = line.split("//").next().unwrap_or_default();
+            let mut words = line.split_whitespace();
+            let Some(keyword) = words.next() else {
+                continue;
+            };
+
+            let args: Vec<&str> = words.collect();
+            let int = |i: usize| args.get(i).and_then(|arg| arg.parse::<i32>().ok());
+
+            match keyword {
+                "name" => details.name = Some(args.join(" ")),
+                "language" => {
+                    if let Some(name) = args.first() {
+                        details.languages.push(Language {
+                            name: (*name).to_owned(),
+                            priority: args.get(1).and_then(|p| p.parse().ok()).unwrap_or(5),
+                        });
+                    }
+                }
+                "gender" => {
+                    details.gender = match args.first().copied() {
+                        Some("male") => Some(Gender::Male),
+                        Some("female") => Some(Gender::Female),
+                        _ => None,
+                    };
+                    details.age = args.get(1).and_then(|age| age.parse().ok());
+                }
+                "pitch" => details.pitch = int(0).zip(int(1)),
+                "formant" => {
+                    let index = args.first().and_then(|i| i.parse().ok());
+                    if let (Some(index), Some(frequency), Some(height), Some(width)) =
+                        (index, int(1), int(2), int(3))
+                    {
+                        details.formants.push(Formant {
+                            index,
+                            frequency,
+                            height,
+                            width,
+                            frequency_add: int(4),
+                        });
+                    }
+                }
+                "dictionary" => details.dictionary = args.first().map(|d| (*d).to_owned()),
+                "dictrules" => details
+                    .dictrules
+                    .extend(args.iter().filter_map(|r| r.parse::<i32>().ok())),
+                "speed" => details.speed = int(0),
+                "flutter" => details.flutter = int(0),
+                "roughness" => details.roughness = int(0),
+                _ => details.other.push((
+                    keyword.to_owned(),
+                    args.iter().map(|arg| (*arg).to_owned()).collect(),
+                )),
+            }
+        }
+
+        details
+    }
+
+    /// Read and parse a voice file.
+    ///
+    /// # Errors
+    /// If the file could not be read.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    /// The path of the file defining `voice`, in the current data directory.
+    #[must_use]
+    pub fn voice_path(voice: &Voice) -> PathBuf {
+        // Language voices are stored in `lang`, while mbrola voices and variants are in `voices`.
+        let directory = if voice.filename.starts_with("mb/") {
+            "voices"
+        } else {
+            "lang"
+        };
+
+        Speaker::info()
+            .data_path
+            .join(directory)
+            .join(&voice.filename)
+    }
+
+    /// Read and parse the file defining `voice`, see [`VoiceDetails::voice_path`].
+    ///
+    /// # Errors
+    /// If the file could not be read.
+    pub fn for_voice(voice: &Voice) -> Result<Self> {
+        Self::from_file(Self::voice_path(voice))
+    }
+}
+
+/// Writes the details in the voice file format, such that [`VoiceDetails::parse`] reads them back.
+impl fmt::Display for VoiceDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = &self.name {
+            writeln!(f, "name {name}")?;
+        }
+
+        for language in &self.languages {
+            writeln!(f, "language {} {}", language.name, language.priority)?;
+        }
+
+        if let Some(gender) = self.gender {
+            let gender = match gender {
+                Gender::Male => "male",
+                Gender::Female => "female",
+            };
+
+            match self.age {
+                Some(age) => writeln!(f, "gender {gender} {age}")?,
+                None => writeln!(f, "gender {gender}")?,
+            }
+        }
+
+        if let Some((base, range)) = self.pitch {
+            writeln!(f, "pitch {base} {range}")?;
+        }
+
+        for formant in &self.formants {
+            write!(
+                f,
+                "formant {} {} {} {}",
+                formant.index, formant.frequency, formant.height, formant.width
+            )?;
+
+            match formant.frequency_add {
+                Some(frequency_add) => writeln!(f, " {frequency_add}")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        if let Some(dictionary) = &self.dictionary {
+            writeln!(f, "dictionary {dictionary}")?;
+        }
+
+        if !self.dictrules.is_empty() {
+            write!(f, "dictrules")?;
+            for rule in &self.dictrules {
+                write!(f, " {rule}")?;
+            }
+            writeln!(f)?;
+        }
+
+        for (keyword, value) in [
+            ("speed", self.speed),
+            ("flutter", self.flutter),
+            ("roughness", self.roughness),
+        ] {
+            if let Some(value) = value {
+                writeln!(f, "{keyword} {value}")?;
+            }
+        }
+
+        for (keyword, args) in &self.other {
+            writeln!(f, "{keyword} {}", args.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
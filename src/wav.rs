@@ -0,0 +1,27 @@
+use std::io::Write;
+
+/// Write mono 16-bit PCM audio, as generated by [`crate::Speaker::synthesize`], as a WAV file.
+///
+/// # Errors
+/// If writing fails, or the audio is too long to fit in a WAV file.
+pub fn write_wav(writer: &mut impl Write, sample_rate: u32, audio: &[i16]) -> std::io::Result<()> {
+    let data_len = u32::try_from(audio.len() * 2)
+        .ok()
+        .filter(|len| *len <= u32::MAX - 36)
+        .ok_or_else(|| std::io::Error::other("audio too long for a WAV file"))?;
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_len).to_le_bytes())?;
+    writer.write_all(b"WAVEfmt ")?;
+    writer.write_all(&16_u32.to_le_bytes())?; // fmt chunk length
+    writer.write_all(&1_u16.to_le_bytes())?; // PCM
+    writer.write_all(&1_u16.to_le_bytes())?; // Mono
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&(sample_rate * 2).to_le_bytes())?; // Byte rate
+    writer.write_all(&2_u16.to_le_bytes())?; // Block align
+    writer.write_all(&16_u16.to_le_bytes())?; // Bits per sample
+    writer.write_all(b"data")?;
+    writer.write_all(&data_len.to_le_bytes())?;
+
+    crate::utils::write_samples_le(writer, audio)
+}
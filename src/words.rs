@@ -0,0 +1,151 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
+use crate::cache::StableHasher;
+
+/// The words masked by [`WordReplacements::mask_profanity`].
+#[rustfmt::skip]
+const PROFANITY: &[&str] = &[
+    "arse", "arsehole", "ass", "asshole", "bastard", "bitch", "bollocks", "bullshit", "cock",
+    "crap", "cunt", "damn", "dick", "fuck", "fucked", "fucker", "fucking", "motherfucker",
+    "piss", "prick", "shit", "shitty", "slut", "twat", "wank", "wanker", "whore",
+];
+
+/// Words to replace before synthesis, such as to fix the pronunciation of `GIF`, or to mask
+/// profanity, used by [`crate::Speaker::set_word_replacements`].
+///
+/// Words are matched case insensitively against whole words, which are runs of letters, digits
+/// and apostrophes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordReplacements {
+    replacements: HashMap<String, String>,
+    masked: HashSet<String>,
+    mask: String,
+}
+
+impl Default for WordReplacements {
+    fn default() -> Self {
+        Self {
+            replacements: HashMap::new(),
+            masked: HashSet::new(),
+            mask: String::from("bleep"),
+        }
+    }
+}
+
+impl<W: Into<String>, R: Into<String>> FromIterator<(W, R)> for WordReplacements {
+    fn from_iter<I: IntoIterator<Item = (W, R)>>(replacements: I) -> Self {
+        let mut words = Self::new();
+        words.extend(replacements);
+        words
+    }
+}
+
+impl<W: Into<String>, R: Into<String>> Extend<(W, R)> for WordReplacements {
+    fn extend<I: IntoIterator<Item = (W, R)>>(&mut self, replacements: I) {
+        for (word, replacement) in replacements {
+            self.insert(word, replacement);
+        }
+    }
+}
+
+impl WordReplacements {
+    /// An empty set of replacements, without masking.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace `word` with `replacement`, which may be multiple words.
+    pub fn insert(&mut self, word: impl Into<String>, replacement: impl Into<String>) {
+        let word: String = word.into();
+        self.replacements
+            .insert(word.to_lowercase(), replacement.into());
+    }
+
+    /// Stop replacing `word`, returning its previous replacement.
+    pub fn remove(&mut self, word: &str) -> Option<String> {
+        self.replacements.remove(&word.to_lowercase())
+    }
+
+    /// Replace `word` with the mask, see [`WordReplacements::set_mask`].
+    pub fn mask(&mut self, word: impl Into<String>) {
+        let word: String = word.into();
+        self.masked.insert(word.to_lowercase());
+    }
+
+    /// Mask a built in list of common English profanity, which can be extended with [`WordReplacements::mask`].
+    pub fn mask_profanity(&mut self) {
+        self.masked
+            .extend(PROFANITY.iter().map(|word| (*word).to_owned()));
+    }
+
+    /// Set the text masked words are replaced with, `bleep` by default, or an empty string to skip them.
+    pub fn set_mask(&mut self, mask: impl Into<String>) {
+        self.mask = mask.into();
+    }
+
+    /// The text `word` is replaced with, if any.
+    #[must_use]
+    pub fn get(&self, word: &str) -> Option<&str> {
+        let word = word.to_lowercase();
+        if let Some(replacement) = self.replacements.get(&word) {
+            Some(replacement)
+        } else if self.masked.contains(&word) {
+            Some(&self.mask)
+        } else {
+            None
+        }
+    }
+
+    /// Replace each word in `text`.
+    #[must_use]
+    pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.replacements.is_empty() && self.masked.is_empty() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut applied = String::new();
+        let mut copied = 0;
+        for (start, word) in words(text) {
+            if let Some(replacement) = self.get(word) {
+                applied.push_str(&text[copied..start]);
+                applied.push_str(replacement);
+                copied = start + word.len();
+            }
+        }
+
+        if copied == 0 {
+            return Cow::Borrowed(text);
+        }
+
+        applied.push_str(&text[copied..]);
+        Cow::Owned(applied)
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut StableHasher) {
+        hasher.write_map(&self.replacements);
+        hasher.write_set(&self.masked);
+        hasher.write_str(&self.mask);
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '\'' | '’')
+}
+
+/// The byte offset and text of each word.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        let start = position + text[position..].find(is_word_char)?;
+        let end = text[start..]
+            .find(|c: char| !is_word_char(c))
+            .map_or(text.len(), |len| start + len);
+
+        position = end;
+        Some((start, &text[start..end]))
+    })
+}
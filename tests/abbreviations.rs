@@ -0,0 +1,32 @@
+use espeakng::Abbreviations;
+
+#[test]
+fn defaults() {
+    let abbreviations = Abbreviations::for_language("en-us");
+    assert_eq!(
+        abbreviations.expand("Ask (Dr. Smith), at 50 km/h."),
+        "Ask (Doctor Smith), at 50 kilometres per hour."
+    );
+    assert_eq!(abbreviations.expand("Drive safely"), "Drive safely");
+
+    assert_eq!(
+        Abbreviations::for_language("de").expand("z.B. Hr. Müller"),
+        "zum Beispiel Herr Müller"
+    );
+    assert_eq!(Abbreviations::for_language("xx"), Abbreviations::new());
+}
+
+#[test]
+fn overrides() {
+    let mut abbreviations = Abbreviations::for_language("en");
+    abbreviations.insert("St.", "Street");
+    assert_eq!(
+        abbreviations.remove("mph").as_deref(),
+        Some("miles per hour")
+    );
+
+    assert_eq!(
+        abbreviations.expand("Baker St. at 30 mph"),
+        "Baker Street at 30 mph"
+    );
+}
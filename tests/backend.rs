@@ -0,0 +1,157 @@
+use espeakng::{
+    backend::EspeakBackend, ESpeakNgError, Error, Parameter, PhonemeGenOptions, PhonemeMode,
+    PhonemeTraceMode, Speaker, SynthCallbackHandler, SynthesisOptions, TextMode, Voice,
+    WordsPerMinute,
+};
+
+/// Stores parameters in memory, with espeak's default rate, and records the text it is given.
+struct FakeBackend {
+    parameters: Vec<(Parameter, i32)>,
+    texts: Vec<String>,
+}
+
+impl FakeBackend {
+    fn new() -> Self {
+        Self {
+            parameters: vec![(Parameter::Rate, 175)],
+            texts: Vec::new(),
+        }
+    }
+}
+
+impl EspeakBackend for FakeBackend {
+    fn get_parameter(&mut self, param: Parameter, _default: bool) -> i32 {
+        self.parameters
+            .iter()
+            .rev()
+            .find(|(p, _)| *p == param)
+            .map_or(0, |(_, value)| *value)
+    }
+
+    fn set_parameter(
+        &mut self,
+        param: Parameter,
+        value: i32,
+        relative: bool,
+    ) -> espeakng::Result<()> {
+        let value = if relative {
+            self.get_parameter(param, false) + value
+        } else {
+            value
+        };
+
+        self.parameters.push((param, value));
+        Ok(())
+    }
+
+    fn list_voices(&mut self) -> Vec<Voice> {
+        Vec::new()
+    }
+
+    fn current_voice(&self) -> Option<Voice> {
+        None
+    }
+
+    fn set_voice_by_name(&mut self, _filename: &str) -> espeakng::Result<()> {
+        Err(Error::ESpeakNg {
+            func: "espeak_ng_SetVoiceByName",
+            code: ESpeakNgError::VoiceNotFound,
+            context: None,
+        })
+    }
+
+    fn sample_rate(&self) -> u32 {
+        22050
+    }
+
+    /// Generates a sample per byte of text.
+    fn synthesize(
+        &mut self,
+        text: &[u8],
+        _options: &SynthesisOptions,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> espeakng::Result<()> {
+        self.texts.push(String::from_utf8_lossy(text).into_owned());
+        handler.on_audio(&vec![0; text.len()]);
+        Ok(())
+    }
+
+    fn synthesize_traced(
+        &mut self,
+        text: &[u8],
+        options: &SynthesisOptions,
+        _mode: PhonemeTraceMode,
+        handler: &mut dyn SynthCallbackHandler,
+    ) -> espeakng::Result<Vec<u8>> {
+        self.synthesize(text, options, handler)?;
+        Ok(Vec::new())
+    }
+
+    fn text_to_phonemes(
+        &mut self,
+        text: &str,
+        _text_mode: TextMode,
+        _phoneme_mode: u32,
+    ) -> espeakng::Result<String> {
+        self.texts.push(text.to_owned());
+        Ok(text.to_uppercase())
+    }
+
+    fn set_phoneme_events(&mut self, _enabled: bool, _ipa: bool) -> espeakng::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn parameters() -> espeakng::Result<()> {
+    let mut speaker = Speaker::with_backend(FakeBackend::new());
+    let snapshot = speaker.snapshot_parameters();
+
+    let rate = WordsPerMinute::new(350)?;
+    speaker.set_rate(rate)?;
+    assert_eq!(speaker.rate(), rate);
+
+    // Word gap units are halved in length at double the normal rate.
+    speaker.set_word_gap(std::time::Duration::from_millis(50))?;
+    assert_eq!(speaker.get_parameter(Parameter::Wordgap, false), 10);
+
+    assert!(speaker.set_range(101).is_err());
+
+    speaker.apply_parameters(&snapshot)?;
+    assert_eq!(speaker.snapshot_parameters(), snapshot);
+
+    Ok(())
+}
+
+#[test]
+fn voices() {
+    let mut speaker = Speaker::with_backend(FakeBackend::new());
+    assert!(speaker.voices().is_empty());
+
+    assert_eq!(speaker.try_current_voice(), None);
+    assert_eq!(speaker.sample_rate(), 22050);
+}
+
+#[test]
+fn preprocessing() -> espeakng::Result<()> {
+    let mut speaker = Speaker::with_backend(FakeBackend::new());
+    speaker.replace_words([("GIF", "jif")]);
+
+    let audio = speaker.synthesize("A GIF")?;
+    assert_eq!(audio.len(), "A jif".len());
+    assert_eq!(
+        speaker.stats().map(|stats| stats.samples),
+        Some(audio.len())
+    );
+
+    let phonemes = speaker.text_to_phonemes(
+        "A GIF",
+        PhonemeGenOptions::Standard {
+            text_mode: TextMode::Utf8,
+            phoneme_mode: PhonemeMode::default(),
+        },
+    )?;
+    assert_eq!(phonemes.as_deref(), Some("A JIF"));
+
+    Ok(())
+}
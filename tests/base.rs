@@ -1,3 +1,3 @@
 pub fn init<'a>() -> parking_lot::MutexGuard<'a, espeakng::Speaker> {
-    espeakng::initialise(None).unwrap().lock()
+    espeakng::initialise_and_lock(espeakng::InitOptions::default()).unwrap()
 }
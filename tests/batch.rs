@@ -0,0 +1,37 @@
+#![cfg(feature = "cli")]
+
+use espeakng::{batch::WorkerPool, Error, SynthesisOptions};
+
+#[test]
+fn worker_pool() -> espeakng::Result<()> {
+    let mut pool = WorkerPool::spawn(env!("CARGO_BIN_EXE_espeakng-cli"), 2)?;
+
+    let texts = ["Hello", "world", "Hello world"];
+    let results = pool.synthesize(&texts, &SynthesisOptions::default());
+    assert_eq!(results.len(), texts.len());
+    for result in results {
+        assert!(!result?.is_empty());
+    }
+
+    // The pool can be reused for another batch.
+    assert_eq!(
+        pool.synthesize(&["Goodbye"], &SynthesisOptions::default())
+            .len(),
+        1
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn stopped_worker() -> espeakng::Result<()> {
+    // `true worker` exits immediately, without answering any requests.
+    let mut pool = WorkerPool::spawn("true", 1)?;
+
+    let results = pool.synthesize(&["Hello", "world"], &SynthesisOptions::default());
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(Error::WorkerDisconnected))));
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+mod base;
+use base::init;
+
+#[test]
+fn missing_source_context() {
+    let source = std::env::temp_dir().join("espeakng_missing_intonations");
+    let output = std::env::temp_dir().join("espeakng_missing_intonations_output");
+
+    let err = init().compile_intonations(&source, &output).unwrap_err();
+    match err {
+        espeakng::Error::ESpeakNg { context, .. } | espeakng::Error::OtherC { context, .. } => {
+            assert!(context.is_some_and(|context| context.contains("espeakng_missing_intonations")));
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn language_pack_dictionaries() -> espeakng::Result<()> {
+    let dictsource = std::env::temp_dir().join("espeakng_langdata_dictsource");
+    std::fs::create_dir_all(&dictsource)?;
+    for file_name in ["fr_rules", "en_rules", "en_list", "en_extra"] {
+        std::fs::write(dictsource.join(file_name), "")?;
+    }
+
+    let output = std::env::temp_dir().join("espeakng_langdata_output");
+    let pack = espeakng::langdata::LanguagePack::from_dictsource(&dictsource, &output);
+    assert_eq!(
+        pack.clone().all_dictionaries()?,
+        pack.dictionary("en").dictionary("fr")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use espeakng::phoneme::convert::{convert, Alphabet};
+
+#[test]
+fn espeak_to_others() {
+    let espeak = include_str!("../test_data/hello_world.pho");
+
+    assert_eq!(
+        convert(espeak, Alphabet::Espeak, Alphabet::Ipa),
+        include_str!("../test_data/hello_world_ipa.pho")
+    );
+    assert_eq!(
+        convert(espeak, Alphabet::Espeak, Alphabet::XSampa),
+        "h@l\"oU w\"3:ld"
+    );
+    assert_eq!(
+        convert(espeak, Alphabet::Espeak, Alphabet::Kirshenbaum),
+        "h@l'oU w'V\":ld"
+    );
+}
+
+#[test]
+fn round_trip() {
+    let ipa = "ðɪs ɪz ɐ tˈɛst";
+    let xsampa = convert(ipa, Alphabet::Ipa, Alphabet::XSampa);
+    assert_eq!(convert(&xsampa, Alphabet::XSampa, Alphabet::Ipa), ipa);
+}
@@ -0,0 +1,47 @@
+use espeakng::data_path::{self, DataPathSource};
+
+#[test]
+fn explicit() -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join("espeakng_data_path_explicit");
+    let data_dir = dir.join("espeak-ng-data");
+    std::fs::create_dir_all(&data_dir)?;
+    std::fs::write(data_dir.join("phontab"), [])?;
+
+    let report = data_path::discover(Some(&dir));
+    assert_eq!(
+        report.found,
+        Some((data_dir.clone(), DataPathSource::Explicit))
+    );
+    assert_eq!(report.searched, [dir.clone(), data_dir]);
+
+    std::fs::remove_dir_all(dir)
+}
+
+#[test]
+fn missing() {
+    let dir = std::env::temp_dir().join("espeakng_data_path_missing");
+
+    let report = data_path::discover(Some(&dir));
+    assert!(report
+        .searched
+        .starts_with(&[dir.clone(), dir.join("espeak-ng-data")]));
+    assert!(report
+        .found
+        .is_none_or(|(_, source)| source != DataPathSource::Explicit));
+}
+
+#[test]
+fn configured_only() {
+    let dir = std::env::temp_dir().join("espeakng_data_path_configured");
+
+    // Guessed install locations are only used if espeak's compiled prefix fails.
+    let report = data_path::discover_configured(Some(&dir));
+    assert!(report.found.is_none_or(|(_, source)| matches!(
+        source,
+        DataPathSource::Explicit | DataPathSource::Environment
+    )));
+    assert!(!report
+        .searched
+        .iter()
+        .any(|path| path.starts_with("/usr/share")));
+}
@@ -0,0 +1,30 @@
+use espeakng::effects::{Chain, FadeIn, FadeOut, Gain, Processor, Speed};
+
+#[test]
+fn gain() {
+    assert_eq!(Gain(2.0).process(&[1, -2, i16::MAX]), [2, -4, i16::MAX]);
+}
+
+#[test]
+fn speed() {
+    assert_eq!(Speed(2.0).process(&[0, 1, 2, 3]), [0, 2]);
+    assert_eq!(Speed(0.5).process(&[0, 2]), [0, 1, 2, 2]);
+}
+
+#[test]
+fn fades() {
+    let audio = [100; 4];
+    assert_eq!(FadeIn { samples: 2 }.process(&audio), [0, 50, 100, 100]);
+    assert_eq!(FadeOut { samples: 2 }.process(&audio), [100, 100, 50, 0]);
+    assert_eq!(
+        FadeIn::from_duration(std::time::Duration::from_millis(10), 22050).samples,
+        221
+    );
+}
+
+#[test]
+fn chain() {
+    let mut chain = Chain::new().then(Gain(2.0)).then(Speed(2.0));
+    assert_eq!(chain.process(&[1, 2, 3, 4]), [2, 6]);
+    assert_eq!(Chain::new().process(&[1, 2]), [1, 2]);
+}
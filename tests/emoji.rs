@@ -0,0 +1,24 @@
+#![cfg(feature = "emoji")]
+
+use espeakng::EmojiNames;
+
+#[test]
+fn verbalize() {
+    let names = EmojiNames::new();
+    assert_eq!(names.verbalize("I love 🍕!"), "I love pizza !");
+    assert_eq!(names.verbalize("😀😀"), "grinning face grinning face");
+    assert_eq!(
+        names.verbalize("nice👍🏽"),
+        "nice thumbs up: medium skin tone"
+    );
+    assert_eq!(names.verbalize("no emoji 1 here"), "no emoji 1 here");
+}
+
+#[test]
+fn custom_names() {
+    let mut names = EmojiNames::empty();
+    names.insert("🍕", "Pizza");
+    names.insert(":)", "smiley");
+
+    assert_eq!(names.verbalize("🍕 :) 😀"), "Pizza smiley 😀");
+}
@@ -0,0 +1,39 @@
+use std::error::Error as _;
+
+#[test]
+fn source() {
+    let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+    let err = espeakng::Error::from(io_err);
+
+    assert!(matches!(err, espeakng::Error::Io(_)));
+    assert_eq!(err.source().unwrap().to_string(), "missing");
+
+    let err = espeakng::Error::from(String::from_utf8(vec![0xFF]).unwrap_err());
+    assert!(err.source().is_some());
+
+    assert!(espeakng::Error::LockTimeout.source().is_none());
+}
+
+#[test]
+fn status_codes() {
+    use espeakng::ESpeakNgError;
+
+    let err = ESpeakNgError::from_code(0x1000_06FF);
+    assert_eq!(err, ESpeakNgError::VoiceNotFound);
+    assert_eq!(err.code(), 0x1000_06FF);
+
+    let err = ESpeakNgError::from_code(0x1000_FFFF);
+    assert_eq!(err, ESpeakNgError::Unknown(0x1000_FFFF));
+    assert_eq!(err.code(), 0x1000_FFFF);
+}
+
+#[test]
+fn into_io_error() {
+    let err = std::io::Error::from(espeakng::Error::LockTimeout);
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+
+    let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+    let err = std::io::Error::from(espeakng::Error::from(io_err));
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(err.to_string(), "missing");
+}
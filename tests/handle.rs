@@ -0,0 +1,69 @@
+#[test]
+fn handle() -> espeakng::Result<()> {
+    let handle = espeakng::SpeakerHandle::new(espeakng::InitOptions::default())?;
+
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            let handle = handle.clone();
+            std::thread::spawn(move || handle.synthesize(format!("Thread {i}")))
+        })
+        .collect();
+
+    for thread in threads {
+        assert!(!thread.join().unwrap()?.is_empty());
+    }
+
+    let voice = handle.run(|speaker| speaker.get_current_voice())?;
+    assert_eq!(voice.filename, espeakng::Speaker::DEFAULT_VOICE);
+
+    Ok(())
+}
+
+#[test]
+fn priority() -> espeakng::Result<()> {
+    use espeakng::Priority;
+
+    let handle = espeakng::SpeakerHandle::new(espeakng::InitOptions::default())?;
+
+    // Block the worker, so the queue order can be observed.
+    let (unblock, blocked) = std::sync::mpsc::channel::<()>();
+    let blocker = handle.submit(move |_| blocked.recv().unwrap())?;
+
+    let order = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let mut results = Vec::new();
+    for priority in [Priority::Low, Priority::High, Priority::Normal] {
+        let order = std::sync::Arc::clone(&order);
+        results.push(handle.submit_with_priority(priority, move |_| order.lock().push(priority))?);
+    }
+
+    unblock.send(()).unwrap();
+    blocker.wait()?;
+    for result in results {
+        result.wait()?;
+    }
+
+    assert_eq!(
+        *order.lock(),
+        [Priority::High, Priority::Normal, Priority::Low]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn shared() -> espeakng::Result<()> {
+    let handle = espeakng::SpeakerHandle::new(espeakng::InitOptions::default())?;
+
+    // Handles alive at the same time share one background thread, so must agree on the options.
+    let second = espeakng::SpeakerHandle::new(espeakng::InitOptions::default())?;
+    assert!(!second.synthesize(String::from("Hello"))?.is_empty());
+
+    let options = espeakng::InitOptions::default().default_voice("de");
+    assert!(matches!(
+        espeakng::SpeakerHandle::new(options),
+        Err(espeakng::Error::AlreadyInit)
+    ));
+
+    drop(handle);
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use espeakng::{Lexicon, LexiconEntry};
+
+#[test]
+fn render() {
+    let mut lexicon = Lexicon::new("/nonexistent", "en");
+    lexicon
+        .add_phonemes("Gnomed", "n'oUmd")
+        .add_replacement("GIF", "jif")
+        .add_replacement("new york", "the big apple");
+
+    assert_eq!(lexicon.get("gif"), Some(&LexiconEntry::Text("jif".into())));
+    assert_eq!(
+        lexicon.to_string(),
+        "gif\t$text jif\ngnomed\tn'oUmd\n(new york)\t$text the big apple\n"
+    );
+}
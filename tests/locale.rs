@@ -0,0 +1,30 @@
+use espeakng::LocaleFormat;
+
+#[test]
+fn numbers() {
+    let german = LocaleFormat::for_language("de").unwrap();
+    assert_eq!(german.apply("1.234,56 €"), "1234 Komma 5 6 Euro");
+    assert_eq!(german.apply("Seite 42"), "Seite 42");
+
+    let english = LocaleFormat::for_language("en-us").unwrap();
+    assert_eq!(
+        english.apply("It cost $1,234.5, or 1 €."),
+        "It cost 1234 point 5 dollars, or 1 euro."
+    );
+    assert_eq!(english.apply("1, 2, 3 go"), "1, 2, 3 go");
+}
+
+#[test]
+fn dates() {
+    let british = LocaleFormat::for_language("en").unwrap();
+    let american = LocaleFormat::for_language("en-us").unwrap();
+    assert_eq!(british.apply("on 3/4/2024."), "on 3 April 2024.");
+    assert_eq!(american.apply("on 3/4/2024."), "on March 4, 2024.");
+    assert_eq!(american.apply("2024-04-03"), "April 3, 2024");
+
+    let spanish = LocaleFormat::for_language("es_ES").unwrap();
+    assert_eq!(spanish.apply("31/12/99"), "31 de diciembre de 99");
+    assert_eq!(spanish.apply("32/12/99"), "32/12/99");
+
+    assert!(LocaleFormat::for_language("xx").is_none());
+}
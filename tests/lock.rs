@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+#[test]
+fn timeout() -> espeakng::Result<()> {
+    let guard = espeakng::try_initialise_for(espeakng::InitOptions::default(), Duration::ZERO)?;
+
+    let result =
+        std::thread::spawn(|| espeakng::try_get_for(Duration::from_millis(10)).map(|_| ()))
+            .join()
+            .unwrap();
+    assert!(matches!(result, Err(espeakng::Error::LockTimeout)));
+
+    drop(guard);
+    espeakng::try_get_for(Duration::from_millis(10)).map(|_| ())
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "mock")]
+
+use espeakng::mock::{MockBackend, MockSpeaker};
+
+#[test]
+fn canned_output() -> espeakng::Result<()> {
+    let mut speaker = MockSpeaker::with_backend(
+        MockBackend::new()
+            .audio("Hello", vec![1, 2, 3])
+            .samples_per_word(10),
+    );
+
+    assert_eq!(speaker.synthesize("Hello")?, [1, 2, 3]);
+    assert_eq!(speaker.synthesize("Hello there world")?.len(), 30);
+    assert_eq!(
+        speaker.backend().synthesized(),
+        ["Hello", "Hello there world"]
+    );
+
+    let options = espeakng::PhonemeGenOptions::Standard {
+        text_mode: espeakng::TextMode::Utf8,
+        phoneme_mode: espeakng::PhonemeMode::empty(),
+    };
+    assert_eq!(
+        speaker.text_to_phonemes("Hi", options)?.as_deref(),
+        Some("hi")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parameters_and_voices() -> espeakng::Result<()> {
+    let mut speaker = MockSpeaker::with_backend(MockBackend::new());
+    assert_eq!(speaker.rate(), espeakng::WordsPerMinute::NORMAL);
+
+    speaker.set_pitch(espeakng::Pitch::new(80)?)?;
+    assert_eq!(speaker.pitch().get(), 80);
+    assert_eq!(speaker.get_parameter(espeakng::Parameter::Pitch, true), 50);
+
+    let voice = speaker.voices()[0].clone();
+    speaker.set_voice(&voice)?;
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    Ok(())
+}
+
+#[test]
+fn synthesis_cache() -> espeakng::Result<()> {
+    let mut speaker = MockSpeaker::with_backend(MockBackend::new());
+    speaker.set_synthesis_cache(Some(espeakng::SynthesisCache::new(1)));
+
+    let first = speaker.synthesize("Hello world")?;
+    assert_eq!(speaker.synthesize("Hello world")?, first);
+    assert_eq!(speaker.backend().synthesized(), ["Hello world"]);
+
+    // Changing a parameter misses the cache.
+    speaker.set_parameter(espeakng::Parameter::Rate, 300, false)?;
+    speaker.synthesize("Hello world")?;
+
+    // The capacity of 1 evicts the older result.
+    speaker.synthesize("Goodbye")?;
+    speaker.synthesize("Hello world")?;
+    assert_eq!(speaker.backend().synthesized().len(), 4);
+
+    let cache = speaker.synthesis_cache().unwrap();
+    assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 4, 1));
+    Ok(())
+}
+
+#[test]
+fn preprocessing() -> espeakng::Result<()> {
+    let mut speaker = MockSpeaker::with_backend(MockBackend::new());
+    speaker.replace_words([("GIF", "jif")]);
+
+    speaker.synthesize("A GIF")?;
+    assert_eq!(speaker.backend().synthesized(), ["A jif"]);
+
+    assert!(matches!(
+        speaker.text_to_phonemes("A GIF", espeakng::PhonemeGenOptions::Mbrola),
+        Err(espeakng::Error::MbrolaWithoutMbrolaVoice)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn clause_phonemes() -> espeakng::Result<()> {
+    let mut speaker =
+        MockSpeaker::with_backend(MockBackend::new().phonemes("Hello world", "h@l'oU w'3:ld"));
+
+    let options = espeakng::PhonemeGenOptions::Ipa {
+        text_mode: espeakng::TextMode::Utf8,
+        phoneme_mode: espeakng::PhonemeMode::empty(),
+    };
+
+    let clauses = speaker.text_to_phonemes_clauses("Hello world", &options)?;
+    assert_eq!(clauses.len(), 1);
+    assert_eq!(clauses[0].text_range, 0..11);
+    assert_eq!(clauses[0].phonemes, "h@l'oU w'3:ld");
+
+    assert_eq!(
+        speaker.text_to_clause_phonemes_batch(&["Hello world", "Hi"], &options)?,
+        ["h@l'oU w'3:ld", "hi"]
+    );
+
+    let utf16: Vec<u16> = "Hi".encode_utf16().collect();
+    assert_eq!(
+        speaker.text_to_phonemes_utf16(&utf16, options)?.as_deref(),
+        Some("hi")
+    );
+
+    Ok(())
+}
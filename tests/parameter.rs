@@ -13,3 +13,155 @@ fn set() {
         speaker.get_parameter(espeakng::Parameter::Volume, false)
     );
 }
+
+#[test]
+fn punctuation_list() {
+    let mut speaker = init();
+    speaker.set_punctuation_list(&['!', '?', '.']).unwrap();
+    speaker
+        .set_parameter(
+            espeakng::Parameter::Punctuation,
+            espeakng::PunctationType::Some as i32,
+            false,
+        )
+        .unwrap();
+}
+
+#[test]
+fn typed_setters() {
+    let mut speaker = init();
+    let rate = espeakng::WordsPerMinute::new(200).unwrap();
+    speaker.set_rate(rate).unwrap();
+    assert_eq!(speaker.get_parameter(espeakng::Parameter::Rate, false), 200);
+    assert_eq!(speaker.rate(), rate);
+
+    // Rates above 450 are only accepted with the sonic feature.
+    let too_fast: u16 = if cfg!(feature = "sonic") { 1001 } else { 451 };
+    assert!(matches!(
+        espeakng::WordsPerMinute::new(too_fast),
+        Err(espeakng::Error::ParameterOutOfRange {
+            param: espeakng::Parameter::Rate,
+            value,
+            ..
+        }) if value == i32::from(too_fast)
+    ));
+    assert!(espeakng::WordsPerMinute::new(too_fast - 1).is_ok());
+    assert!(espeakng::Pitch::new(101).is_err());
+    assert!(espeakng::Volume::try_from(50).is_ok());
+    assert!(espeakng::Volume::new(200).is_ok());
+    assert!(espeakng::Volume::new(201).is_err());
+}
+
+#[test]
+fn line_length() {
+    let mut speaker = init();
+    speaker
+        .set_parameter(espeakng::Parameter::LineLength, 40, false)
+        .unwrap();
+
+    assert_eq!(
+        speaker.get_parameter(espeakng::Parameter::LineLength, false),
+        40
+    );
+}
+
+#[test]
+fn snapshot_restore() {
+    let mut speaker = init();
+    let snapshot = speaker.snapshot_parameters();
+
+    speaker
+        .set_pitch(espeakng::Pitch::new(10).unwrap())
+        .unwrap();
+    assert_ne!(speaker.snapshot_parameters(), snapshot);
+
+    speaker.apply_parameters(&snapshot).unwrap();
+    assert_eq!(speaker.snapshot_parameters(), snapshot);
+}
+
+#[test]
+fn scoped() {
+    let mut speaker = init();
+    let rate = speaker.get_parameter(espeakng::Parameter::Rate, false);
+
+    let result = speaker.with_parameters(&[(espeakng::Parameter::Rate, 300)], |speaker| {
+        assert_eq!(speaker.get_parameter(espeakng::Parameter::Rate, false), 300);
+        speaker.synthesize("Hello world")
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(
+        speaker.get_parameter(espeakng::Parameter::Rate, false),
+        rate
+    );
+}
+
+#[test]
+fn capitals_mode() {
+    let mut speaker = init();
+    for mode in [
+        espeakng::CapitalsMode::Spell,
+        espeakng::CapitalsMode::RaisePitch(20),
+        espeakng::CapitalsMode::None,
+    ] {
+        speaker.set_capitals_mode(mode).unwrap();
+        assert_eq!(speaker.capitals_mode(), mode);
+    }
+
+    assert!(matches!(
+        speaker.set_capitals_mode(espeakng::CapitalsMode::RaisePitch(1)),
+        Err(espeakng::Error::ParameterOutOfRange {
+            param: espeakng::Parameter::Capitals,
+            value: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn word_gap() {
+    let mut speaker = init();
+    speaker
+        .set_rate(espeakng::WordsPerMinute::new(175).unwrap())
+        .unwrap();
+    speaker
+        .set_word_gap(std::time::Duration::from_millis(50))
+        .unwrap();
+
+    assert_eq!(
+        speaker.get_parameter(espeakng::Parameter::Wordgap, false),
+        5
+    );
+    assert_eq!(speaker.word_gap(), std::time::Duration::from_millis(50));
+
+    speaker.set_word_gap(std::time::Duration::ZERO).unwrap();
+}
+
+#[test]
+#[cfg(feature = "sonic")]
+fn sonic_rate() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let rate = espeakng::WordsPerMinute::new(800)?;
+
+    // The feature cannot link libsonic, so the rate is rejected if espeak-ng was built without it.
+    if !speaker.sonic_available() {
+        assert!(matches!(
+            speaker.set_rate(rate),
+            Err(espeakng::Error::ESpeakNg {
+                code: espeakng::ESpeakNgError::NotSupported,
+                ..
+            })
+        ));
+        return Ok(());
+    }
+
+    let normal = speaker.synthesize("Hello world, this is a test.")?;
+    speaker.set_rate(rate)?;
+    assert_eq!(speaker.rate(), rate);
+
+    let fast = speaker.synthesize("Hello world, this is a test.")?;
+    speaker.set_rate(espeakng::WordsPerMinute::NORMAL)?;
+
+    assert!(fast.len() < normal.len() / 2);
+    Ok(())
+}
@@ -21,6 +21,24 @@ fn espeak() -> Result<(), espeakng::Error> {
     Ok(())
 }
 
+#[test]
+fn ipa() -> Result<(), espeakng::Error> {
+    assert_eq!(
+        init()
+            .text_to_phonemes(
+                "Hello world",
+                espeakng::PhonemeGenOptions::Ipa {
+                    text_mode: TextMode::Utf8,
+                    phoneme_mode: PhonemeMode::empty()
+                }
+            )?
+            .unwrap(),
+        include_str!("../test_data/hello_world_ipa.pho")
+    );
+
+    Ok(())
+}
+
 #[test]
 fn mbrola() -> Result<(), espeakng::Error> {
     let mut speaker = init();
@@ -35,3 +53,208 @@ fn mbrola() -> Result<(), espeakng::Error> {
 
     Ok(())
 }
+
+#[test]
+fn mbrola_file() -> Result<(), espeakng::Error> {
+    let mut speaker = init();
+    speaker.set_voice_raw("mb/mb-en1")?;
+
+    let mut output = Vec::new();
+    let phonemes = speaker.text_to_phonemes(
+        "Hello world",
+        espeakng::PhonemeGenOptions::MbrolaFile(&mut output),
+    )?;
+
+    assert_eq!(phonemes, None);
+    assert_eq!(
+        String::from_utf8(output)?,
+        include_str!("../test_data/hello_world_mbrola.pho")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn aligned() -> Result<(), espeakng::Error> {
+    let text = "Hello world";
+    let (words, filtered_text) = init().text_to_phonemes_aligned(text)?;
+    assert_eq!(filtered_text, text);
+
+    let word_texts: Vec<&str> = words.iter().map(|w| &text[w.text_range.clone()]).collect();
+    assert_eq!(word_texts, ["Hello", "world"]);
+    assert!(words.iter().all(|w| !w.phonemes.is_empty()));
+
+    Ok(())
+}
+
+#[test]
+fn text_filter() -> Result<(), espeakng::Error> {
+    let mut speaker = init();
+    speaker.set_text_filter(|sentence| sentence.replace("Hi", "Hello").into());
+
+    let options = espeakng::PhonemeGenOptions::Standard {
+        text_mode: TextMode::Utf8,
+        phoneme_mode: PhonemeMode::empty(),
+    };
+    let phonemes = speaker.text_to_phonemes("Hi world", options);
+    speaker.clear_text_filter();
+
+    assert_eq!(
+        phonemes?.unwrap(),
+        include_str!("../test_data/hello_world.pho")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn synthesize_with_phonemes() -> Result<(), espeakng::Error> {
+    let mut speaker = init();
+    let audio = speaker.synthesize("Hello world")?;
+
+    let (traced_audio, phonemes) =
+        speaker.synthesize_with_phonemes("Hello world", espeakng::PhonemeTraceMode::Ipa)?;
+
+    assert_eq!(traced_audio, audio);
+    assert!(phonemes.contains("wˈɜːld"));
+
+    Ok(())
+}
+
+#[test]
+fn clauses() -> Result<(), espeakng::Error> {
+    let text = "Hello world, this is a test. Another sentence.";
+    let clauses = init().text_to_phonemes_clauses(
+        text,
+        &espeakng::PhonemeGenOptions::Ipa {
+            text_mode: espeakng::TextMode::default(),
+            phoneme_mode: espeakng::PhonemeMode::default(),
+        },
+    )?;
+
+    assert_eq!(clauses.len(), 3);
+    assert_eq!(clauses[0].text_range.start, 0);
+    assert_eq!(clauses.last().unwrap().text_range.end, text.len());
+    assert!(clauses[0].phonemes.starts_with("həlˈoʊ"));
+
+    Ok(())
+}
+
+#[test]
+fn batch() -> Result<(), espeakng::Error> {
+    let option = espeakng::PhonemeGenOptions::Ipa {
+        text_mode: espeakng::TextMode::default(),
+        phoneme_mode: espeakng::PhonemeMode::default(),
+    };
+
+    let phonemes =
+        init().text_to_clause_phonemes_batch(&["Hello world", "Hello world"], &option)?;
+
+    assert_eq!(phonemes.len(), 2);
+    assert!(phonemes
+        .iter()
+        .all(|p| p == include_str!("../test_data/hello_world_ipa.pho")));
+
+    Ok(())
+}
+
+#[test]
+fn set_phoneme_trace() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let (_, expected) =
+        speaker.synthesize_with_phonemes("Hello world", espeakng::PhonemeTraceMode::Ipa)?;
+
+    let mut tracer = speaker.set_phoneme_trace(espeakng::PhonemeTraceMode::Ipa, Vec::new())?;
+    tracer.synthesize("Hello world")?;
+    let trace = tracer.finish()?;
+
+    assert_eq!(String::from_utf8(trace)?, expected);
+    Ok(())
+}
+
+#[test]
+fn utf16() -> Result<(), espeakng::Error> {
+    let text: Vec<u16> = "Hello world".encode_utf16().collect();
+    assert_eq!(
+        init()
+            .text_to_phonemes_utf16(
+                &text,
+                espeakng::PhonemeGenOptions::Ipa {
+                    text_mode: TextMode::WideChar,
+                    phoneme_mode: PhonemeMode::empty()
+                }
+            )?
+            .unwrap(),
+        include_str!("../test_data/hello_world_ipa.pho")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rule_trace() -> Result<(), espeakng::Error> {
+    let words = init().text_to_rule_trace("Hello world")?;
+    assert_eq!(
+        words.iter().map(|w| w.word.as_str()).collect::<Vec<_>>(),
+        ["hello", "world"]
+    );
+    assert!(words.iter().all(|w| !w.phonemes.is_empty()));
+
+    Ok(())
+}
+
+#[test]
+fn rule_trace_entries() {
+    let words = espeakng::phoneme::trace::parse(
+        "Translate '42'\nFound: '_4X' [f'o@ti]\nFound: '_2' [t'u:]\n\nf'o@ti t'u:\n",
+    );
+
+    assert_eq!(words[0].word, "42");
+    assert_eq!(words[0].entries, ["_4X", "_2"]);
+}
+
+#[test]
+fn normalized_text() -> Result<(), espeakng::Error> {
+    use espeakng::phoneme::trace::NormalizedWord;
+
+    let mut speaker = init();
+    assert_eq!(
+        speaker.normalized_text("Hello   World")?,
+        [
+            NormalizedWord::Word("hello".into()),
+            NormalizedWord::Word("world".into())
+        ]
+    );
+
+    assert_eq!(
+        speaker.normalized_text("42")?,
+        [NormalizedWord::Word("forty two".into())]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn input_normalization() -> Result<(), espeakng::Error> {
+    let options = || espeakng::PhonemeGenOptions::Standard {
+        text_mode: TextMode::Utf8,
+        phoneme_mode: PhonemeMode::empty(),
+    };
+
+    let mut speaker = init();
+    speaker.set_input_normalization(
+        espeakng::Normalization::Nfc | espeakng::Normalization::StripInvisible,
+    );
+
+    assert_eq!(
+        speaker.text_to_phonemes("cafe\u{301}", options())?,
+        speaker.text_to_phonemes("café", options())?
+    );
+    assert_eq!(
+        speaker.text_to_phonemes("Hel\u{200B}lo", options())?,
+        speaker.text_to_phonemes("Hello", options())?
+    );
+
+    speaker.set_input_normalization(espeakng::Normalization::empty());
+    Ok(())
+}
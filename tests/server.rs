@@ -0,0 +1,97 @@
+#![cfg(feature = "server")]
+
+use std::io::{Read, Write};
+
+fn request(addr: std::net::SocketAddr, path: &str, body: &str) -> std::io::Result<String> {
+    raw_request(
+        addr,
+        &format!(
+            "POST {path} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        ),
+    )
+}
+
+fn raw_request(addr: std::net::SocketAddr, request: &str) -> std::io::Result<String> {
+    let mut stream = std::net::TcpStream::connect(addr)?;
+    stream.write_all(request.as_bytes())?;
+
+    // Audio responses are not UTF-8, but only the status line and headers are checked.
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    Ok(String::from_utf8_lossy(&response).into_owned())
+}
+
+/// The address of a server shared by every test, as there is only one [`espeakng::Speaker`].
+fn server_addr() -> std::net::SocketAddr {
+    static ADDR: std::sync::OnceLock<std::net::SocketAddr> = std::sync::OnceLock::new();
+    *ADDR.get_or_init(|| {
+        let handle = espeakng::SpeakerHandle::new(espeakng::InitOptions::default()).unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || runtime.block_on(espeakng::server::serve(listener, handle)));
+        addr
+    })
+}
+
+#[test]
+fn phonemes() -> Result<(), Box<dyn std::error::Error>> {
+    let addr = server_addr();
+
+    let response = request(addr, "/phonemes", "Hello world")?;
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.ends_with(include_str!("../test_data/hello_world_ipa.pho")));
+
+    let response = request(addr, "/unknown", "")?;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+    let response = raw_request(addr, "GET /phonemes HTTP/1.1\r\n\r\n")?;
+    assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed"));
+
+    let response = raw_request(
+        addr,
+        "POST /phonemes HTTP/1.1\r\nContent-Length: ten\r\n\r\n",
+    )?;
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"));
+
+    // Requests with too many headers are rejected before the body is read.
+    let headers: String = (0..100).map(|i| format!("X-Header-{i}: {i}\r\n")).collect();
+    let response = raw_request(addr, &format!("POST /phonemes HTTP/1.1\r\n{headers}\r\n"))?;
+    assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large"));
+
+    Ok(())
+}
+
+#[test]
+fn synthesize() -> Result<(), Box<dyn std::error::Error>> {
+    let addr = server_addr();
+
+    let response = request(addr, "/synthesize", "Hello world")?;
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("content-type: audio/wav"));
+    assert!(response.contains("RIFF"));
+
+    let body = "Hello world";
+    let response = raw_request(
+        addr,
+        &format!(
+            "POST /synthesize HTTP/1.1\r\nAccept: audio/ogg\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        ),
+    )?;
+
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("content-type: audio/ogg; codecs=opus"));
+    assert!(response.contains("OggS"));
+    assert!(response.contains("OpusHead"));
+
+    Ok(())
+}
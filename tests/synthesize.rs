@@ -0,0 +1,574 @@
+mod base;
+use base::init;
+use espeakng::TextMode;
+
+#[test]
+fn synthesize() -> espeakng::Result<()> {
+    assert!(!init().synthesize("Hello world")?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn eight_bit() -> espeakng::Result<()> {
+    let audio = init().synthesize_encoded(b"Caf\xe9", TextMode::EightBit)?;
+    assert!(!audio.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn phonemes() -> espeakng::Result<()> {
+    let audio = init().synthesize_phonemes(include_str!("../test_data/hello_world.pho"))?;
+    assert!(!audio.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn characters_and_keys() -> espeakng::Result<()> {
+    let mut speaker = init();
+    assert!(!speaker.speak_char('a')?.is_empty());
+    assert!(!speaker.speak_key_name("Enter")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn from_position() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let full = speaker.synthesize("Hello world, this is a test.")?;
+    let partial = speaker.synthesize_with_options(
+        "Hello world, this is a test.",
+        &espeakng::SynthesisOptions {
+            position: 3,
+            position_type: espeakng::PositionType::Word,
+            ..Default::default()
+        },
+    )?;
+
+    assert!(!partial.is_empty());
+    assert!(partial.len() < full.len());
+
+    Ok(())
+}
+
+#[test]
+fn ssml() -> espeakng::Result<()> {
+    let audio = init().synthesize_with_options(
+        "<speak>Hello <break time=\"500ms\"/> world</speak>",
+        &espeakng::SynthesisOptions {
+            flags: espeakng::SynthFlags::Ssml,
+            ..Default::default()
+        },
+    )?;
+
+    assert!(!audio.is_empty());
+    Ok(())
+}
+
+#[test]
+fn document() -> espeakng::Result<()> {
+    let document = "This is a sentence which will be repeated. ".repeat(100);
+    let options = espeakng::SynthesisOptions::default();
+
+    let mut speaker = init();
+    let chunks = speaker
+        .synthesize_document(&document, &options)
+        .collect::<espeakng::Result<Vec<_>>>()?;
+
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.first().unwrap().text_range.start, 0);
+    assert_eq!(chunks.last().unwrap().text_range.end, document.len());
+    assert!(chunks
+        .windows(2)
+        .all(|w| w[0].text_range.end == w[1].text_range.start));
+    assert!(chunks.iter().all(|c| !c.audio.is_empty()));
+
+    Ok(())
+}
+
+#[test]
+fn with_speaker() -> espeakng::Result<()> {
+    let audio = espeakng::with_speaker(|speaker| speaker.synthesize("Hello world"))?;
+    assert!(!audio.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn speak() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let sync_audio = speaker.speak("Hello world")?.wait()?;
+    assert!(!sync_audio.is_empty());
+
+    let options = espeakng::InitOptions::default().output_mode(espeakng::OutputMode::Retrieval);
+    speaker.reinitialise(options)?;
+
+    let utterance = speaker.speak("Hello world");
+    let async_audio = utterance.and_then(espeakng::Utterance::wait);
+    speaker.reinitialise(espeakng::InitOptions::default())?;
+
+    assert_eq!(async_audio?, sync_audio);
+    Ok(())
+}
+
+#[test]
+fn is_speaking() -> espeakng::Result<()> {
+    let mut speaker = init();
+    speaker.synthesize("Hello world")?;
+    assert!(!speaker.is_speaking());
+
+    Ok(())
+}
+
+#[test]
+fn synchronize() -> espeakng::Result<()> {
+    let mut speaker = init();
+    speaker.speak("Hello world")?;
+    speaker.synchronize()?;
+    assert!(!speaker.is_speaking());
+
+    Ok(())
+}
+
+#[test]
+fn stats() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let audio = speaker.synthesize("Hello world")?;
+
+    let stats = speaker.stats().unwrap();
+    assert_eq!(stats.samples, audio.len());
+    assert!(stats.audio_duration > std::time::Duration::ZERO);
+    assert!(stats.real_time_factor().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn synthesize_into() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let audio = speaker.synthesize("Hello world")?;
+
+    let mut output = Vec::new();
+    speaker.synthesize_into("Hello world", &mut output)?;
+    assert_eq!(output, audio);
+
+    speaker.synthesize_into("Hello world", &mut output)?;
+    assert_eq!(output.len(), audio.len() * 2);
+
+    Ok(())
+}
+
+#[test]
+fn synthesize_to_sink() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let audio = speaker.synthesize("Hello world")?;
+
+    let mut sunk = Vec::new();
+    speaker.synthesize_to_sink(
+        "Hello world",
+        &espeakng::SynthesisOptions::default(),
+        |chunk| sunk.extend_from_slice(chunk),
+    )?;
+
+    assert_eq!(sunk, audio);
+    Ok(())
+}
+
+#[test]
+fn synthesize_with_handler() -> espeakng::Result<()> {
+    #[derive(Default)]
+    struct Handler {
+        audio: Vec<i16>,
+        words: usize,
+        terminated: bool,
+    }
+
+    impl espeakng::SynthCallbackHandler for Handler {
+        fn on_audio(&mut self, audio: &[i16]) {
+            self.audio.extend_from_slice(audio);
+        }
+
+        fn on_event(&mut self, event: espeakng::RawEvent) {
+            match event.event_type {
+                espeakng::EventType::Word => self.words += 1,
+                espeakng::EventType::MsgTerminated => self.terminated = true,
+                _ => {}
+            }
+        }
+    }
+
+    let mut speaker = init();
+    let audio = speaker.synthesize("Hello world")?;
+
+    let mut handler = Handler::default();
+    speaker.synthesize_with_handler(
+        "Hello world",
+        &espeakng::SynthesisOptions::default(),
+        &mut handler,
+    )?;
+
+    assert_eq!(handler.audio, audio);
+    assert_eq!(handler.words, 2);
+    assert!(handler.terminated);
+    Ok(())
+}
+
+#[test]
+fn segments() -> espeakng::Result<()> {
+    use espeakng::Segment;
+
+    let segments = [
+        Segment::Text("Hello & <goodbye>"),
+        Segment::Pause(std::time::Duration::from_millis(500)),
+        Segment::Text("world"),
+    ];
+
+    assert_eq!(
+        Segment::to_ssml(&segments),
+        "<speak>Hello &amp; &lt;goodbye&gt;<break time=\"500ms\"/>world</speak>"
+    );
+
+    let mut speaker = init();
+    let without_pause = speaker.synthesize_segments(&[segments[0], segments[2]])?;
+    let with_pause = speaker.synthesize_segments(&segments)?;
+    assert!(with_pause.len() > without_pause.len());
+
+    Ok(())
+}
+
+#[test]
+fn utf16() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let text: Vec<u16> = "Hello world".encode_utf16().collect();
+
+    assert_eq!(
+        speaker.synthesize_utf16(&text)?,
+        speaker.synthesize("Hello world")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn from_reader() -> espeakng::Result<()> {
+    let text = "Hello world.\n\nThis is a test.\n";
+    let options = espeakng::SynthesisOptions::default();
+
+    let mut speaker = init();
+    let chunks = speaker
+        .synthesize_from_reader(text.as_bytes(), &options)
+        .collect::<espeakng::Result<Vec<_>>>()?;
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0], speaker.synthesize("Hello world.\n")?);
+
+    Ok(())
+}
+
+#[test]
+fn partial() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let outcome = speaker.synthesize_partial("Hello world", &espeakng::SynthesisOptions::default());
+
+    assert!(outcome.is_complete());
+    assert_eq!(outcome.into_result()?, speaker.synthesize("Hello world")?);
+
+    Ok(())
+}
+
+#[test]
+fn say_as() -> espeakng::Result<()> {
+    use espeakng::{SayAs, Segment};
+
+    assert_eq!(
+        Segment::to_ssml(&[
+            Segment::SayAs("1984", SayAs::Year),
+            Segment::SayAs("2005", SayAs::Year),
+            Segment::SayAs("112", SayAs::Ordinal),
+            Segment::SayAs("22", SayAs::Ordinal),
+        ]),
+        "<speak>19 842005112th22nd</speak>"
+    );
+
+    let mut speaker = init();
+    let number = speaker.synthesize("5551234")?;
+    let digits = speaker.synthesize_segments(&[Segment::SayAs("5551234", SayAs::Digits)])?;
+    assert_ne!(number, digits);
+
+    Ok(())
+}
+
+#[test]
+fn spell() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let a = speaker.speak_char('a')?;
+    let b = speaker.speak_char('b')?;
+
+    let gap = std::time::Duration::from_millis(100);
+    let spelt = speaker.spell("ab", gap)?;
+    assert_eq!(spelt.len(), a.len() + b.len() + 2205);
+    assert!(spelt.starts_with(&a) && spelt.ends_with(&b));
+
+    Ok(())
+}
+
+#[test]
+fn voice_changes() -> espeakng::Result<()> {
+    let ssml =
+        "<speak>Hello there. <voice xml:lang=\"fr\" gender=\"female\">Bonjour tout le monde.\
+        </voice> Goodbye.</speak>";
+
+    let (audio, changes) = init().synthesize_ssml_with_voice_changes(ssml)?;
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].text_position, ssml.find("<voice").unwrap());
+    assert_eq!(changes[0].selection.language.as_deref(), Some("fr"));
+    assert_eq!(changes[0].selection.gender.as_deref(), Some("female"));
+
+    assert_eq!(changes[1].text_position, ssml.find("</voice>").unwrap());
+    assert_eq!(changes[1].selection, espeakng::VoiceSelection::default());
+
+    assert!(0 < changes[0].sample);
+    assert!(changes[0].sample < changes[1].sample);
+    assert!(changes[1].sample < audio.len());
+
+    Ok(())
+}
+
+#[test]
+fn word_timings() -> espeakng::Result<()> {
+    let text = "Héllo wörld, goodbye";
+    let (audio, timings, filtered_text) = init().synthesize_with_word_timings(text)?;
+    assert_eq!(filtered_text, text);
+
+    let words: Vec<&str> = timings
+        .iter()
+        .map(|timing| &text[timing.text_range.clone()])
+        .collect();
+    assert_eq!(words, ["Héllo", "wörld", "goodbye"]);
+
+    assert_eq!(timings[1].char_range, 6..11);
+    assert!(timings.windows(2).all(|w| w[0].sample < w[1].sample));
+    assert!(timings[2].sample < audio.len());
+
+    Ok(())
+}
+
+#[test]
+fn filtered_word_timings() -> espeakng::Result<()> {
+    let mut speaker = init();
+    speaker.replace_words([("GIF", "graphics interchange format")]);
+
+    let result = speaker.synthesize_with_word_timings("A GIF file.");
+    speaker.set_word_replacements(None);
+    let (_, timings, filtered_text) = result?;
+
+    assert_eq!(filtered_text, "A graphics interchange format file.");
+    let words: Vec<&str> = timings
+        .iter()
+        .map(|timing| &filtered_text[timing.text_range.clone()])
+        .collect();
+    assert_eq!(words, ["A", "graphics", "interchange", "format", "file"]);
+
+    Ok(())
+}
+
+#[test]
+fn highlights() -> espeakng::Result<()> {
+    let text = "Hello world. How are you?";
+    let (audio, highlights) = init().synthesize_with_highlights(text)?;
+    assert_eq!(highlights.text, text);
+
+    let sentences: Vec<&str> = highlights
+        .sentences
+        .iter()
+        .map(|sentence| &text[sentence.text_range.clone()])
+        .collect();
+    assert_eq!(sentences, ["Hello world.", "How are you?"]);
+    assert_eq!(highlights.words.len(), 5);
+
+    for highlight in highlights.words.iter().chain(&highlights.sentences) {
+        assert!(highlight.audio_range.start < highlight.audio_range.end);
+        assert!(highlight.audio_range.end <= audio.len());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn filtered_highlights() -> espeakng::Result<()> {
+    let mut speaker = init();
+    speaker.replace_words([("GIF", "graphics interchange format")]);
+
+    let result = speaker.synthesize_with_highlights("A GIF file.");
+    speaker.set_word_replacements(None);
+    let (_, highlights) = result?;
+
+    assert_eq!(highlights.text, "A graphics interchange format file.");
+    let words: Vec<&str> = highlights
+        .words
+        .iter()
+        .map(|word| &highlights.text[word.text_range.clone()])
+        .collect();
+    assert_eq!(words, ["A", "graphics", "interchange", "format", "file"]);
+
+    Ok(())
+}
+
+#[test]
+fn timing_only() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let text = "Hello world, how are you?";
+
+    let (audio, word_timings, filtered_text) = speaker.synthesize_with_word_timings(text)?;
+    let timing = speaker.synthesize_timing(text)?;
+
+    assert_eq!(timing.text, filtered_text);
+    assert_eq!(timing.samples, audio.len());
+    assert_eq!(timing.words, word_timings);
+    assert!(!timing.duration.is_zero());
+
+    Ok(())
+}
+
+#[test]
+fn option_overrides() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let voices = espeakng::Speaker::get_voices();
+    let voice = voices.iter().find(|v| v.filename == "roa/fr").unwrap();
+
+    let text = "Hello world, this is a test.";
+    let rate = speaker.rate();
+    let normal = speaker.synthesize(text)?;
+    let fast = speaker.synthesize_with_options(
+        text,
+        &espeakng::SynthesisOptions {
+            rate: Some(espeakng::WordsPerMinute::new(400)?),
+            pitch: Some(espeakng::Pitch::new(80)?),
+            voice: Some(voice.clone()),
+            ..espeakng::SynthesisOptions::default()
+        },
+    )?;
+
+    assert!(fast.len() < normal.len());
+    assert_eq!(speaker.rate(), rate);
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    Ok(())
+}
+
+#[test]
+fn builder() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let result = espeakng::Synthesis::text("<speak>Hello <mark name=\"here\"/> world</speak>")
+        .rate(180)
+        .ssml(true)
+        .events(true)
+        .run(&mut speaker)?;
+
+    assert!(!result.audio.is_empty());
+    assert!(result
+        .events
+        .iter()
+        .any(|event| event.id == espeakng::EventId::Name("here".into())));
+    assert_eq!(result.stats.unwrap().samples, result.audio.len());
+
+    assert!(matches!(
+        espeakng::Synthesis::text("Hello")
+            .pitch(200)
+            .run(&mut speaker),
+        Err(espeakng::Error::ParameterOutOfRange { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_input() {
+    use espeakng::{Error, InvalidInput};
+
+    let mut speaker = init();
+    assert!(matches!(
+        speaker.synthesize("Hello\0world"),
+        Err(Error::InvalidInput(InvalidInput::InteriorNul {
+            position: 5
+        }))
+    ));
+
+    let utf16: Vec<u16> = "Hi\0".encode_utf16().collect();
+    assert!(matches!(
+        speaker.synthesize_utf16(&utf16),
+        Err(Error::InvalidInput(InvalidInput::InteriorNul {
+            position: 2
+        }))
+    ));
+
+    let options = espeakng::PhonemeGenOptions::Standard {
+        text_mode: TextMode::Utf8,
+        phoneme_mode: espeakng::PhonemeMode::empty(),
+    };
+    assert!(matches!(
+        speaker.text_to_phonemes("Hello\0", options),
+        Err(Error::InvalidInput(InvalidInput::InteriorNul { .. }))
+    ));
+
+    assert!(matches!(
+        speaker.synthesize(""),
+        Err(Error::InvalidInput(InvalidInput::Empty))
+    ));
+
+    speaker.set_max_input_length(Some(5));
+    let too_long = speaker.synthesize("Hello world");
+    speaker.set_max_input_length(None);
+
+    assert!(matches!(
+        too_long,
+        Err(Error::InvalidInput(InvalidInput::TooLong {
+            length: 11,
+            limit: 5
+        }))
+    ));
+}
+
+#[test]
+fn disk_cache() -> espeakng::Result<()> {
+    let directory = std::env::temp_dir().join("espeakng_disk_cache");
+    let mut speaker = init();
+
+    let mut disk_cache = espeakng::DiskCache::open(&directory)?;
+    disk_cache.clear()?;
+    speaker.set_disk_cache(Some(disk_cache));
+
+    let audio = speaker.synthesize("Hello world")?;
+    let size = speaker.disk_cache_mut().unwrap().size();
+    assert!(size > 0);
+
+    // Reopening the directory, as after a restart, reuses the stored audio.
+    speaker.set_disk_cache(Some(espeakng::DiskCache::open(&directory)?));
+    assert_eq!(speaker.synthesize("Hello world")?, audio);
+    assert_eq!(speaker.disk_cache_mut().unwrap().size(), size);
+
+    // Changing preprocessing misses the stored audio, rather than returning it stale.
+    speaker.replace_words([("Hello", "Goodbye")]);
+    let replaced = speaker.synthesize("Hello world");
+    speaker.set_word_replacements(None);
+    assert_ne!(replaced?, audio);
+
+    // Returning to the same configuration reuses the stored audio, as it would in another run.
+    let size = speaker.disk_cache_mut().unwrap().size();
+    assert_eq!(speaker.synthesize("Hello world")?, audio);
+    assert_eq!(speaker.disk_cache_mut().unwrap().size(), size);
+
+    // Text filters cannot be compared between runs, so their output is not stored.
+    speaker.set_text_filter(|sentence| sentence.replace("Hello", "Goodbye").into());
+    speaker.synthesize("Hello world")?;
+    speaker.clear_text_filter();
+    assert_eq!(speaker.disk_cache_mut().unwrap().size(), size);
+
+    speaker.set_disk_cache(None);
+    Ok(())
+}
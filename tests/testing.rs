@@ -0,0 +1,51 @@
+mod base;
+use base::init;
+use espeakng::testing::{parse_cases, run, TestCase};
+
+#[test]
+fn parse() {
+    let cases = parse_cases("// comment\nhello\thəlˈəʊ\n\nno tab\nworld \t wˈɜːld\n");
+    assert_eq!(
+        cases,
+        [
+            TestCase {
+                text: String::from("hello"),
+                expected: String::from("həlˈəʊ"),
+            },
+            TestCase {
+                text: String::from("world"),
+                expected: String::from("wˈɜːld"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn report() -> espeakng::Result<()> {
+    let cases = [
+        TestCase {
+            text: String::from("Hello world"),
+            expected: String::from(include_str!("../test_data/hello_world_ipa.pho")),
+        },
+        TestCase {
+            text: String::from("Hello world"),
+            expected: String::from("wrong"),
+        },
+    ];
+
+    let report = run(
+        &mut init(),
+        &cases,
+        &espeakng::PhonemeGenOptions::Ipa {
+            text_mode: espeakng::TextMode::Utf8,
+            phoneme_mode: espeakng::PhonemeMode::empty(),
+        },
+    )?;
+
+    assert!(!report.is_success());
+    assert_eq!(report.passed, 1);
+    assert_eq!(report.mismatches[0].expected, "wrong");
+    assert!(report.to_string().starts_with("1/2 passed"));
+
+    Ok(())
+}
@@ -10,3 +10,267 @@ fn get_voice() -> espeakng::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn sample_rate() {
+    assert_eq!(init().sample_rate(), 22050);
+}
+
+#[test]
+fn reinitialise() -> espeakng::Result<()> {
+    let mut speaker = init();
+    speaker.set_voice_raw("gmw/en-US")?;
+
+    speaker.reinitialise(espeakng::InitOptions::new().buffer_length_ms(100))?;
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    speaker.reinitialise(espeakng::InitOptions::default())
+}
+
+#[test]
+fn voice_not_found() {
+    let err = init().set_voice_raw("not/a-voice").unwrap_err();
+    assert!(matches!(
+        err,
+        espeakng::Error::ESpeakNg {
+            func: "espeak_ng_SetVoiceByName",
+            code: espeakng::ESpeakNgError::VoiceNotFound,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn cached_voices() {
+    let mut speaker = init();
+    let voices = espeakng::Speaker::get_voices();
+
+    assert_eq!(speaker.voices(), voices);
+    assert_eq!(speaker.refresh_voices(), voices);
+}
+
+#[test]
+#[cfg(feature = "detect-language")]
+fn synthesize_auto() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let text = "Bonjour tout le monde, comment allez-vous aujourd'hui ?";
+
+    let voice = speaker.detect_voice(text).unwrap();
+    assert!(voice.languages.iter().any(|l| l.name.starts_with("fr")));
+
+    assert!(!speaker.synthesize_auto(text)?.is_empty());
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    Ok(())
+}
+
+#[test]
+fn with_voice() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let voices = espeakng::Speaker::get_voices();
+    let voice = voices.iter().find(|v| v.filename == "gmw/en-US").unwrap();
+
+    assert!(!speaker
+        .synthesize_with_voice(voice, "Hello world")?
+        .is_empty());
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    let err = speaker.with_voice(voice, |speaker| {
+        assert_eq!(speaker.get_current_voice().filename, voice.filename);
+        Err::<(), _>(espeakng::Error::Cancelled)
+    });
+
+    assert!(matches!(err, Err(espeakng::Error::Cancelled)));
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    Ok(())
+}
+
+#[test]
+fn try_current_voice() {
+    let speaker = init();
+    assert_eq!(
+        speaker.try_current_voice(),
+        Some(speaker.get_current_voice())
+    );
+}
+
+#[test]
+fn klatt() -> espeakng::Result<()> {
+    let variants = espeakng::Speaker::klatt_variants();
+    assert!(variants.contains(&espeakng::KlattVariant::Klatt));
+
+    let mut speaker = init();
+    let normal = speaker.synthesize("Hello world")?;
+
+    speaker.set_klatt_variant(espeakng::KlattVariant::Klatt)?;
+    let klatt = speaker.synthesize("Hello world");
+    speaker.set_voice_raw(espeakng::Speaker::DEFAULT_VOICE)?;
+
+    assert_ne!(klatt?, normal);
+    Ok(())
+}
+
+#[test]
+fn speech_player() -> espeakng::Result<()> {
+    let mut speaker = init();
+    if speaker.speech_player_available() {
+        speaker.set_speech_player()?;
+        let audio = speaker.synthesize("Hello world");
+        speaker.set_voice_raw(espeakng::Speaker::DEFAULT_VOICE)?;
+        assert!(!audio?.is_empty());
+    } else {
+        assert!(matches!(
+            speaker.set_speech_player(),
+            Err(espeakng::Error::ESpeakNg {
+                code: espeakng::ESpeakNgError::NotSupported,
+                ..
+            })
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn voice_details() -> espeakng::Result<()> {
+    let details = espeakng::VoiceDetails::parse(
+        "name Test // A comment\nlanguage en 2\ngender female 30\npitch 80 120\nformant 1 90 100 110 5\ntone 600 170\n",
+    );
+
+    assert_eq!(details.name.as_deref(), Some("Test"));
+    assert_eq!(details.languages[0].priority, 2);
+    assert_eq!(details.gender, Some(espeakng::Gender::Female));
+    assert_eq!(details.age, Some(30));
+    assert_eq!(details.pitch, Some((80, 120)));
+    assert_eq!(details.formants[0].frequency_add, Some(5));
+    assert_eq!(details.other[0].0, "tone");
+
+    let voice = init().get_current_voice();
+    let details = espeakng::VoiceDetails::for_voice(&voice)?;
+    assert!(!details.languages.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn voice_attributes() -> espeakng::Result<()> {
+    let variant = espeakng::VoiceDetails {
+        pitch: Some((120, 180)),
+        formants: vec![espeakng::Formant {
+            index: 1,
+            frequency: 110,
+            height: 100,
+            width: 100,
+            frequency_add: None,
+        }],
+        roughness: Some(4),
+        ..Default::default()
+    };
+    assert_eq!(espeakng::VoiceDetails::parse(&variant.to_string()), variant);
+
+    let mut speaker = init();
+    let normal = speaker.synthesize("Hello world")?;
+
+    speaker.set_voice_attributes(&variant)?;
+    let custom = speaker.synthesize("Hello world");
+    speaker.set_voice_raw(espeakng::Speaker::DEFAULT_VOICE)?;
+
+    assert_ne!(custom?, normal);
+    Ok(())
+}
+
+#[test]
+fn voice_gender() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let normal = speaker.synthesize("Hello world")?;
+
+    speaker.set_voice_gender(espeakng::Gender::Female)?;
+    let female = speaker.synthesize("Hello world");
+    speaker.set_voice_raw(espeakng::Speaker::DEFAULT_VOICE)?;
+
+    assert_ne!(female?, normal);
+    Ok(())
+}
+
+#[test]
+fn voice_runs() -> espeakng::Result<()> {
+    let mut speaker = init();
+    let voices = espeakng::Speaker::get_voices();
+    let french = voices.iter().find(|v| v.filename == "roa/fr").unwrap();
+
+    let runs = [
+        espeakng::VoiceRun {
+            text: "Hello world.",
+            voice: None,
+        },
+        espeakng::VoiceRun {
+            text: "Bonjour le monde.",
+            voice: Some(french.clone()),
+        },
+    ];
+
+    assert!(!speaker.synthesize_runs(&runs)?.is_empty());
+    let options = espeakng::PhonemeGenOptions::Standard {
+        text_mode: espeakng::TextMode::Utf8,
+        phoneme_mode: espeakng::PhonemeMode::empty(),
+    };
+    let phonemes = speaker.text_to_phonemes_runs(&runs, &options)?;
+    assert!(!phonemes.is_empty());
+
+    assert_eq!(
+        speaker.get_current_voice().filename,
+        espeakng::Speaker::DEFAULT_VOICE
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "detect-language")]
+fn split_language_runs() {
+    let mut speaker = init();
+    let runs = speaker.split_language_runs(
+        "The weather is lovely today, so we are going to the park. \
+        Je ne sais pas pourquoi il fait si froid ce matin dans la maison.",
+    );
+
+    assert_eq!(runs.len(), 2);
+    assert!(runs[0].voice.as_ref().unwrap().filename.ends_with("en"));
+    assert!(runs[1].voice.as_ref().unwrap().filename.ends_with("fr"));
+}
+
+#[test]
+fn info() {
+    let _speaker = init();
+    let info = espeakng::Speaker::info();
+
+    assert!(info.is_at_least(espeakng::Version::new(1, 49, 0)));
+    assert!(info.data_path.join("phontab").exists());
+    assert_eq!(info.data_version, Some(espeakng::Version::new(1, 48, 1)));
+}
+
+#[test]
+fn capabilities() {
+    let mut speaker = init();
+    let capabilities = speaker.capabilities();
+
+    assert!(capabilities.ssml);
+    assert_eq!(capabilities.sonic, speaker.sonic_available());
+    assert_eq!(
+        capabilities.speech_player,
+        speaker.speech_player_available()
+    );
+}
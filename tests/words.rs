@@ -0,0 +1,25 @@
+use espeakng::WordReplacements;
+
+#[test]
+fn replacements() {
+    let words: WordReplacements = [("GIF", "jif"), ("espeak", "ee speak")]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        words.apply("A gif, made with eSpeak's help."),
+        "A jif, made with eSpeak's help."
+    );
+    assert_eq!(words.apply("Espeak (GIF)"), "ee speak (jif)");
+    assert_eq!(words.apply("GIFs"), "GIFs");
+}
+
+#[test]
+fn profanity() {
+    let mut words = WordReplacements::new();
+    words.mask_profanity();
+    assert_eq!(words.apply("Oh shit, Scunthorpe!"), "Oh bleep, Scunthorpe!");
+
+    words.set_mask("");
+    words.mask("heck");
+    assert_eq!(words.apply("what the Heck"), "what the ");
+}